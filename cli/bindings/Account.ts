@@ -1,4 +1,37 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccountKind } from "./AccountKind";
+import type { Money } from "./Money";
 import type { ReadTransactionOptions } from "./ReadTransactionOptions";
+import type { TransactionSort } from "./TransactionSort";
 
-export type Account = { id: { tb: string, id: { String: string }}, name: string, currency: string, filter: ReadTransactionOptions, };
+export type Account = { 
+/**
+ * Filter of the transactions of the account. Not part of the flattened data, since
+ * the record ids of the filter cannot be deserialized from flattened fields.
+ */
+filter: ReadTransactionOptions, id: { tb: string, id: { String: string }}, name: string, currency: string, 
+/**
+ * Sort of the transactions of the account.
+ */
+sort: TransactionSort, kind: AccountKind, 
+/**
+ * Archived accounts are hidden from account lists unless requested.
+ */
+archived: boolean, institution?: string, 
+/**
+ * Account number or IBAN.
+ */
+number?: string, color?: string, icon?: string, 
+/**
+ * Position of the account in account lists, lowest first.
+ */
+position: number, 
+/**
+ * Balance of the account before its first transaction.
+ */
+opening_balance: Money, 
+/**
+ * Date of the opening balance. The opening balance is not counted in balances
+ * computed before this date.
+ */
+opening_date?: string, };
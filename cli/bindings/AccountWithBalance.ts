@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { Account } from "./Account";
+import type { Money } from "./Money";
 
-export type AccountWithBalance = { account: Account, balance: number, };
+export type AccountWithBalance = { account: Account, balance: Money, };
@@ -1,3 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccountKind } from "./AccountKind";
 
-export type AddAccountOptions = { name: string, currency: string, };
+export type AddAccountOptions = { name: string, currency: string, kind: AccountKind, };
@@ -1,4 +1,19 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+import type { Split } from "./Split";
 import type { Tag } from "./Tag";
 
-export type AddTransactionOptions = { amount: number, category?: { tb: string, id: { String: string }}, description: string, tags: Array<Tag>, date?: string, };
+export type AddTransactionOptions = { amount: Money, category?: { tb: string, id: { String: string }}, description: string, tags: Array<Tag>, date?: string, 
+/**
+ * Splits of the transaction, which must add up to `amount`.
+ */
+splits: Array<Split>, 
+/**
+ * Identifier of the transaction in the file it is imported from, used to
+ * skip transactions that were already imported.
+ */
+external_id?: string, 
+/**
+ * Payee of the transaction, found from the description if not given.
+ */
+payee?: { tb: string, id: { String: string }}, };
@@ -1,4 +1,19 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
 import type { Tag } from "./Tag";
 
-export type AddTransactionTransferOptions = { description: string, amount: number, date?: string, tags: Array<Tag>, from: { tb: string, id: { String: string }}, to: { tb: string, id: { String: string }}, };
+export type AddTransactionTransferOptions = { description: string, 
+/**
+ * Amount sent from the `from` account, in its currency.
+ */
+amount: Money, 
+/**
+ * Amount received on the `to` account, in its currency. Defaults to `amount`
+ * converted with the exchange rate at the date of the transfer, rounded to the
+ * minor unit of the currency.
+ */
+received?: Money, 
+/**
+ * Fee paid on the `from` account on top of `amount`, in its currency.
+ */
+fee?: Money, date?: string, tags: Array<Tag>, from: { tb: string, id: { String: string }}, to: { tb: string, id: { String: string }}, };
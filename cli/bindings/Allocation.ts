@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { CategoryWithId } from "./CategoryWithId";
+import type { Money } from "./Money";
 
-export type Allocation = { id: { tb: string, id: { String: string }}, name: string, amount: number, category: CategoryWithId, partition: { tb: string, id: { String: string }}, };
+export type Allocation = { id: { tb: string, id: { String: string }}, name: string, amount: Money, category: CategoryWithId, partition: { tb: string, id: { String: string }}, };
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { CategoryWithId } from "./CategoryWithId";
+import type { Money } from "./Money";
 
-export type AllocationGroup = { total: number, category: CategoryWithId, partition: { tb: string, id: { String: string }}, };
+export type AllocationGroup = { total: Money, category: CategoryWithId, partition: { tb: string, id: { String: string }}, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Columns holding the amount of the transactions. Columns start at 0.
+ */
+export type AmountColumns = { "type": "Single", "data": { column: number, 
+/**
+ * Expenses are positive and income is negative, like on some credit card exports.
+ */
+inverted: boolean, } } | { "type": "DebitCredit", "data": { debit: number, credit: number, } };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ApplyRulesOptions = { 
+/**
+ * Only apply the rules to the transactions of this account.
+ */
+account?: { tb: string, id: { String: string }}, start: string, end: string, 
+/**
+ * Only return the changes, without updating the transactions.
+ */
+dry_run: boolean, };
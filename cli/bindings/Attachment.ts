@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Attachment = { id: { tb: string, id: { String: string }}, transaction: { tb: string, id: { String: string }}, 
+/**
+ * Name of the file when it was attached.
+ */
+name: string, 
+/**
+ * SHA-256 hash of the content, hex encoded.
+ */
+hash: string, mime: string, 
+/**
+ * Size of the file, in bytes.
+ */
+size: number, added: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+
+/**
+ * Balance of an account read on a statement at the end of a day.
+ */
+export type BalanceAssertion = { id: { tb: string, id: { String: string }}, account: { tb: string, id: { String: string }}, date: string, balance: Money, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+
+/**
+ * Balance of a statement compared to the balance of the account after the import.
+ */
+export type BalanceCheck = { date: string, 
+/**
+ * Balance written on the statement.
+ */
+expected: Money, 
+/**
+ * Balance of the account at the end of the day.
+ */
+computed: Money, 
+/**
+ * Computed balance minus the expected balance.
+ */
+difference: Money, };
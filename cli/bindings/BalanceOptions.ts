@@ -1,3 +1,33 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Status } from "./Status";
 
-export type BalanceOptions = { period_start?: string, period_end?: string, tag?: string, };
+export type BalanceOptions = { 
+/**
+ * Only count transactions dated on or after this date.
+ */
+period_start?: string, 
+/**
+ * Only count transactions dated on or before this date.
+ */
+period_end?: string, 
+/**
+ * Get the balance at the end of this day, the day included.
+ */
+as_of?: string, 
+/**
+ * Only count transactions with at least one of these tags.
+ */
+tags: Array<string>, 
+/**
+ * Only count transactions of this category or of its sub-categories. Only the
+ * matching splits of split transactions are counted.
+ */
+category?: { tb: string, id: { String: string }}, 
+/**
+ * Ignore transfers between accounts and internal movements.
+ */
+exclude_internal_movements: boolean, 
+/**
+ * Only count transactions in one of these states. All transactions are counted if empty.
+ */
+statuses: Array<Status>, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Tag } from "./Tag";
+
+/**
+ * A category suggested for a description.
+ */
+export type CategorySuggestion = { category: { tb: string, id: { String: string }}, 
+/**
+ * Probability of the category, between 0 and 1.
+ */
+confidence: number, 
+/**
+ * Tags of at least half of the past transactions of the category sharing a word
+ * with the description.
+ */
+tags: Array<Tag>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ConversionOptions = { 
+/**
+ * Currency to report amounts in.
+ */
+currency: string, 
+/**
+ * Date of the rates to use, defaults to now.
+ */
+date?: string, };
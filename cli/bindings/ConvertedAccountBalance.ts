@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Account } from "./Account";
+import type { Money } from "./Money";
+
+export type ConvertedAccountBalance = { converted_balance: Money, account: Account, balance: Money, };
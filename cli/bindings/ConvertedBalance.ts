@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConvertedAccountBalance } from "./ConvertedAccountBalance";
+import type { Money } from "./Money";
+
+/**
+ * A currency balance reported in another currency.
+ */
+export type ConvertedBalance = { currency: string, total_balance: Money, 
+/**
+ * Currency the balance is reported in.
+ */
+reporting_currency: string, rate: number, converted_total_balance: Money, accounts: Array<ConvertedAccountBalance>, };
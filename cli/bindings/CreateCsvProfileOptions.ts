@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CsvOptions } from "./CsvOptions";
+
+export type CreateCsvProfileOptions = { account: { tb: string, id: { String: string }}, name: string, options: CsvOptions, };
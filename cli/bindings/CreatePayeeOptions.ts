@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CreatePayeeOptions = { name: string, aliases: Array<string>, patterns: Array<string>, category?: { tb: string, id: { String: string }}, };
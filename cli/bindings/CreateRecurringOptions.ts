@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+import type { Schedule } from "./Schedule";
+import type { Tag } from "./Tag";
+
+export type CreateRecurringOptions = { account: { tb: string, id: { String: string }}, amount: Money, category?: { tb: string, id: { String: string }}, description: string, tags: Array<Tag>, schedule: Schedule, start: string, end?: string, };
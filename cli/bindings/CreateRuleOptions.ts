@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DescriptionMatch } from "./DescriptionMatch";
+import type { Money } from "./Money";
+import type { Tag } from "./Tag";
+
+export type CreateRuleOptions = { name: string, priority: bigint, description?: DescriptionMatch, min_amount?: Money, max_amount?: Money, account?: { tb: string, id: { String: string }}, days_of_month: Array<number>, category?: { tb: string, id: { String: string }}, tags: Array<Tag>, rename?: string, };
@@ -1,3 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
 
-export type CreateSplitBudgetOptions = { name: string, income: number, currency: string, accounts: { tb: string, id: { String: string }}[], };
+export type CreateSplitBudgetOptions = { name: string, income: Money, currency: string, accounts: { tb: string, id: { String: string }}[], };
@@ -0,0 +1,31 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AmountColumns } from "./AmountColumns";
+
+/**
+ * How to read the CSV export of a bank. Columns start at 0.
+ */
+export type CsvOptions = { 
+/**
+ * Character separating the fields. Only the first character is used.
+ */
+delimiter: string, 
+/**
+ * Label of the encoding of the file, like `utf-8` or `windows-1252`.
+ */
+encoding: string, 
+/**
+ * Format of the dates, like `%d/%m/%Y`.
+ */
+date_format: string, 
+/**
+ * Character separating the decimals. Only the first character is used.
+ */
+decimal_separator: string, 
+/**
+ * Number of rows to skip at the start of the file, like headers.
+ */
+skip_rows: number, date: number, 
+/**
+ * Columns joined to build the description.
+ */
+description: Array<number>, amount: AmountColumns, };
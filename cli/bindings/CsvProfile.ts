@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CsvOptions } from "./CsvOptions";
+
+/**
+ * Options of a CSV export saved for an account.
+ */
+export type CsvProfile = { id: { tb: string, id: { String: string }}, account: { tb: string, id: { String: string }}, name: string, options: CsvOptions, };
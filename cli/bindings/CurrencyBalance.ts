@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { AccountWithBalance } from "./AccountWithBalance";
+import type { Money } from "./Money";
 
-export type CurrencyBalance = { currency: string, total_balance: number, accounts: Array<AccountWithBalance>, };
+export type CurrencyBalance = { currency: string, total_balance: Money, accounts: Array<AccountWithBalance>, };
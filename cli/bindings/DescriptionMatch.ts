@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How the description of a transaction is matched. Case is ignored.
+ */
+export type DescriptionMatch = { "type": "Contains", "data": string } | { "type": "Regex", "data": string };
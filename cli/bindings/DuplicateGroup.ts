@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TransactionWithId } from "./TransactionWithId";
+
+/**
+ * Transactions of an account that are possibly the same.
+ */
+export type DuplicateGroup = { 
+/**
+ * Transactions of the group, oldest first.
+ */
+transactions: Array<TransactionWithId>, 
+/**
+ * Highest score between two transactions of the group.
+ */
+score: number, };
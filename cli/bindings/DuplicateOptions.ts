@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type DuplicateOptions = { 
+/**
+ * Maximum number of days between the dates of two duplicates.
+ */
+max_days: number, 
+/**
+ * Score, between 0 and 1, from which transactions are duplicates. Imports skip them.
+ */
+skip_score: number, 
+/**
+ * Score, between 0 and 1, from which transactions are possible duplicates to review.
+ */
+flag_score: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Date of the entries of bank statements used as the date of the transactions.
+ */
+export type EntryDate = "Booking" | "Value";
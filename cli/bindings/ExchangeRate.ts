@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Rate to convert one unit of `base` into `quote`, valid from `date` until a newer rate
+ * for the same pair is registered.
+ */
+export type ExchangeRate = { id: { tb: string, id: { String: string }}, date: string, base: string, quote: string, rate: number, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CategoryWithId } from "./CategoryWithId";
+import type { Money } from "./Money";
+
+export type ExpensesCategory = { transactions_total: Money, category: CategoryWithId, };
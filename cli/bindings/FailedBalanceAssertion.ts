@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BalanceAssertion } from "./BalanceAssertion";
+import type { Money } from "./Money";
+
+/**
+ * A balance assertion that does not match the balance computed from the transactions.
+ */
+export type FailedBalanceAssertion = { assertion: BalanceAssertion, 
+/**
+ * Balance of the account at the end of the day of the assertion.
+ */
+computed: Money, 
+/**
+ * Computed balance minus the asserted balance.
+ */
+difference: Money, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Direction of the money of a transaction.
+ */
+export type Flow = "Income" | "Expense";
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BalanceCheck } from "./BalanceCheck";
+import type { PossibleDuplicate } from "./PossibleDuplicate";
+import type { PreviewRow } from "./PreviewRow";
+import type { TransactionWithId } from "./TransactionWithId";
+
+export type ImportSummary = { imported: Array<TransactionWithId>, 
+/**
+ * Number of transactions skipped because they were already on the account.
+ */
+duplicates: number, 
+/**
+ * Imported transactions that are possibly duplicates of transactions of the account.
+ */
+possible_duplicates: Array<PossibleDuplicate>, 
+/**
+ * Rows that could not be parsed and were not imported.
+ */
+errors: Array<PreviewRow>, balances: Array<BalanceCheck>, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An exact monetary amount.
+ *
+ * Amounts are fixed-point decimals (an integer number of minor units and a scale),
+ * so adding thousands of transactions never drifts like `f64` does.
+ *
+ * An amount does not carry its currency: the currency is the one of the account or
+ * budget it belongs to. Amounts are stored as plain SurrealDB `decimal`s so that queries
+ * can still sum and compare them with `math::sum`, `>=`, etc., which would not work on
+ * an amount stored along its currency. Code mixing accounts must group amounts by
+ * currency itself, like balances and tag usage do.
+ *
+ * Serialized as a SurrealDB `decimal` when written to the database, without loss, and
+ * as a JSON number when sent to the frontend.
+ */
+export type Money = number;
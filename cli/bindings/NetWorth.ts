@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConvertedBalance } from "./ConvertedBalance";
+import type { Money } from "./Money";
+
+export type NetWorth = { currency: string, date: string, total: Money, currencies: Array<ConvertedBalance>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+
+/**
+ * An upcoming occurrence of a recurring transaction.
+ */
+export type Occurrence = { recurring: { tb: string, id: { String: string }}, account: { tb: string, id: { String: string }}, date: string, amount: Money, category: { tb: string, id: { String: string }}, description: string, };
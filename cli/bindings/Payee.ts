@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Payee = { id: { tb: string, id: { String: string }}, name: string, 
+/**
+ * Other names of the payee in bank descriptions, like `carrefour market`. The name
+ * of the payee is an alias too. Case, numbers and punctuation are ignored.
+ */
+aliases: Array<string>, 
+/**
+ * Regular expressions matching bank descriptions of the payee. Case is ignored.
+ */
+patterns: Array<string>, 
+/**
+ * Category of new transactions with the payee, unless one is chosen.
+ */
+category?: { tb: string, id: { String: string }}, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+
+/**
+ * Transactions made with a payee in one currency.
+ */
+export type PayeeSpending = { payee: { tb: string, id: { String: string }}, name: string, 
+/**
+ * Currency of the accounts of the transactions.
+ */
+currency: string, 
+/**
+ * Sum of the amounts of the transactions, negative for expenses.
+ */
+total: Money, count: number, average: Money, 
+/**
+ * Date of the first transaction.
+ */
+first: string, 
+/**
+ * Date of the last transaction.
+ */
+last: string, };
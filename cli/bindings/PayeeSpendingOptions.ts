@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PayeeSpendingOptions = { 
+/**
+ * Only get the spending of this payee.
+ */
+payee?: { tb: string, id: { String: string }}, 
+/**
+ * Only count the transactions of this account.
+ */
+account?: { tb: string, id: { String: string }}, start?: string, end?: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TransactionWithId } from "./TransactionWithId";
+
+/**
+ * Imported transaction that is possibly a duplicate of a transaction of the account.
+ */
+export type PossibleDuplicate = { transaction: TransactionWithId, existing: TransactionWithId, score: number, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PreviewRecurringOptions = { 
+/**
+ * Only preview occurrences of this account.
+ */
+account?: { tb: string, id: { String: string }}, 
+/**
+ * Last date to preview.
+ */
+until: string, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AddTransactionOptions } from "./AddTransactionOptions";
+
+/**
+ * A row of an imported file, parsed as a transaction or with the reason it
+ * could not be parsed.
+ */
+export type PreviewRow = { 
+/**
+ * Line of the row in the file, starting at 1.
+ */
+line: number, transaction?: AddTransactionOptions, error?: string, };
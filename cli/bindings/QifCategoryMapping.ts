@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Category of the application to use for a category name of QIF files.
+ */
+export type QifCategoryMapping = { 
+/**
+ * Name of the category in QIF files, like `Food:Groceries`.
+ */
+name: string, category: { tb: string, id: { String: string }}, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How to read or write a QIF file.
+ */
+export type QifOptions = { 
+/**
+ * Label of the encoding of the file, like `utf-8` or `windows-1252`.
+ */
+encoding: string, 
+/**
+ * Format of the dates, like `%m/%d/%Y`. Quicken's `'` year separator is read as `/`.
+ */
+date_format: string, 
+/**
+ * Character separating the decimals. Only the first character is used.
+ */
+decimal_separator: string, };
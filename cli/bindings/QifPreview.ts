@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PreviewRow } from "./PreviewRow";
+
+/**
+ * Transactions read from a QIF file.
+ */
+export type QifPreview = { rows: Array<PreviewRow>, 
+/**
+ * Categories of the file that match no category, imported as `other`. They can be
+ * mapped with [`set_category_mapping`] before importing.
+ */
+unmapped: Array<string>, };
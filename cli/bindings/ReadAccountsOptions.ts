@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccountKind } from "./AccountKind";
+
+export type ReadAccountsOptions = { 
+/**
+ * List archived accounts too.
+ */
+include_archived: boolean, 
+/**
+ * Only list accounts of these kinds. All kinds are listed if empty.
+ */
+kinds: Array<AccountKind>, };
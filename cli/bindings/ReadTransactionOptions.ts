@@ -1,8 +1,32 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Flow } from "./Flow";
+import type { Money } from "./Money";
+import type { TagFilter } from "./TagFilter";
 
-export type ReadTransactionOptions = { search?: string, category?: string, start?: string, end?: string, 
+export type ReadTransactionOptions = { search?: string, 
+/**
+ * Get transactions in one of these categories, or with a split in one of them.
+ * All categories if empty.
+ */
+categories: { tb: string, id: { String: string }}[], 
+/**
+ * Also get transactions in the descendants of `categories`.
+ */
+include_subcategories: boolean, 
+/**
+ * Only get transactions left in the default `other` category, without splits.
+ */
+uncategorized: boolean, start?: string, end?: string, 
 /**
  * Get transactions dated in the last `last_x_days` days before today.
  * Cannot be combined with `start` and `end` options.
  */
-last_x_days?: number, };
+last_x_days?: number, 
+/**
+ * Minimum amount, included. Expenses have negative amounts.
+ */
+min_amount?: Money, 
+/**
+ * Maximum amount, included.
+ */
+max_amount?: Money, flow?: Flow, tags?: TagFilter, };
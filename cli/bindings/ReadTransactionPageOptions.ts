@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReadTransactionOptions } from "./ReadTransactionOptions";
+import type { TransactionSort } from "./TransactionSort";
+
+export type ReadTransactionPageOptions = { 
+/**
+ * Filter of the transactions, the filter saved on the account if not set.
+ */
+filter?: ReadTransactionOptions, 
+/**
+ * Sort of the transactions, the sort saved on the account if not set.
+ */
+sort?: TransactionSort, 
+/**
+ * Number of transactions to skip.
+ */
+offset: number, 
+/**
+ * Maximum number of transactions of the page, all of them if not set.
+ */
+limit?: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ReadTransferOptions = { account: { tb: string, id: { String: string }}, 
+/**
+ * Only get transfers between `account` and this account, in both directions.
+ */
+other_account?: { tb: string, id: { String: string }}, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+
+/**
+ * Reconciliation of an account against a statement.
+ */
+export type Reconciliation = { id: { tb: string, id: { String: string }}, account: { tb: string, id: { String: string }}, 
+/**
+ * Last day of the statement, included.
+ */
+statement_date: string, 
+/**
+ * Balance of the account at the end of the statement.
+ */
+statement_balance: Money, 
+/**
+ * Date the reconciliation was finished at.
+ */
+finished?: string, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+import type { Reconciliation } from "./Reconciliation";
+
+export type ReconciliationSummary = { reconciliation: Reconciliation, 
+/**
+ * Balance of the cleared and reconciled transactions up to the statement date.
+ */
+cleared_balance: Money, 
+/**
+ * Statement balance minus the cleared balance. The reconciliation can be
+ * finished once it is zero.
+ */
+difference: Money, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+import type { Schedule } from "./Schedule";
+import type { Tag } from "./Tag";
+
+/**
+ * Template of a transaction that repeats on a schedule.
+ */
+export type Recurring = { id: { tb: string, id: { String: string }}, account: { tb: string, id: { String: string }}, amount: Money, category: { tb: string, id: { String: string }}, description: string, tags: Array<Tag>, schedule: Schedule, 
+/**
+ * Date of the first occurrence. Occurrences are created at the same time of day.
+ */
+start: string, 
+/**
+ * No occurrence is created after this date.
+ */
+end?: string, 
+/**
+ * Date of the last occurrence created as a transaction. Only later occurrences are
+ * created, so deleting or moving a created transaction does not bring it back.
+ */
+last_materialized?: string, };
@@ -0,0 +1,35 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DescriptionMatch } from "./DescriptionMatch";
+import type { Money } from "./Money";
+import type { Tag } from "./Tag";
+
+export type Rule = { id: { tb: string, id: { String: string }}, name: string, 
+/**
+ * Rules with a higher priority are applied first.
+ */
+priority: bigint, description?: DescriptionMatch, 
+/**
+ * Lowest amount of the transactions to match, included.
+ */
+min_amount?: Money, 
+/**
+ * Highest amount of the transactions to match, included.
+ */
+max_amount?: Money, account?: { tb: string, id: { String: string }}, 
+/**
+ * Days of the month of the transactions to match, any day if empty.
+ */
+days_of_month: Array<number>, 
+/**
+ * Category set on matching transactions.
+ */
+category?: { tb: string, id: { String: string }}, 
+/**
+ * Tags added to matching transactions.
+ */
+tags: Array<Tag>, 
+/**
+ * Description set on matching transactions. With a regular expression, `$1` or
+ * `${name}` are replaced by the groups it captured.
+ */
+rename?: string, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Tag } from "./Tag";
+import type { TransactionWithId } from "./TransactionWithId";
+
+/**
+ * Change of a transaction by the rules.
+ */
+export type RuleChange = { 
+/**
+ * The transaction before the change.
+ */
+transaction: TransactionWithId, category: { tb: string, id: { String: string }}, description: string, tags: Array<Tag>, };
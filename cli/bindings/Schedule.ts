@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Unit } from "./Unit";
+
+/**
+ * How often a recurring transaction occurs. Occurrences are computed from the start
+ * date of the recurring transaction, so a monthly schedule starting on the 31st
+ * falls on the last day of shorter months without drifting.
+ */
+export type Schedule = { "type": "Daily" } | { "type": "Weekly" } | { "type": "Monthly", "data": { day: number, } } | { "type": "LastBusinessDay" } | { "type": "Yearly" } | { "type": "Every", "data": { interval: number, unit: Unit, } };
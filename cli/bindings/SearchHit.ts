@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TransactionWithId } from "./TransactionWithId";
+
+/**
+ * A transaction found by a search, with its account.
+ */
+export type SearchHit = { transaction: TransactionWithId, 
+/**
+ * Name of the account, empty if the account does not exist anymore.
+ */
+account_name: string, 
+/**
+ * Currency of the account, empty if the account does not exist anymore.
+ */
+currency: string, };
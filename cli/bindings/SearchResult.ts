@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SearchHit } from "./SearchHit";
+
+/**
+ * A page of the transactions found by a search.
+ */
+export type SearchResult = { hits: Array<SearchHit>, 
+/**
+ * Number of transactions found, on all pages.
+ */
+total: number, };
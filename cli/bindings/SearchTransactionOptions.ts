@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReadTransactionOptions } from "./ReadTransactionOptions";
+import type { TransactionSort } from "./TransactionSort";
+
+export type SearchTransactionOptions = { filter: ReadTransactionOptions, 
+/**
+ * Accounts to search, all of them if empty.
+ */
+accounts: { tb: string, id: { String: string }}[], sort: TransactionSort, 
+/**
+ * Number of transactions to skip.
+ */
+offset: number, 
+/**
+ * Maximum number of transactions of the page, all of them if not set.
+ */
+limit?: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Field transactions are sorted by.
+ */
+export type SortKey = "Date" | "Amount" | "Description" | "Category";
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+import type { Tag } from "./Tag";
+
+/**
+ * Part of a split transaction.
+ */
+export type Split = { category: { tb: string, id: { String: string }}, amount: Money, description: string, tags: Array<Tag>, };
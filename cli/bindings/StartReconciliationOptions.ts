@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+
+export type StartReconciliationOptions = { account: { tb: string, id: { String: string }}, statement_date: string, statement_balance: Money, };
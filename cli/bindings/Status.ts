@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Reconciliation state of a transaction against the statements of its account.
+ */
+export type Status = "Pending" | "Cleared" | "Reconciled";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Filter on the labels of the tags of transactions.
+ */
+export type TagFilter = { "type": "Any", "data": Array<string> } | { "type": "All", "data": Array<string> } | { "type": "Untagged" };
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Tag } from "./Tag";
+import type { TagTotal } from "./TagTotal";
+
+export type TagUsage = { tag: Tag, 
+/**
+ * Number of transactions with the tag, or with a split with the tag.
+ */
+count: number, 
+/**
+ * Sum of the amounts of the transactions and splits with the tag, for each currency.
+ */
+totals: Array<TagTotal>, last_used?: string, };
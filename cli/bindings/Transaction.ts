@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
 import type { Tag } from "./Tag";
 
-export type Transaction = { date: string, amount: number, description: string, tags: Array<Tag>, };
+export type Transaction = { date: string, amount: Money, description: string, tags: Array<Tag>, };
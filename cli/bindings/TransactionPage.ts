@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+import type { TransactionWithId } from "./TransactionWithId";
+
+/**
+ * A page of the transactions of an account.
+ */
+export type TransactionPage = { transactions: Array<TransactionWithId>, 
+/**
+ * Number of transactions matching the filter, on all pages.
+ */
+total: number, 
+/**
+ * Sum of the amounts of the transactions matching the filter, on all pages.
+ */
+sum: Money, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SortDirection } from "./SortDirection";
+import type { SortKey } from "./SortKey";
+
+export type TransactionSort = { key: SortKey, direction: SortDirection, };
@@ -1,4 +1,24 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+import type { Split } from "./Split";
+import type { Status } from "./Status";
 import type { Tag } from "./Tag";
 
-export type TransactionWithId = { id: { tb: string, id: { String: string }}, account: { tb: string, id: { String: string }}, category: { tb: string, id: { String: string }}, date: string, amount: number, description: string, tags: Array<Tag>, };
+export type TransactionWithId = { id: { tb: string, id: { String: string }}, account: { tb: string, id: { String: string }}, category: { tb: string, id: { String: string }}, 
+/**
+ * Set on both transactions of a transfer between two accounts.
+ */
+transfer?: { tb: string, id: { String: string }}, 
+/**
+ * Recurring transaction this transaction was created from.
+ */
+recurring?: { tb: string, id: { String: string }}, 
+/**
+ * Parts of the transaction attributed to their own category. When not empty,
+ * the category of the transaction is ignored by budgets.
+ */
+splits: Array<Split>, status: Status, 
+/**
+ * Identifier of the transaction in the file it was imported from.
+ */
+external_id?: string, payee?: { tb: string, id: { String: string }}, date: string, amount: Money, description: string, tags: Array<Tag>, };
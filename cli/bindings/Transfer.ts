@@ -0,0 +1,32 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Money } from "./Money";
+import type { TransactionWithId } from "./TransactionWithId";
+
+/**
+ * Links both transactions of a transfer between two accounts.
+ */
+export type Transfer = { id: { tb: string, id: { String: string }}, from: { tb: string, id: { String: string }}, to: { tb: string, id: { String: string }}, 
+/**
+ * Amount sent, in the currency of the `from` account, without the fee.
+ */
+sent: Money, 
+/**
+ * Amount received, in the currency of the `to` account.
+ */
+received: Money, 
+/**
+ * Fee paid on the `from` account on top of the sent amount.
+ */
+fee: Money, 
+/**
+ * Implied exchange rate, `received / sent`.
+ */
+rate: number, 
+/**
+ * Transaction on the `from` account.
+ */
+from_transaction: TransactionWithId, 
+/**
+ * Transaction on the `to` account.
+ */
+to_transaction: TransactionWithId, };
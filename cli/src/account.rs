@@ -1,11 +1,53 @@
-use crate::{transaction::ReadTransactionOptions, Error};
+use crate::{
+    money::Money,
+    transaction::{ReadTransactionOptions, Status, TransactionSort},
+    Error,
+};
 use surrealdb::{engine::local::Db, RecordId, Surreal};
 
+pub mod assertion;
+pub mod reconciliation;
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AccountKind {
+    #[default]
+    Checking,
+    Savings,
+    CreditCard,
+    Cash,
+    Loan,
+    Investment,
+    Asset,
+}
+
 #[derive(ts_rs::TS, Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Data {
     pub name: String,
     pub currency: String,
-    pub filter: ReadTransactionOptions,
+    /// Sort of the transactions of the account.
+    pub sort: TransactionSort,
+    pub kind: AccountKind,
+    /// Archived accounts are hidden from account lists unless requested.
+    pub archived: bool,
+    #[ts(optional)]
+    pub institution: Option<String>,
+    /// Account number or IBAN.
+    #[ts(optional)]
+    pub number: Option<String>,
+    #[ts(optional)]
+    pub color: Option<String>,
+    #[ts(optional)]
+    pub icon: Option<String>,
+    /// Position of the account in account lists, lowest first.
+    pub position: u32,
+    /// Balance of the account before its first transaction.
+    pub opening_balance: Money,
+    /// Date of the opening balance. The opening balance is not counted in balances
+    /// computed before this date.
+    #[ts(as = "Option<String>", optional)]
+    pub opening_date: Option<surrealdb::Datetime>,
 }
 
 #[derive(ts_rs::TS)]
@@ -14,6 +56,9 @@ pub struct Data {
 pub struct Account {
     #[serde(flatten)]
     pub data: Data,
+    /// Filter of the transactions of the account. Not part of the flattened data, since
+    /// the record ids of the filter cannot be deserialized from flattened fields.
+    pub filter: ReadTransactionOptions,
     #[ts(type = "{ tb: string, id: { String: string }}")]
     pub id: RecordId,
 }
@@ -33,14 +78,39 @@ impl Account {
         db: &Surreal<Db>,
         name: impl Into<String>,
         currency: impl Into<String>,
+        kind: AccountKind,
     ) -> Result<Option<Self>, Error> {
-        db.create("account")
-            .content(Data {
-                name: name.into(),
-                currency: currency.into(),
-                filter: ReadTransactionOptions::default(),
-            })
-            .await
+        // New accounts are listed after the existing ones.
+        let position: Option<u32> = db
+            .query("RETURN (math::max((SELECT VALUE position FROM account)) ?? -1) + 1")
+            .await?
+            .take(0)?;
+
+        let query = r#"
+        LET $account = CREATE ONLY account CONTENT $data;
+        UPDATE $account.id SET filter = $filter;"#;
+
+        db.query(query)
+            .bind((
+                "data",
+                Data {
+                    name: name.into(),
+                    currency: currency.into(),
+                    sort: TransactionSort::default(),
+                    kind,
+                    archived: false,
+                    institution: None,
+                    number: None,
+                    color: None,
+                    icon: None,
+                    position: position.unwrap_or_default(),
+                    opening_balance: Money::ZERO,
+                    opening_date: None,
+                },
+            ))
+            .bind(("filter", ReadTransactionOptions::default()))
+            .await?
+            .take(1)
             .map_err(core::convert::Into::into)
     }
 }
@@ -51,10 +121,13 @@ impl Account {
 pub struct AddAccountOptions {
     pub name: String,
     pub currency: String,
+    #[serde(default)]
+    pub kind: AccountKind,
 }
 
 pub async fn create(db: &Surreal<Db>, options: AddAccountOptions) -> Result<Account, Error> {
-    let account: Option<Account> = Account::new(db, options.name, options.currency).await?;
+    let account: Option<Account> =
+        Account::new(db, options.name, options.currency, options.kind).await?;
 
     // Note: could probably expect here, because the create function does not change
     //       the return value of the CREATE statement.
@@ -72,33 +145,80 @@ pub async fn read(db: &Surreal<Db>, account_id: RecordId) -> Result<Account, Err
 }
 
 pub async fn update(db: &Surreal<Db>, account: Account) -> Result<(), surrealdb::Error> {
-    let _: Option<crate::Record> = db
-        .update(("account", account.id.key().clone()))
-        .merge(account)
-        .await?;
+    let query = r#"
+    UPDATE $account MERGE $data;
+    UPDATE $account SET filter = $filter;"#;
+
+    db.query(query)
+        .bind(("filter", account.filter))
+        .bind(("account", account.id))
+        .bind(("data", account.data))
+        .await?
+        .check()?;
 
     Ok(())
 }
 
 pub async fn delete(db: &Surreal<Db>, account_id: RecordId) -> Result<(), surrealdb::Error> {
+    // Transfers from or to this account become regular transactions on the other account.
     db.query(
         r#"
+    LET $transfers = (SELECT VALUE transfer FROM transaction WHERE account = $account_id AND transfer != NONE);
+    UPDATE transaction SET transfer = NONE WHERE transfer IN $transfers;
+    DELETE transfer WHERE id IN $transfers;
     DELETE account WHERE id = $account_id;
-    DELETE transaction WHERE account = $account_id;"#,
+    DELETE attachment WHERE transaction.account = $account_id;
+    DELETE transaction WHERE account = $account_id;
+    DELETE recurring WHERE account = $account_id;
+    DELETE balance_assertion WHERE account = $account_id;
+    DELETE reconciliation WHERE account = $account_id;
+    DELETE csv_profile WHERE account = $account_id;"#,
     )
     .bind(("account_id", account_id))
     .await
     .map(|_| ())
 }
 
-pub async fn list_accounts(db: &Surreal<Db>) -> Result<Vec<AccountIdentifiers>, surrealdb::Error> {
-    db.query("SELECT name, id FROM account").await?.take(0)
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReadAccountsOptions {
+    /// List archived accounts too.
+    #[serde(default)]
+    pub include_archived: bool,
+    /// Only list accounts of these kinds. All kinds are listed if empty.
+    #[serde(default)]
+    pub kinds: Vec<AccountKind>,
+}
+
+/// Condition on the `account` table matching the accounts of the options.
+pub(crate) const ACCOUNTS_CONDITION: &str =
+    "($include_archived OR archived = false) AND (array::len($kinds) = 0 OR kind IN $kinds)";
+
+pub async fn list_accounts(
+    db: &Surreal<Db>,
+    options: ReadAccountsOptions,
+) -> Result<Vec<AccountIdentifiers>, surrealdb::Error> {
+    db.query(format!(
+        "SELECT name, id, position FROM account WHERE {ACCOUNTS_CONDITION} ORDER BY position"
+    ))
+    .bind(("include_archived", options.include_archived))
+    .bind(("kinds", options.kinds))
+    .await?
+    .take(0)
 }
 
 pub async fn list_accounts_with_details(
     db: &Surreal<Db>,
+    options: ReadAccountsOptions,
 ) -> Result<Vec<Account>, surrealdb::Error> {
-    db.select("account").await
+    db.query(format!(
+        "SELECT * FROM account WHERE {ACCOUNTS_CONDITION} ORDER BY position"
+    ))
+    .bind(("include_archived", options.include_archived))
+    .bind(("kinds", options.kinds))
+    .await?
+    .take(0)
 }
 
 pub async fn get_currency(db: &Surreal<Db>, account_id: RecordId) -> Result<String, Error> {
@@ -118,47 +238,125 @@ pub async fn get_currency(db: &Surreal<Db>, account_id: RecordId) -> Result<Stri
 #[ts(export)]
 #[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct BalanceOptions {
+    /// Only count transactions dated on or after this date.
     #[ts(as = "Option<String>", optional)]
     pub period_start: Option<surrealdb::Datetime>,
+    /// Only count transactions dated on or before this date.
     #[ts(as = "Option<String>", optional)]
     pub period_end: Option<surrealdb::Datetime>,
-    #[ts(optional)]
-    pub tag: Option<String>,
+    /// Get the balance at the end of this day, the day included.
+    #[ts(as = "Option<String>", optional)]
+    pub as_of: Option<chrono::NaiveDate>,
+    /// Only count transactions with at least one of these tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Only count transactions of this category or of its sub-categories. Only the
+    /// matching splits of split transactions are counted.
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub category: Option<RecordId>,
+    /// Ignore transfers between accounts and internal movements.
+    #[serde(default)]
+    pub exclude_internal_movements: bool,
+    /// Only count transactions in one of these states. All transactions are counted if empty.
+    #[serde(default)]
+    pub statuses: Vec<Status>,
+}
+
+/// Get the end of a day, which is the start of the next one.
+pub(crate) fn end_of_day(date: chrono::NaiveDate) -> chrono::DateTime<chrono::Utc> {
+    date.succ_opt()
+        .unwrap_or(chrono::NaiveDate::MAX)
+        .and_time(chrono::NaiveTime::MIN)
+        .and_utc()
 }
 
 pub async fn balance(
     db: &Surreal<Db>,
     account_id: RecordId,
     options: BalanceOptions,
-) -> Result<f64, Error> {
-    let mut query =
-        r#"RETURN (SELECT math::sum(amount) AS sum FROM transaction WHERE account = $account_id"#
-            .to_string();
+) -> Result<Money, Error> {
+    let account = read(db, account_id.clone()).await?;
+    let categories = match options.category.clone() {
+        Some(category) => crate::transaction::category::with_descendants(db, category).await?,
+        None => vec![],
+    };
+
+    let amount = if options.category.is_some() {
+        "IF splits THEN math::sum(splits[WHERE category IN $categories].amount) ELSE amount END"
+    } else {
+        "amount"
+    };
+
+    let mut query = format!(
+        "RETURN (SELECT math::sum({amount}) AS sum FROM transaction WHERE account = $account_id"
+    );
 
     if options.period_start.is_some() {
-        query.push_str(" AND date > $start");
+        query.push_str(" AND date >= $start");
     }
 
     if options.period_end.is_some() {
-        query.push_str(" AND date < $end");
+        query.push_str(" AND date <= $end");
+    }
+
+    if options.as_of.is_some() {
+        query.push_str(" AND date < <datetime>$before");
     }
 
-    if options.tag.is_some() {
-        query.push_str(" AND tags.find(|$tag| $tag.label = '$tag_label')");
+    if !options.tags.is_empty() {
+        query.push_str(" AND tags.label ANYINSIDE $tags");
+    }
+
+    if options.category.is_some() {
+        query.push_str(" AND (category IN $categories OR splits.category ANYINSIDE $categories)");
+    }
+
+    if !options.statuses.is_empty() {
+        query.push_str(" AND (status ?? 'Pending') IN $statuses");
+    }
+
+    if options.exclude_internal_movements {
+        query.push_str(
+            " AND transfer = NONE AND category != type::thing('category', 'internal-movements')",
+        );
     }
 
     query.push_str(" GROUP ALL).sum");
 
-    let sum: Option<f64> = db
+    let before = options.as_of.map(end_of_day).unwrap_or_default();
+
+    // The opening balance is not a transaction, it cannot match tags or categories.
+    let opening = options.tags.is_empty()
+        && options.category.is_none()
+        && match &account.data.opening_date {
+            Some(date) => {
+                options
+                    .period_start
+                    .as_ref()
+                    .is_none_or(|start| date >= start)
+                    && options.period_end.as_ref().is_none_or(|end| date <= end)
+                    && (options.as_of.is_none() || *date < surrealdb::Datetime::from(before))
+            }
+            None => options.period_start.is_none(),
+        };
+
+    let sum: Option<Money> = db
         .query(query)
         .bind(("account_id", account_id))
         .bind(("start", options.period_start.unwrap_or_default()))
         .bind(("end", options.period_end.unwrap_or_default()))
-        .bind(("tag_label", options.tag.unwrap_or_default()))
+        .bind(("before", before))
+        .bind(("tags", options.tags))
+        .bind(("categories", categories))
+        .bind(("statuses", options.statuses))
         .await?
         .take(0)?;
 
-    // Note: could probably expect here, because the query does not change
-    //       the return value of the SELECT statement.
-    Ok(sum.unwrap_or(0.0))
+    let sum = sum.unwrap_or_default();
+
+    if opening {
+        Ok(account.data.opening_balance + sum)
+    } else {
+        Ok(sum)
+    }
 }
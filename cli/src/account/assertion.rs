@@ -0,0 +1,113 @@
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    account::{balance, BalanceOptions},
+    money::Money,
+    Error,
+};
+
+/// Balance of an account read on a statement at the end of a day.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BalanceAssertion {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub id: RecordId,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub account: RecordId,
+    #[ts(as = "String")]
+    pub date: chrono::NaiveDate,
+    pub balance: Money,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, serde::Deserialize)]
+pub struct CreateBalanceAssertionOptions {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub account: RecordId,
+    #[ts(as = "String")]
+    pub date: chrono::NaiveDate,
+    pub balance: Money,
+}
+
+/// A balance assertion that does not match the balance computed from the transactions.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FailedBalanceAssertion {
+    pub assertion: BalanceAssertion,
+    /// Balance of the account at the end of the day of the assertion.
+    pub computed: Money,
+    /// Computed balance minus the asserted balance.
+    pub difference: Money,
+}
+
+pub async fn create(
+    db: &Surreal<Db>,
+    options: CreateBalanceAssertionOptions,
+) -> Result<BalanceAssertion, Error> {
+    let query = r#"
+    CREATE balance_assertion SET
+        account = $account,
+        date = $date,
+        balance = $balance"#;
+
+    let assertion: Option<BalanceAssertion> = db
+        .query(query)
+        .bind(("account", options.account))
+        .bind(("date", options.date))
+        .bind(("balance", options.balance))
+        .await?
+        .take(0)?;
+
+    assertion.ok_or(Error::RecordNotFound)
+}
+
+/// List the balance assertions of an account, sorted by date.
+pub async fn read(
+    db: &Surreal<Db>,
+    account: RecordId,
+) -> Result<Vec<BalanceAssertion>, surrealdb::Error> {
+    db.query("SELECT * FROM balance_assertion WHERE account = $account ORDER BY date")
+        .bind(("account", account))
+        .await?
+        .take(0)
+}
+
+pub async fn delete(db: &Surreal<Db>, assertion: RecordId) -> Result<(), surrealdb::Error> {
+    let _: Option<BalanceAssertion> = db.delete(assertion).await?;
+
+    Ok(())
+}
+
+/// Check the balance assertions of an account against its balance and return the
+/// ones that do not match.
+pub async fn check(
+    db: &Surreal<Db>,
+    account: RecordId,
+) -> Result<Vec<FailedBalanceAssertion>, Error> {
+    let mut failed = vec![];
+
+    for assertion in read(db, account.clone()).await? {
+        let computed = balance(
+            db,
+            account.clone(),
+            BalanceOptions {
+                as_of: Some(assertion.date),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        if computed != assertion.balance {
+            failed.push(FailedBalanceAssertion {
+                difference: computed - assertion.balance,
+                computed,
+                assertion,
+            });
+        }
+    }
+
+    Ok(failed)
+}
@@ -0,0 +1,256 @@
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    account::{balance, end_of_day, BalanceOptions},
+    money::Money,
+    transaction::{Status, TransactionWithId},
+    Error,
+};
+
+#[derive(Debug)]
+pub enum ReconciliationError {
+    /// The account already has an unfinished reconciliation.
+    InProgress(RecordId),
+    /// The reconciliation is finished and cannot be changed anymore.
+    Finished(RecordId),
+    /// The transaction does not belong to the account of the reconciliation.
+    OtherAccount(RecordId),
+    /// The cleared balance does not match the balance of the statement.
+    Unbalanced { difference: Money },
+    /// The transaction is reconciled and can only be changed if forced.
+    Reconciled(RecordId),
+}
+
+impl std::fmt::Display for ReconciliationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReconciliationError::InProgress(id) => {
+                write!(f, "reconciliation {id} is not finished")
+            }
+            ReconciliationError::Finished(id) => write!(f, "reconciliation {id} is finished"),
+            ReconciliationError::OtherAccount(id) => {
+                write!(f, "transaction {id} belongs to another account")
+            }
+            ReconciliationError::Unbalanced { difference } => {
+                write!(f, "cleared balance is off by {difference}")
+            }
+            ReconciliationError::Reconciled(id) => write!(f, "transaction {id} is reconciled"),
+        }
+    }
+}
+
+/// Reconciliation of an account against a statement.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Reconciliation {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub id: RecordId,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub account: RecordId,
+    /// Last day of the statement, included.
+    #[ts(as = "String")]
+    pub statement_date: chrono::NaiveDate,
+    /// Balance of the account at the end of the statement.
+    pub statement_balance: Money,
+    /// Date the reconciliation was finished at.
+    #[ts(as = "Option<String>", optional)]
+    pub finished: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, serde::Deserialize)]
+pub struct StartReconciliationOptions {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub account: RecordId,
+    #[ts(as = "String")]
+    pub statement_date: chrono::NaiveDate,
+    pub statement_balance: Money,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReconciliationSummary {
+    pub reconciliation: Reconciliation,
+    /// Balance of the cleared and reconciled transactions up to the statement date.
+    pub cleared_balance: Money,
+    /// Statement balance minus the cleared balance. The reconciliation can be
+    /// finished once it is zero.
+    pub difference: Money,
+}
+
+/// Start the reconciliation of an account. Only one reconciliation per account can be
+/// in progress.
+pub async fn start(
+    db: &Surreal<Db>,
+    options: StartReconciliationOptions,
+) -> Result<ReconciliationSummary, Error> {
+    let query = r#"
+    RETURN (SELECT VALUE id FROM reconciliation
+        WHERE account = $account AND finished = NONE)[0]"#;
+
+    let in_progress: Option<RecordId> = db
+        .query(query)
+        .bind(("account", options.account.clone()))
+        .await?
+        .take(0)?;
+
+    if let Some(reconciliation) = in_progress {
+        return Err(Error::Reconciliation(ReconciliationError::InProgress(
+            reconciliation,
+        )));
+    }
+
+    let query = r#"
+    CREATE reconciliation SET
+        account = $account,
+        statement_date = $statement_date,
+        statement_balance = $statement_balance"#;
+
+    let reconciliation: Option<Reconciliation> = db
+        .query(query)
+        .bind(("account", options.account))
+        .bind(("statement_date", options.statement_date))
+        .bind(("statement_balance", options.statement_balance))
+        .await?
+        .take(0)?;
+
+    summarize(db, reconciliation.ok_or(Error::RecordNotFound)?).await
+}
+
+/// List the reconciliations of an account, sorted by statement date.
+pub async fn read(
+    db: &Surreal<Db>,
+    account: RecordId,
+) -> Result<Vec<Reconciliation>, surrealdb::Error> {
+    db.query("SELECT * FROM reconciliation WHERE account = $account ORDER BY statement_date")
+        .bind(("account", account))
+        .await?
+        .take(0)
+}
+
+async fn read_one(db: &Surreal<Db>, reconciliation: RecordId) -> Result<Reconciliation, Error> {
+    let reconciliation: Option<Reconciliation> = db.select(reconciliation).await?;
+
+    reconciliation.ok_or(Error::RecordNotFound)
+}
+
+async fn read_in_progress(
+    db: &Surreal<Db>,
+    reconciliation: RecordId,
+) -> Result<Reconciliation, Error> {
+    let reconciliation = read_one(db, reconciliation).await?;
+
+    match reconciliation.finished {
+        Some(_) => Err(Error::Reconciliation(ReconciliationError::Finished(
+            reconciliation.id,
+        ))),
+        None => Ok(reconciliation),
+    }
+}
+
+async fn summarize(
+    db: &Surreal<Db>,
+    reconciliation: Reconciliation,
+) -> Result<ReconciliationSummary, Error> {
+    let cleared_balance = balance(
+        db,
+        reconciliation.account.clone(),
+        BalanceOptions {
+            as_of: Some(reconciliation.statement_date),
+            statuses: vec![Status::Cleared, Status::Reconciled],
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    Ok(ReconciliationSummary {
+        difference: reconciliation.statement_balance - cleared_balance,
+        cleared_balance,
+        reconciliation,
+    })
+}
+
+/// Get the cleared balance and the remaining difference of a reconciliation.
+pub async fn summary(
+    db: &Surreal<Db>,
+    reconciliation: RecordId,
+) -> Result<ReconciliationSummary, Error> {
+    summarize(db, read_one(db, reconciliation).await?).await
+}
+
+/// Mark a pending transaction as cleared, or a cleared transaction as pending.
+pub async fn toggle(
+    db: &Surreal<Db>,
+    reconciliation: RecordId,
+    transaction: RecordId,
+) -> Result<ReconciliationSummary, Error> {
+    let reconciliation = read_in_progress(db, reconciliation).await?;
+    let transaction: TransactionWithId =
+        db.select(transaction).await?.ok_or(Error::RecordNotFound)?;
+
+    if transaction.account != reconciliation.account {
+        return Err(Error::Reconciliation(ReconciliationError::OtherAccount(
+            transaction.id,
+        )));
+    }
+
+    let status = match transaction.status {
+        Status::Pending => Status::Cleared,
+        Status::Cleared => Status::Pending,
+        Status::Reconciled => {
+            return Err(Error::Reconciliation(ReconciliationError::Reconciled(
+                transaction.id,
+            )))
+        }
+    };
+
+    db.query("UPDATE $transaction SET status = $status")
+        .bind(("transaction", transaction.id))
+        .bind(("status", status))
+        .await?
+        .check()?;
+
+    summarize(db, reconciliation).await
+}
+
+/// Finish a reconciliation once its difference is zero. Cleared transactions up to the
+/// statement date become reconciled.
+pub async fn finish(
+    db: &Surreal<Db>,
+    reconciliation: RecordId,
+) -> Result<ReconciliationSummary, Error> {
+    let current = summarize(db, read_in_progress(db, reconciliation).await?).await?;
+
+    if !current.difference.is_zero() {
+        return Err(Error::Reconciliation(ReconciliationError::Unbalanced {
+            difference: current.difference,
+        }));
+    }
+
+    let query = r#"
+    BEGIN TRANSACTION;
+    UPDATE transaction SET status = "Reconciled"
+        WHERE account = $account AND status = "Cleared" AND date < <datetime>$before;
+    UPDATE $reconciliation SET finished = time::now();
+    COMMIT TRANSACTION;"#;
+
+    db.query(query)
+        .bind(("account", current.reconciliation.account.clone()))
+        .bind(("before", end_of_day(current.reconciliation.statement_date)))
+        .bind(("reconciliation", current.reconciliation.id.clone()))
+        .await?
+        .check()?;
+
+    summary(db, current.reconciliation.id).await
+}
+
+/// Cancel a reconciliation in progress. Cleared transactions stay cleared.
+pub async fn cancel(db: &Surreal<Db>, reconciliation: RecordId) -> Result<(), Error> {
+    let reconciliation = read_in_progress(db, reconciliation).await?;
+    let _: Option<Reconciliation> = db.delete(reconciliation.id).await?;
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use crate::account::Account;
+use crate::money::Money;
 use crate::{ChronoLocalResultError, Error};
 use chrono::offset::LocalResult;
 use chrono::{DateTime, Datelike, Utc};
@@ -78,7 +79,7 @@ pub struct Budget {
     #[ts(type = "{ tb: string, id: { String: string }}")]
     pub id: surrealdb::RecordId,
     pub name: String,
-    pub income: f64,
+    pub income: Money,
     pub currency: String,
     pub accounts: Vec<Account>,
     pub view: View,
@@ -104,7 +105,7 @@ pub async fn list(db: &Surreal<Db>) -> Result<Vec<Budget>, surrealdb::Error> {
 #[derive(Debug, serde::Deserialize)]
 pub struct CreateSplitBudgetOptions {
     pub name: String,
-    pub income: f64,
+    pub income: Money,
     pub currency: String,
     #[ts(type = "{ tb: string, id: { String: string }}[]")]
     pub accounts: Vec<surrealdb::RecordId>,
@@ -150,9 +151,28 @@ pub async fn read(db: &Surreal<Db>, budget_id: surrealdb::RecordId) -> Result<Bu
 }
 
 pub async fn update(db: &Surreal<Db>, budget: Budget) -> Result<(), surrealdb::Error> {
-    let _: Option<crate::Record> = db
-        .update(("budget", budget.id.key().clone()))
-        .merge(budget)
+    let query = r#"
+    UPDATE $budget SET
+        name     = $name,
+        income   = $income,
+        currency = $currency,
+        accounts = $accounts,
+        view     = $view"#;
+
+    db.query(query)
+        .bind(("budget", budget.id))
+        .bind(("name", budget.name))
+        .bind(("income", budget.income))
+        .bind(("currency", budget.currency))
+        .bind((
+            "accounts",
+            budget
+                .accounts
+                .into_iter()
+                .map(|account| account.id)
+                .collect::<Vec<_>>(),
+        ))
+        .bind(("view", budget.view))
         .await?;
 
     Ok(())
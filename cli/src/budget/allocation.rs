@@ -1,6 +1,6 @@
 use surrealdb::{engine::local::Db, Surreal};
 
-use crate::{transaction::category::CategoryWithId, Error};
+use crate::{money::Money, transaction::category::CategoryWithId, Error};
 
 #[derive(ts_rs::TS)]
 #[ts(export)]
@@ -9,7 +9,7 @@ pub struct Allocation {
     #[ts(type = "{ tb: string, id: { String: string }}")]
     pub id: surrealdb::RecordId,
     pub name: String,
-    pub amount: f64,
+    pub amount: Money,
     pub category: CategoryWithId,
     #[ts(type = "{ tb: string, id: { String: string }}")]
     pub partition: surrealdb::RecordId,
@@ -22,7 +22,7 @@ pub struct CreateAllocationOptions {
     pub name: String,
     #[ts(type = "{ tb: string, id: { String: string }}", optional)]
     pub category: Option<surrealdb::RecordId>,
-    pub amount: f64,
+    pub amount: Money,
     #[ts(type = "{ tb: string, id: { String: string }}")]
     pub partition: surrealdb::RecordId,
 }
@@ -77,7 +77,7 @@ pub struct UpdateAllocationOptions {
     pub name: String,
     #[ts(type = "{ tb: string, id: { String: string }}")]
     pub category: surrealdb::RecordId,
-    pub amount: f64,
+    pub amount: Money,
     #[ts(type = "{ tb: string, id: { String: string }}")]
     pub partition: surrealdb::RecordId,
 }
@@ -44,7 +44,7 @@ pub async fn read(
             r#"LET $budget = (SELECT * FROM ONLY $budget_id FETCH accounts);
     RETURN SELECT * FROM transaction
     WHERE account.id in $budget.accounts.map(|$a| $a.id)
-    AND category.id = $category_id
+    AND (category.id = $category_id OR $category_id IN splits.category)
     AND date >= <datetime>$start AND date <= <datetime>$end;
         "#,
         )
@@ -55,7 +55,13 @@ pub async fn read(
         .await
         .map_err(Error::Database)?;
 
-    let transactions = response.take::<Vec<TransactionWithId>>(1)?;
+    // Only keep the splits of the category for split transactions.
+    let transactions = response
+        .take::<Vec<TransactionWithId>>(1)?
+        .iter()
+        .flat_map(TransactionWithId::by_category)
+        .filter(|transaction| transaction.category == options.category)
+        .collect();
 
     Ok(ReadCategoryResult {
         period_start: start.to_string(),
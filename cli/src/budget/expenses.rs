@@ -1,6 +1,7 @@
 use crate::budget::partition::Partition;
 use crate::budget::{reset_datetime_hms, Budget, ExpensesPeriod};
-use crate::transaction::category::CategoryWithId;
+use crate::money::Money;
+use crate::transaction::category::{self, CategoryWithId};
 use crate::transaction::TransactionWithId;
 use crate::Error;
 use surrealdb::engine::local::Db;
@@ -11,11 +12,23 @@ use surrealdb::{RecordId, Surreal};
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ExpensesAllocation {
     // Total transaction amount for the allocation.
-    pub transactions_total: f64,
+    pub transactions_total: Money,
     // Total theoretical maximum amount for the given allocation.
-    pub allocations_total: f64,
+    pub allocations_total: Money,
     pub category: CategoryWithId,
+    // Transactions of the allocation category and of its descendants that do not
+    // have their own allocation.
     pub transactions: Vec<TransactionWithId>,
+    // Total transaction amount for each category of the transactions.
+    pub categories: Vec<ExpensesCategory>,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ExpensesCategory {
+    pub transactions_total: Money,
+    pub category: CategoryWithId,
 }
 
 #[derive(ts_rs::TS)]
@@ -23,9 +36,9 @@ pub struct ExpensesAllocation {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ExpensesPartition {
     // Sums up all transactions part of the allocations of this partition.
-    pub transactions_total: f64,
+    pub transactions_total: Money,
     // Total theoretical maximum amount for the given allocations of this partition.
-    pub allocations_total: f64,
+    pub allocations_total: Money,
     pub inner: Partition,
     pub allocations: Vec<ExpensesAllocation>,
 }
@@ -35,13 +48,13 @@ pub struct ExpensesPartition {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ExpensesBudget {
     // Sums up all transactions that match all allocation of the budget.
-    pub transactions_total: f64,
+    pub transactions_total: Money,
     // Total theoretical maximum amount for the given budget. Sums up all allocations
     // of all the partitions of this budget.
-    pub allocations_total: f64,
+    pub allocations_total: Money,
     pub inner: Budget,
     pub partitions: Vec<ExpensesPartition>,
-    pub income_total: f64,
+    pub income_total: Money,
 }
 
 #[derive(ts_rs::TS)]
@@ -57,7 +70,7 @@ pub struct ReadExpensesResult {
 #[ts(export)]
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct AllocationGroup {
-    pub total: f64,
+    pub total: Money,
     pub category: CategoryWithId,
     #[ts(type = "{ tb: string, id: { String: string }}")]
     pub partition: RecordId,
@@ -108,14 +121,45 @@ pub async fn read(
         .ok_or(Error::RecordNotFound)?;
     let partitions = response.take::<Vec<Partition>>(3)?;
     let allocation_groups = response.take::<Vec<AllocationGroup>>(4)?;
-    let transactions = response.take::<Vec<TransactionWithId>>(5)?;
+    // Splits are attributed to the allocation of their own category.
+    let transactions: Vec<TransactionWithId> = response
+        .take::<Vec<TransactionWithId>>(5)?
+        .iter()
+        .flat_map(TransactionWithId::by_category)
+        .collect();
+
+    let categories = category::read(db).await?;
+    // Spending of a category goes to the allocation of the category itself, or of its
+    // closest ancestor that has one.
+    let allocation_category = |category: &RecordId| -> Option<RecordId> {
+        let mut current = Some(category);
 
-    let period_factor = match options.period {
-        ExpensesPeriod::Monthly => 1.0,
-        ExpensesPeriod::Trimestrial => 3.0,
-        ExpensesPeriod::Yearly => 12.0,
+        // Note: bounded by the number of categories in case the hierarchy has a cycle.
+        for _ in 0..=categories.len() {
+            let id = current?;
+
+            if allocation_groups
+                .iter()
+                .any(|allocation_group| &allocation_group.category.id == id)
+            {
+                return Some(id.clone());
+            }
+
+            current = categories
+                .iter()
+                .find(|category| &category.id == id)
+                .and_then(|category| category.parent.as_ref());
+        }
+
+        None
     };
 
+    let period_factor = rust_decimal::Decimal::from(match options.period {
+        ExpensesPeriod::Monthly => 1,
+        ExpensesPeriod::Trimestrial => 3,
+        ExpensesPeriod::Yearly => 12,
+    });
+
     let partitions: Vec<ExpensesPartition> = partitions
         .into_iter()
         .map(|partition: Partition| {
@@ -125,16 +169,39 @@ pub async fn read(
                 .map(|allocation_group| {
                     let transactions: Vec<TransactionWithId> = transactions
                         .iter()
-                        .filter(|&transaction| transaction.category == allocation_group.category.id)
+                        .filter(|&transaction| {
+                            allocation_category(&transaction.category).as_ref()
+                                == Some(&allocation_group.category.id)
+                        })
                         .cloned()
                         .collect();
                     let transactions_total = transactions
                         .iter()
-                        .fold(0.0, |acc, transactions| acc + transactions.inner.amount);
+                        .map(|transaction| transaction.inner.amount)
+                        .sum();
+                    let transactions_categories = categories
+                        .iter()
+                        .filter_map(|category| {
+                            let mut category_transactions = transactions
+                                .iter()
+                                .filter(|transaction| transaction.category == category.id)
+                                .peekable();
+
+                            category_transactions.peek()?;
+
+                            Some(ExpensesCategory {
+                                transactions_total: category_transactions
+                                    .map(|transaction| transaction.inner.amount)
+                                    .sum(),
+                                category: category.clone(),
+                            })
+                        })
+                        .collect();
 
                     ExpensesAllocation {
                         transactions,
                         transactions_total,
+                        categories: transactions_categories,
                         allocations_total: allocation_group.total
                         // Need to apply the period factor on each allocations instead of applying it on the total
                         // because the frontend breaks down the allocations to display details. 
@@ -147,10 +214,12 @@ pub async fn read(
             ExpensesPartition {
                 allocations_total: allocations
                     .iter()
-                    .fold(0.0, |acc, allocation| acc + allocation.allocations_total),
+                    .map(|allocation| allocation.allocations_total)
+                    .sum(),
                 transactions_total: allocations
                     .iter()
-                    .fold(0.0, |acc, allocation| acc + allocation.transactions_total),
+                    .map(|allocation| allocation.transactions_total)
+                    .sum(),
                 inner: partition,
                 allocations,
             }
@@ -163,10 +232,12 @@ pub async fn read(
         budget: ExpensesBudget {
             allocations_total: partitions
                 .iter()
-                .fold(0.0, |acc, partition| acc + partition.allocations_total),
+                .map(|partition| partition.allocations_total)
+                .sum(),
             transactions_total: partitions
                 .iter()
-                .fold(0.0, |acc, partition| acc + partition.transactions_total),
+                .map(|partition| partition.transactions_total)
+                .sum(),
             income_total: budget.income * period_factor,
             inner: budget,
             partitions,
@@ -0,0 +1,340 @@
+use rust_decimal::Decimal;
+use surrealdb::{engine::local::Db, sql::Number, RecordId, Surreal};
+
+use crate::{
+    money::Money,
+    portfolio::currency::Currency,
+    transaction::{AccountWithBalance, CurrencyBalance},
+    Error,
+};
+
+/// Rate to convert one unit of `base` into `quote`, valid from `date` until a newer rate
+/// for the same pair is registered.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ExchangeRate {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub id: RecordId,
+    #[ts(as = "String")]
+    pub date: chrono::DateTime<chrono::Utc>,
+    pub base: String,
+    pub quote: String,
+    #[ts(type = "number")]
+    #[serde(with = "crate::money::decimal")]
+    pub rate: Decimal,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, serde::Deserialize)]
+pub struct CreateExchangeRateOptions {
+    #[ts(as = "String")]
+    pub date: chrono::DateTime<chrono::Utc>,
+    pub base: String,
+    pub quote: String,
+    #[ts(type = "number")]
+    #[serde(with = "crate::money::decimal")]
+    pub rate: Decimal,
+}
+
+/// Register a rate. Replaces the rate of the same pair at the same date if it exists.
+pub async fn create(
+    db: &Surreal<Db>,
+    options: CreateExchangeRateOptions,
+) -> Result<ExchangeRate, Error> {
+    let query = r#"
+    DELETE exchange_rate WHERE base = $base AND quote = $quote AND date = <datetime>$date;
+    CREATE exchange_rate SET
+        date  = <datetime>$date,
+        base  = $base,
+        quote = $quote,
+        rate  = $rate"#;
+
+    let rate: Option<ExchangeRate> = db
+        .query(query)
+        .bind(("date", options.date))
+        .bind(("base", options.base))
+        .bind(("quote", options.quote))
+        .bind(("rate", Number::Decimal(options.rate)))
+        .await?
+        .take(1)?;
+
+    rate.ok_or(Error::RecordNotFound)
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Debug, serde::Deserialize)]
+pub struct ReadExchangeRateOptions {
+    #[ts(optional)]
+    pub base: Option<String>,
+    #[ts(optional)]
+    pub quote: Option<String>,
+}
+
+/// List registered rates, newest first.
+pub async fn read(
+    db: &Surreal<Db>,
+    options: ReadExchangeRateOptions,
+) -> Result<Vec<ExchangeRate>, Error> {
+    let mut query = "SELECT * FROM exchange_rate WHERE true".to_string();
+
+    if options.base.is_some() {
+        query.push_str(" AND base = $base");
+    }
+
+    if options.quote.is_some() {
+        query.push_str(" AND quote = $quote");
+    }
+
+    query.push_str(" ORDER BY date DESC");
+
+    Ok(db
+        .query(query)
+        .bind(("base", options.base.unwrap_or_default()))
+        .bind(("quote", options.quote.unwrap_or_default()))
+        .await?
+        .take(0)?)
+}
+
+pub async fn update(db: &Surreal<Db>, rate: ExchangeRate) -> Result<(), surrealdb::Error> {
+    let query = r#"
+    UPDATE $rate SET
+        date  = <datetime>$date,
+        base  = $base,
+        quote = $quote,
+        rate  = $value"#;
+
+    db.query(query)
+        .bind(("rate", rate.id))
+        .bind(("date", rate.date))
+        .bind(("base", rate.base))
+        .bind(("quote", rate.quote))
+        .bind(("value", Number::Decimal(rate.rate)))
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete(db: &Surreal<Db>, rate: RecordId) -> Result<(), surrealdb::Error> {
+    let _: Option<ExchangeRate> = db.delete(rate).await?;
+
+    Ok(())
+}
+
+/// Get the rate to convert `base` into `quote` valid on `date`, which is the most recent
+/// rate registered on or before that date. Falls back on the inverse of the `quote` to
+/// `base` rate if the pair was only registered the other way around.
+pub async fn rate_at(
+    db: &Surreal<Db>,
+    base: &str,
+    quote: &str,
+    date: chrono::DateTime<chrono::Utc>,
+) -> Result<Decimal, Error> {
+    if base == quote {
+        return Ok(Decimal::ONE);
+    }
+
+    let query = r#"
+    SELECT * FROM exchange_rate
+        WHERE base = $base AND quote = $quote AND date <= <datetime>$date
+        ORDER BY date DESC LIMIT 1;
+    SELECT * FROM exchange_rate
+        WHERE base = $quote AND quote = $base AND date <= <datetime>$date
+        ORDER BY date DESC LIMIT 1;"#;
+
+    let mut response = db
+        .query(query)
+        .bind(("base", base.to_string()))
+        .bind(("quote", quote.to_string()))
+        .bind(("date", date))
+        .await?;
+
+    let direct: Option<ExchangeRate> = response.take(0)?;
+    let inverse: Option<ExchangeRate> = response.take(1)?;
+
+    // Prefer the most recent of both directions.
+    let rate = match (direct, inverse) {
+        (Some(direct), Some(inverse)) if inverse.date > direct.date => {
+            Decimal::ONE.checked_div(inverse.rate)
+        }
+        (Some(direct), _) => Some(direct.rate),
+        (None, Some(inverse)) => Decimal::ONE.checked_div(inverse.rate),
+        (None, None) => None,
+    };
+
+    rate.ok_or_else(|| Error::ExchangeRateNotFound {
+        base: base.to_string(),
+        quote: quote.to_string(),
+    })
+}
+
+/// Convert an amount from one currency to another using the rate valid on `date`.
+pub async fn convert(
+    db: &Surreal<Db>,
+    amount: Money,
+    from: &str,
+    to: &str,
+    date: chrono::DateTime<chrono::Utc>,
+) -> Result<Money, Error> {
+    Ok(amount * rate_at(db, from, to, date).await?)
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ConversionOptions {
+    /// Currency to report amounts in.
+    pub currency: String,
+    /// Date of the rates to use, defaults to now.
+    #[ts(as = "Option<String>", optional)]
+    pub date: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct ConvertedAccountBalance {
+    #[serde(flatten)]
+    pub inner: AccountWithBalance,
+    pub converted_balance: Money,
+}
+
+/// A currency balance reported in another currency.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct ConvertedBalance {
+    pub currency: String,
+    pub total_balance: Money,
+    /// Currency the balance is reported in.
+    pub reporting_currency: String,
+    #[ts(type = "number")]
+    #[serde(with = "crate::money::decimal")]
+    pub rate: Decimal,
+    pub converted_total_balance: Money,
+    pub accounts: Vec<ConvertedAccountBalance>,
+}
+
+/// Convert the balances of the accounts of a currency, rounded to the minor unit of
+/// the reporting currency. The converted total is the sum of the rounded balances of
+/// the accounts, so that it matches the balances shown.
+async fn convert_balance(
+    db: &Surreal<Db>,
+    currency: String,
+    total_balance: Money,
+    accounts: Vec<AccountWithBalance>,
+    options: &ConversionOptions,
+) -> Result<ConvertedBalance, Error> {
+    let date = options.date.unwrap_or_else(chrono::Utc::now);
+    let rate = rate_at(db, &currency, &options.currency, date).await?;
+    let accounts: Vec<ConvertedAccountBalance> = accounts
+        .into_iter()
+        .map(|account| ConvertedAccountBalance {
+            converted_balance: (account.balance * rate).round_to_currency(&options.currency),
+            inner: account,
+        })
+        .collect();
+    let converted_total_balance = if accounts.is_empty() {
+        (total_balance * rate).round_to_currency(&options.currency)
+    } else {
+        accounts
+            .iter()
+            .map(|account| account.converted_balance)
+            .sum()
+    };
+
+    Ok(ConvertedBalance {
+        currency,
+        total_balance,
+        reporting_currency: options.currency.clone(),
+        rate,
+        converted_total_balance,
+        accounts,
+    })
+}
+
+/// Report a currency tile in the reporting currency of the options.
+pub async fn convert_currency(
+    db: &Surreal<Db>,
+    currency: Currency,
+    options: &ConversionOptions,
+) -> Result<ConvertedBalance, Error> {
+    convert_balance(
+        db,
+        currency.currency,
+        currency.total_balance,
+        currency.accounts,
+        options,
+    )
+    .await
+}
+
+/// Report a currency balance in the reporting currency of the options.
+pub async fn convert_currency_balance(
+    db: &Surreal<Db>,
+    balance: CurrencyBalance,
+    options: &ConversionOptions,
+) -> Result<ConvertedBalance, Error> {
+    convert_balance(
+        db,
+        balance.currency,
+        balance.total_balance,
+        balance.accounts,
+        options,
+    )
+    .await
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct NetWorth {
+    pub currency: String,
+    #[ts(as = "String")]
+    pub date: chrono::DateTime<chrono::Utc>,
+    pub total: Money,
+    pub currencies: Vec<ConvertedBalance>,
+}
+
+/// Sum the balances of all accounts at the date of the options, converted into
+/// the reporting currency with the rates valid on that date.
+pub async fn net_worth(db: &Surreal<Db>, options: ConversionOptions) -> Result<NetWorth, Error> {
+    let date = options.date.unwrap_or_else(chrono::Utc::now);
+    let query = r#"
+        SELECT
+            math::sum(balance) as total_balance,
+            array::group([{account: account, balance: balance}]) as accounts,
+            account.currency as currency
+        FROM (
+            SELECT
+                math::sum((SELECT VALUE amount FROM transaction WHERE account = $parent.id AND date <= <datetime>$date))
+                    + IF opening_date = NONE OR opening_date <= <datetime>$date THEN opening_balance ELSE 0 END as balance,
+                id as account
+            FROM account
+            FETCH account
+        )
+        GROUP BY currency"#;
+
+    let balances: Vec<CurrencyBalance> = db.query(query).bind(("date", date)).await?.take(0)?;
+    let options = ConversionOptions {
+        date: Some(date),
+        ..options
+    };
+
+    let mut currencies = Vec::with_capacity(balances.len());
+    for balance in balances {
+        currencies.push(convert_currency_balance(db, balance, &options).await?);
+    }
+
+    Ok(NetWorth {
+        total: currencies
+            .iter()
+            .map(|currency| currency.converted_total_balance)
+            .sum(),
+        currency: options.currency,
+        date,
+        currencies,
+    })
+}
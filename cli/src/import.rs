@@ -0,0 +1,391 @@
+use std::str::FromStr;
+
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    account::{balance, BalanceOptions},
+    money::Money,
+    transaction::{
+        duplicate::{Detector, DuplicateOptions, PossibleDuplicate},
+        insert,
+        payee::Payees,
+        rule::Rules,
+        AddTransactionOptions, TransactionWithId,
+    },
+    Error,
+};
+
+pub mod camt;
+pub mod csv;
+mod markup;
+pub mod mt940;
+pub mod ofx;
+pub mod qif;
+
+#[derive(Debug)]
+pub enum ImportError {
+    /// The file could not be read or written.
+    Io(std::io::Error),
+    /// The encoding label is not supported.
+    UnknownEncoding(String),
+    /// The date format is not a valid `strftime` format.
+    InvalidDateFormat(String),
+    /// The file holds several statements, with the number of statements.
+    MultipleStatements(usize),
+    /// The file holds statements of different accounts, with the identifiers of two
+    /// of them.
+    DifferentAccounts(String, String),
+    /// The statement is not for the account it is imported on, with the identifier of
+    /// the statement account and the number of the account.
+    AccountMismatch(String, String),
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Io(error) => write!(f, "failed to access file: {error}"),
+            ImportError::UnknownEncoding(label) => write!(f, "unknown encoding {label}"),
+            ImportError::InvalidDateFormat(format) => write!(f, "invalid date format {format}"),
+            ImportError::MultipleStatements(count) => {
+                write!(
+                    f,
+                    "the file holds {count} statements, only one can be imported at a time"
+                )
+            }
+            ImportError::DifferentAccounts(first, second) => {
+                write!(
+                    f,
+                    "the file holds statements of accounts {first} and {second}"
+                )
+            }
+            ImportError::AccountMismatch(statement, account) => {
+                write!(
+                    f,
+                    "the statement is for account {statement}, not for account {account}"
+                )
+            }
+        }
+    }
+}
+
+/// A row of an imported file, parsed as a transaction or with the reason it
+/// could not be parsed.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PreviewRow {
+    /// Line of the row in the file, starting at 1.
+    pub line: usize,
+    #[ts(optional)]
+    pub transaction: Option<AddTransactionOptions>,
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
+/// Read a file to import.
+#[allow(clippy::result_large_err)]
+pub(crate) fn read_file(path: &str) -> Result<Vec<u8>, Error> {
+    std::fs::read(path).map_err(|error| Error::Import(ImportError::Io(error)))
+}
+
+/// Decode the content of a file with the encoding of the given label, like `utf-8`
+/// or `windows-1252`. A byte order mark takes precedence over the label.
+#[allow(clippy::result_large_err)]
+pub(crate) fn decode(content: &[u8], label: &str) -> Result<String, Error> {
+    let encoding = encoding_rs::Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| Error::Import(ImportError::UnknownEncoding(label.to_string())))?;
+    let (text, _, _) = encoding.decode(content);
+
+    Ok(text.into_owned())
+}
+
+/// Parse an amount written with the given decimal separator. Spaces and the other
+/// separators are accepted as thousands separators between groups of three digits,
+/// and amounts between parentheses are negative. Amounts whose separators do not
+/// match the decimal separator, like `12.50` with a comma, are rejected instead of
+/// being read as `1250`.
+pub(crate) fn parse_amount(value: &str, decimal_separator: char) -> Option<Money> {
+    let value = value.trim();
+    let (value, negative) = match value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(value) => ("-", value.trim_start()),
+        None => ("", value.strip_prefix('+').unwrap_or(value).trim_start()),
+    };
+    let (integer, fraction) = match value.split_once(decimal_separator) {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (value, None),
+    };
+
+    let mut groups = integer.split(|c: char| matches!(c, '.' | ',' | '\'') || c.is_whitespace());
+    let first = groups.next()?;
+    let mut number = format!("{sign}{first}");
+
+    for group in groups {
+        if first.is_empty() || first.len() > 3 || group.len() != 3 {
+            return None;
+        }
+        number.push_str(group);
+    }
+
+    if let Some(fraction) = fraction {
+        number.push('.');
+        number.push_str(fraction);
+    }
+
+    if !number.chars().any(|c| c.is_ascii_digit())
+        || !number
+            .trim_start_matches('-')
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.')
+    {
+        return None;
+    }
+
+    let amount = Money::from_str(&number).ok()?;
+
+    Some(if negative { -amount } else { amount })
+}
+
+/// Create the transactions of an import preview on an account after applying the
+/// rules to them and finding their payees, skipping duplicates. Transactions with an
+/// external id already imported on the account are skipped, so importing the same
+/// file twice does not create duplicates. Other transactions are compared to the
+/// transactions of the account: sure duplicates are skipped, and possible duplicates
+/// are imported and returned for review.
+pub async fn create(
+    db: &Surreal<Db>,
+    account: RecordId,
+    mut transactions: Vec<AddTransactionOptions>,
+) -> Result<ImportSummary, Error> {
+    let rules = Rules::load(db).await?;
+    let payees = Payees::load(db).await?;
+
+    for transaction in &mut transactions {
+        rules.apply(&account, transaction);
+        payees.apply(transaction);
+    }
+
+    let mut imported: std::collections::HashSet<String> = db
+        .query(
+            "SELECT VALUE external_id FROM transaction WHERE account = $account AND external_id != NONE",
+        )
+        .bind(("account", account.clone()))
+        .await?
+        .take::<Vec<String>>(0)?
+        .into_iter()
+        .collect();
+    let mut detector = Detector::load(
+        db,
+        account.clone(),
+        &transactions,
+        DuplicateOptions::default(),
+    )
+    .await?;
+    let mut summary = ImportSummary {
+        imported: Vec::with_capacity(transactions.len()),
+        duplicates: 0,
+        possible_duplicates: vec![],
+        errors: vec![],
+        balances: vec![],
+    };
+
+    for transaction in transactions {
+        if let Some(external_id) = &transaction.external_id {
+            if !imported.insert(external_id.clone()) {
+                summary.duplicates += 1;
+                continue;
+            }
+        }
+
+        let duplicate = detector.find(&transaction);
+
+        if let Some((_, score)) = &duplicate {
+            if *score >= detector.options().skip_score {
+                summary.duplicates += 1;
+                continue;
+            }
+        }
+
+        let created = insert(db, account.clone(), transaction).await?;
+
+        if let Some((existing, score)) = duplicate {
+            summary.possible_duplicates.push(PossibleDuplicate {
+                transaction: created.clone(),
+                existing,
+                score,
+            });
+        }
+
+        summary.imported.push(created);
+    }
+
+    Ok(summary)
+}
+
+/// Date of the entries of bank statements used as the date of the transactions.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum EntryDate {
+    /// Date the entry was booked on the account.
+    #[default]
+    Booking,
+    /// Date the amount of the entry starts or stops bearing interest.
+    Value,
+}
+
+/// Balance of the account written on a statement, at the end of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementBalance {
+    pub date: chrono::NaiveDate,
+    pub amount: Money,
+}
+
+/// Transactions and balances read from a statement file.
+#[derive(Debug, Clone, Default)]
+pub struct Statement {
+    pub rows: Vec<PreviewRow>,
+    pub balances: Vec<StatementBalance>,
+    /// Identifier of the account of the statement, like an IBAN or an account number.
+    pub account: Option<String>,
+}
+
+impl Statement {
+    /// Set the account of the statement, which must be the account of the previous
+    /// statements of the file.
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn set_account(&mut self, account: &str) -> Result<(), Error> {
+        let account = account.trim();
+
+        match &self.account {
+            Some(previous) if normalize_account(previous) != normalize_account(account) => {
+                Err(Error::Import(ImportError::DifferentAccounts(
+                    previous.clone(),
+                    account.to_string(),
+                )))
+            }
+            Some(_) => Ok(()),
+            None => {
+                self.account = Some(account.to_string());
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Keep the letters and digits of an account identifier, in uppercase.
+fn normalize_account(account: &str) -> String {
+    account
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Check that the identifier of a statement account matches the number of an account.
+/// Banks write account numbers with or without their bank code, so either one ending
+/// with the other is a match, as is a number ending with the part of the identifier
+/// after its last `/`, like the account of a `bank code/account` MT940 identifier.
+fn same_account(statement: &str, number: &str) -> bool {
+    let (statement_id, number) = (normalize_account(statement), normalize_account(number));
+    let last = statement
+        .rsplit('/')
+        .next()
+        .map(normalize_account)
+        .unwrap_or_default();
+    let last = last.trim_start_matches('0');
+
+    statement_id.ends_with(&number)
+        || number.ends_with(&statement_id)
+        || (!last.is_empty() && number.ends_with(last))
+}
+
+/// Balance of a statement compared to the balance of the account after the import.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BalanceCheck {
+    #[ts(as = "String")]
+    pub date: chrono::NaiveDate,
+    /// Balance written on the statement.
+    pub expected: Money,
+    /// Balance of the account at the end of the day.
+    pub computed: Money,
+    /// Computed balance minus the expected balance.
+    pub difference: Money,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ImportSummary {
+    pub imported: Vec<TransactionWithId>,
+    /// Number of transactions skipped because they were already on the account.
+    pub duplicates: usize,
+    /// Imported transactions that are possibly duplicates of transactions of the account.
+    pub possible_duplicates: Vec<PossibleDuplicate>,
+    /// Rows that could not be parsed and were not imported.
+    pub errors: Vec<PreviewRow>,
+    pub balances: Vec<BalanceCheck>,
+}
+
+/// Create the transactions of a statement on an account, then check the balances of
+/// the statement against the balance of the account. Statements for another account
+/// than the number of the account are rejected.
+pub async fn import_statement(
+    db: &Surreal<Db>,
+    account: RecordId,
+    statement: Statement,
+) -> Result<ImportSummary, Error> {
+    if let Some(statement_account) = &statement.account {
+        if let Some(number) = crate::account::read(db, account.clone())
+            .await?
+            .data
+            .number
+            .filter(|number| !number.trim().is_empty())
+        {
+            if !same_account(statement_account, &number) {
+                return Err(Error::Import(ImportError::AccountMismatch(
+                    statement_account.clone(),
+                    number,
+                )));
+            }
+        }
+    }
+
+    let (valid, errors): (Vec<_>, Vec<_>) = statement
+        .rows
+        .into_iter()
+        .partition(|row| row.transaction.is_some());
+    let transactions: Vec<AddTransactionOptions> = valid
+        .into_iter()
+        .filter_map(|row| row.transaction)
+        .collect();
+    let mut summary = create(db, account.clone(), transactions).await?;
+
+    for expected in statement.balances {
+        let computed = balance(
+            db,
+            account.clone(),
+            BalanceOptions {
+                as_of: Some(expected.date),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        summary.balances.push(BalanceCheck {
+            date: expected.date,
+            expected: expected.amount,
+            computed,
+            difference: computed - expected.amount,
+        });
+    }
+
+    summary.errors = errors;
+
+    Ok(summary)
+}
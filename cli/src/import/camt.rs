@@ -0,0 +1,251 @@
+//! ISO 20022 CAMT.053 bank to customer statements. A file can hold several
+//! statements of the same account, whose entries and balances are all read.
+
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    import::{
+        decode, import_statement,
+        markup::{declared_encoding, elements, Element},
+        parse_amount, read_file, EntryDate, ImportSummary, PreviewRow, Statement, StatementBalance,
+    },
+    money::Money,
+    transaction::AddTransactionOptions,
+    Error,
+};
+
+/// Fields of an `Ntry` element.
+#[derive(Default)]
+struct Entry {
+    line: usize,
+    amount: Option<String>,
+    debit: bool,
+    reversal: bool,
+    booking_date: Option<String>,
+    value_date: Option<String>,
+    reference: Option<String>,
+    servicer_reference: Option<String>,
+    debtor: Option<String>,
+    creditor: Option<String>,
+    remittance: Vec<String>,
+    additional_info: Option<String>,
+}
+
+/// Fields of a `Bal` element.
+#[derive(Default)]
+struct Balance {
+    code: Option<String>,
+    amount: Option<String>,
+    debit: bool,
+    date: Option<String>,
+}
+
+/// Parse an ISO date, like `2026-01-31` or `2026-01-31T12:00:00+01:00`.
+fn parse_date(value: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// Get the external id of an entry. The reference of the account servicer is unique,
+/// but the entry reference is often reused or blank, so it is only unique with the
+/// booking date and the amount of the entry.
+fn external_id(entry: &Entry, amount: Money) -> Option<String> {
+    let reference = |reference: &Option<String>| {
+        reference
+            .as_deref()
+            .map(str::trim)
+            .filter(|reference| !reference.is_empty() && *reference != "NOTPROVIDED")
+            .map(str::to_string)
+    };
+
+    reference(&entry.servicer_reference).or_else(|| {
+        let date = entry.booking_date.as_ref().or(entry.value_date.as_ref())?;
+        let date = parse_date(date)?;
+
+        reference(&entry.reference).map(|reference| format!("{date}/{amount}/{reference}"))
+    })
+}
+
+fn parse_entry(entry: &Entry, date: EntryDate) -> Result<AddTransactionOptions, String> {
+    let value = match date {
+        EntryDate::Booking => entry.booking_date.as_ref().or(entry.value_date.as_ref()),
+        EntryDate::Value => entry.value_date.as_ref().or(entry.booking_date.as_ref()),
+    };
+    let value = value.ok_or("missing booking and value dates")?;
+    let date = parse_date(value).ok_or_else(|| format!("invalid date {value:?}"))?;
+
+    let amount = entry.amount.as_ref().ok_or("missing amount")?;
+    let amount = parse_amount(amount, '.').ok_or_else(|| format!("invalid amount {amount:?}"))?;
+    // Reversals keep the indicator of the entry they cancel, so their sign is the opposite.
+    let amount = if entry.debit != entry.reversal {
+        -amount.abs()
+    } else {
+        amount.abs()
+    };
+
+    // The counterparty of a payment is its creditor, and the counterparty of a
+    // received amount is its debtor.
+    let counterparty = if amount.is_sign_negative() {
+        entry.creditor.as_ref()
+    } else {
+        entry.debtor.as_ref()
+    };
+    let remittance = if entry.remittance.is_empty() {
+        entry.additional_info.clone()
+    } else {
+        Some(entry.remittance.join(" "))
+    };
+
+    Ok(AddTransactionOptions {
+        amount,
+        description: [counterparty, remittance.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" "),
+        date: Some(date.and_time(chrono::NaiveTime::MIN).and_utc()),
+        external_id: external_id(entry, amount),
+        ..Default::default()
+    })
+}
+
+/// Get the balance of the account at the end of a day from an opening or closing balance.
+fn parse_balance(balance: &Balance) -> Option<StatementBalance> {
+    let date = parse_date(balance.date.as_ref()?)?;
+    let amount = parse_amount(balance.amount.as_ref()?, '.')?.abs();
+    let amount = if balance.debit { -amount } else { amount };
+
+    match balance.code.as_deref()? {
+        // Opening balances are the balances at the start of their day.
+        "OPBD" | "PRCD" => Some(StatementBalance {
+            date: date.pred_opt()?,
+            amount,
+        }),
+        "CLBD" => Some(StatementBalance { date, amount }),
+        _ => None,
+    }
+}
+
+/// Parse the content of a CAMT.053 file, in the encoding of its XML declaration or
+/// in UTF-8. Nothing is written to the database. Files with statements of different
+/// accounts are rejected.
+#[allow(clippy::result_large_err)]
+pub fn parse(content: &[u8], date: EntryDate) -> Result<Statement, Error> {
+    let encoding = declared_encoding(content).unwrap_or_else(|| "utf-8".to_string());
+    let text = decode(content, &encoding)?;
+    let mut statement = Statement::default();
+    let mut path: Vec<String> = vec![];
+    let mut entry: Option<Entry> = None;
+    let mut balance: Option<Balance> = None;
+
+    for (line, element) in elements(&text) {
+        match element {
+            Element::Start(name) => {
+                match name.as_str() {
+                    "Ntry" => {
+                        entry = Some(Entry {
+                            line,
+                            ..Default::default()
+                        })
+                    }
+                    "Bal" => balance = Some(Balance::default()),
+                    _ => {}
+                }
+
+                path.push(name);
+            }
+            Element::End(name) => {
+                // End tags of elements holding a value were never pushed.
+                if path.last() != Some(&name) {
+                    continue;
+                }
+
+                path.pop();
+
+                match name.as_str() {
+                    "Ntry" => {
+                        if let Some(entry) = entry.take() {
+                            statement.rows.push(match parse_entry(&entry, date) {
+                                Ok(transaction) => PreviewRow {
+                                    line: entry.line,
+                                    transaction: Some(transaction),
+                                    error: None,
+                                },
+                                Err(error) => PreviewRow {
+                                    line: entry.line,
+                                    transaction: None,
+                                    error: Some(error),
+                                },
+                            });
+                        }
+                    }
+                    "Bal" => {
+                        if let Some(balance) = balance.take().as_ref().and_then(parse_balance) {
+                            statement.balances.push(balance);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Element::Value(name, value) => {
+                let parent = path.last().map(String::as_str);
+                let inside = |element: &str| path.iter().any(|name| name == element);
+                // Whether the value is directly inside the given elements.
+                let within = |elements: &[&str]| {
+                    path.len() >= elements.len()
+                        && path[path.len() - elements.len()..]
+                            .iter()
+                            .zip(elements)
+                            .all(|(name, element)| name == element)
+                };
+
+                if let Some(entry) = &mut entry {
+                    match (parent, name.as_str()) {
+                        (Some("Ntry"), "Amt") => entry.amount = Some(value),
+                        (Some("Ntry"), "CdtDbtInd") => entry.debit = value == "DBIT",
+                        (Some("Ntry"), "RvslInd") => entry.reversal = value == "true",
+                        (Some("Ntry"), "NtryRef") => entry.reference = Some(value),
+                        (Some("Ntry"), "AcctSvcrRef") => entry.servicer_reference = Some(value),
+                        (Some("Ntry"), "AddtlNtryInf") => entry.additional_info = Some(value),
+                        (Some("BookgDt"), "Dt" | "DtTm") => entry.booking_date = Some(value),
+                        (Some("ValDt"), "Dt" | "DtTm") => entry.value_date = Some(value),
+                        (_, "Ustrd") if inside("RmtInf") => entry.remittance.push(value),
+                        (_, "Nm") if inside("Dbtr") && entry.debtor.is_none() => {
+                            entry.debtor = Some(value)
+                        }
+                        (_, "Nm") if inside("Cdtr") && entry.creditor.is_none() => {
+                            entry.creditor = Some(value)
+                        }
+                        _ => {}
+                    }
+                } else if let Some(balance) = &mut balance {
+                    match (parent, name.as_str()) {
+                        (_, "Cd") if inside("Tp") => balance.code = Some(value),
+                        (Some("Bal"), "Amt") => balance.amount = Some(value),
+                        (Some("Bal"), "CdtDbtInd") => balance.debit = value == "DBIT",
+                        (Some("Dt"), "Dt" | "DtTm") => balance.date = Some(value),
+                        _ => {}
+                    }
+                } else if (name == "IBAN" && within(&["Stmt", "Acct", "Id"]))
+                    || (name == "Id" && within(&["Stmt", "Acct", "Id", "Othr"]))
+                {
+                    statement.set_account(&value)?;
+                }
+            }
+        }
+    }
+
+    Ok(statement)
+}
+
+/// Import a CAMT.053 file on an account, skipping the entries already imported, then
+/// check the opening and closing balances of its statements against the balance of
+/// the account.
+pub async fn import(
+    db: &Surreal<Db>,
+    path: &str,
+    account: RecordId,
+    date: EntryDate,
+) -> Result<ImportSummary, Error> {
+    import_statement(db, account, parse(&read_file(path)?, date)?).await
+}
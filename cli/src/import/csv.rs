@@ -0,0 +1,269 @@
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    import::{decode, parse_amount, read_file, PreviewRow},
+    money::Money,
+    transaction::AddTransactionOptions,
+    Error,
+};
+
+/// Columns holding the amount of the transactions. Columns start at 0.
+#[derive(ts_rs::TS)]
+#[ts(export, tag = "type", content = "data")]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum AmountColumns {
+    /// A single column with signed amounts.
+    Single {
+        column: usize,
+        /// Expenses are positive and income is negative, like on some credit card exports.
+        inverted: bool,
+    },
+    /// Debits and credits in separate columns. Signs are ignored, debits are expenses.
+    DebitCredit { debit: usize, credit: usize },
+}
+
+/// How to read the CSV export of a bank. Columns start at 0.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CsvOptions {
+    /// Character separating the fields. Only the first character is used.
+    pub delimiter: String,
+    /// Label of the encoding of the file, like `utf-8` or `windows-1252`.
+    pub encoding: String,
+    /// Format of the dates, like `%d/%m/%Y`.
+    pub date_format: String,
+    /// Character separating the decimals. Only the first character is used.
+    pub decimal_separator: String,
+    /// Number of rows to skip at the start of the file, like headers.
+    pub skip_rows: usize,
+    pub date: usize,
+    /// Columns joined to build the description.
+    pub description: Vec<usize>,
+    pub amount: AmountColumns,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ",".to_string(),
+            encoding: "utf-8".to_string(),
+            date_format: "%Y-%m-%d".to_string(),
+            decimal_separator: ".".to_string(),
+            skip_rows: 1,
+            date: 0,
+            description: vec![1],
+            amount: AmountColumns::Single {
+                column: 2,
+                inverted: false,
+            },
+        }
+    }
+}
+
+/// Split CSV text into records, with the line each record starts on.
+/// Quoted fields can contain delimiters, new lines and doubled quotes.
+fn records(text: &str, delimiter: char) -> Vec<(usize, Vec<String>)> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            '\n' if quoted => {
+                line += 1;
+                field.push(c);
+            }
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                let fields = std::mem::take(&mut record);
+
+                // Blank lines are skipped.
+                if fields.iter().any(|field| !field.trim().is_empty()) {
+                    records.push((start, fields));
+                }
+
+                line += 1;
+                start = line;
+            }
+            c if c == delimiter && !quoted => record.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    record.push(field);
+
+    if record.iter().any(|field| !field.trim().is_empty()) {
+        records.push((start, record));
+    }
+
+    records
+}
+
+fn field(record: &[String], column: usize) -> Result<&str, String> {
+    record
+        .get(column)
+        .map(|field| field.trim())
+        .ok_or_else(|| format!("missing column {column}"))
+}
+
+fn parse_record(record: &[String], options: &CsvOptions) -> Result<AddTransactionOptions, String> {
+    let decimal_separator = options.decimal_separator.chars().next().unwrap_or('.');
+    let date = field(record, options.date)?;
+    let date = chrono::NaiveDate::parse_from_str(date, &options.date_format)
+        .map_err(|error| format!("invalid date {date:?}: {error}"))?;
+
+    let amount = |column| -> Result<Option<Money>, String> {
+        let value = field(record, column)?;
+
+        if value.is_empty() {
+            return Ok(None);
+        }
+
+        parse_amount(value, decimal_separator)
+            .map(Some)
+            .ok_or_else(|| format!("invalid amount {value:?}"))
+    };
+
+    let amount = match options.amount {
+        AmountColumns::Single { column, inverted } => {
+            let amount =
+                amount(column)?.ok_or_else(|| format!("missing amount in column {column}"))?;
+
+            if inverted {
+                -amount
+            } else {
+                amount
+            }
+        }
+        AmountColumns::DebitCredit { debit, credit } => match (amount(debit)?, amount(credit)?) {
+            (None, None) => return Err("missing debit and credit".to_string()),
+            (debit, credit) => credit.unwrap_or_default().abs() - debit.unwrap_or_default().abs(),
+        },
+    };
+
+    let description = options
+        .description
+        .iter()
+        .map(|column| field(record, *column))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(AddTransactionOptions {
+        amount,
+        description,
+        date: Some(date.and_time(chrono::NaiveTime::MIN).and_utc()),
+        ..Default::default()
+    })
+}
+
+/// Parse CSV content with the given options. Nothing is written to the database.
+#[allow(clippy::result_large_err)]
+pub fn parse(content: &[u8], options: &CsvOptions) -> Result<Vec<PreviewRow>, Error> {
+    let text = decode(content, &options.encoding)?;
+    let delimiter = options.delimiter.chars().next().unwrap_or(',');
+
+    Ok(records(&text, delimiter)
+        .into_iter()
+        .skip(options.skip_rows)
+        .map(|(line, record)| match parse_record(&record, options) {
+            Ok(transaction) => PreviewRow {
+                line,
+                transaction: Some(transaction),
+                error: None,
+            },
+            Err(error) => PreviewRow {
+                line,
+                transaction: None,
+                error: Some(error),
+            },
+        })
+        .collect())
+}
+
+/// Parse a CSV file with the given options. Nothing is written to the database.
+#[allow(clippy::result_large_err)]
+pub fn preview(path: &str, options: &CsvOptions) -> Result<Vec<PreviewRow>, Error> {
+    parse(&read_file(path)?, options)
+}
+
+/// Options of a CSV export saved for an account.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CsvProfile {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub id: RecordId,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub account: RecordId,
+    pub name: String,
+    pub options: CsvOptions,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, serde::Deserialize)]
+pub struct CreateCsvProfileOptions {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub account: RecordId,
+    pub name: String,
+    pub options: CsvOptions,
+}
+
+pub async fn create_profile(
+    db: &Surreal<Db>,
+    options: CreateCsvProfileOptions,
+) -> Result<CsvProfile, Error> {
+    let profile: Option<CsvProfile> = db
+        .query("CREATE csv_profile SET account = $account, name = $name, options = $options")
+        .bind(("account", options.account))
+        .bind(("name", options.name))
+        .bind(("options", options.options))
+        .await?
+        .take(0)?;
+
+    profile.ok_or(Error::RecordNotFound)
+}
+
+/// List the CSV profiles of an account.
+pub async fn read_profiles(
+    db: &Surreal<Db>,
+    account: RecordId,
+) -> Result<Vec<CsvProfile>, surrealdb::Error> {
+    db.query("SELECT * FROM csv_profile WHERE account = $account ORDER BY name")
+        .bind(("account", account))
+        .await?
+        .take(0)
+}
+
+pub async fn update_profile(db: &Surreal<Db>, profile: CsvProfile) -> Result<(), surrealdb::Error> {
+    db.query("UPDATE $profile SET name = $name, options = $options")
+        .bind(("profile", profile.id))
+        .bind(("name", profile.name))
+        .bind(("options", profile.options))
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+pub async fn delete_profile(db: &Surreal<Db>, profile: RecordId) -> Result<(), surrealdb::Error> {
+    let _: Option<CsvProfile> = db.delete(profile).await?;
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+//! Tolerant reader of the SGML and XML markup of statement files. Elements holding a
+//! value do not need an end tag, like in OFX 1.x files, and attributes and namespace
+//! prefixes are ignored.
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Element {
+    Start(String),
+    End(String),
+    Value(String, String),
+}
+
+/// Get the label of the encoding declared by the XML declaration of a file, like
+/// `<?xml version="1.0" encoding="ISO-8859-1"?>`.
+pub(crate) fn declared_encoding(content: &[u8]) -> Option<String> {
+    let header = String::from_utf8_lossy(&content[..content.len().min(1024)]);
+    let declaration = &header[header.find("<?xml")?..];
+    let (_, label) = declaration[..declaration.find("?>")?].split_once("encoding=")?;
+
+    label
+        .trim_start_matches(['"', '\''])
+        .split(['"', '\''])
+        .next()
+        .map(str::to_string)
+}
+
+/// Replace the predefined and numeric character references of a value.
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let character = match &rest[1..end] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "amp" => Some('&'),
+            "nbsp" => Some(' '),
+            reference => reference
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| reference.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+
+        match character {
+            Some(character) => {
+                unescaped.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// Remove the namespace prefix of an element name, like `ns2:Ntry`.
+fn local_name(name: &str) -> String {
+    let name = name.trim();
+
+    name.rsplit_once(':')
+        .map_or(name, |(_, name)| name)
+        .to_string()
+}
+
+/// Split markup into elements, with the line each element starts on. Text before the
+/// first tag, like the header of OFX 1.x files, is ignored.
+pub(crate) fn elements(text: &str) -> Vec<(usize, Element)> {
+    let mut elements = vec![];
+    let mut rest = text;
+    let mut line = 1;
+
+    while let Some(start) = rest.find('<') {
+        line += rest[..start].matches('\n').count();
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+
+        line += tag.matches('\n').count();
+        rest = &rest[start + end + 1..];
+
+        // Skip the XML declaration, processing instructions, comments and empty elements.
+        if tag.starts_with('?') || tag.starts_with('!') || tag.ends_with('/') {
+            continue;
+        }
+
+        let element = match tag.strip_prefix('/') {
+            Some(name) => Element::End(local_name(name)),
+            None => {
+                let name = local_name(tag.split_whitespace().next().unwrap_or_default());
+                let value = rest[..rest.find('<').unwrap_or(rest.len())].trim();
+
+                if value.is_empty() {
+                    Element::Start(name)
+                } else {
+                    Element::Value(name, unescape(value))
+                }
+            }
+        };
+
+        elements.push((line, element));
+    }
+
+    elements
+}
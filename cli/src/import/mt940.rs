@@ -0,0 +1,307 @@
+//! SWIFT MT940 customer statements. A file can hold several statements of the same
+//! account, each starting with a `:20:` field, whose entries and balances are all read.
+
+use chrono::Datelike;
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    import::{
+        decode, import_statement, parse_amount, read_file, EntryDate, ImportSummary, PreviewRow,
+        Statement, StatementBalance,
+    },
+    money::Money,
+    transaction::AddTransactionOptions,
+    Error,
+};
+
+/// A `:tag:` field, with its continuation lines.
+struct Field {
+    line: usize,
+    tag: String,
+    value: String,
+}
+
+/// Split MT940 text into fields. The headers and trailers of SWIFT messages are ignored.
+fn fields(text: &str) -> Vec<Field> {
+    let mut fields: Vec<Field> = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        let tag = line
+            .strip_prefix(':')
+            .and_then(|rest| rest.split_once(':'))
+            .filter(|(tag, _)| {
+                !tag.is_empty() && tag.len() <= 3 && tag.chars().all(|c| c.is_ascii_alphanumeric())
+            });
+
+        match tag {
+            Some((tag, value)) => fields.push(Field {
+                line: index + 1,
+                tag: tag.to_string(),
+                value: value.to_string(),
+            }),
+            None if line == "-"
+                || line.starts_with("-}")
+                || line.starts_with('{')
+                || line.is_empty() => {}
+            None => {
+                if let Some(field) = fields.last_mut() {
+                    field.value.push('\n');
+                    field.value.push_str(line);
+                }
+            }
+        }
+    }
+
+    fields
+}
+
+fn parse_date(value: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(value.get(..6)?, "%y%m%d").ok()
+}
+
+/// Parse a balance field, like `C260131EUR1000,00`.
+fn parse_balance(value: &str) -> Option<StatementBalance> {
+    let debit = match value.get(..1)? {
+        "C" => false,
+        "D" => true,
+        _ => return None,
+    };
+    let amount = parse_amount(value.get(10..)?, ',')?;
+
+    Some(StatementBalance {
+        date: parse_date(value.get(1..7)?)?,
+        amount: if debit { -amount } else { amount },
+    })
+}
+
+/// Take the leading characters of `value` matching `predicate`.
+fn take_while(value: &str, predicate: impl Fn(char) -> bool) -> (&str, &str) {
+    let end = value
+        .char_indices()
+        .find(|(_, c)| !predicate(*c))
+        .map_or(value.len(), |(index, _)| index);
+
+    value.split_at(end)
+}
+
+/// Fields of a `:61:` statement line.
+struct Line {
+    value_date: chrono::NaiveDate,
+    booking_date: Option<chrono::NaiveDate>,
+    amount: Money,
+    reference: Option<String>,
+}
+
+/// Parse a statement line, like `2602010201DR50,00NTRFNONREF//B1234`.
+fn parse_line(value: &str) -> Result<Line, String> {
+    let first = value.lines().next().unwrap_or_default();
+    let value_date = parse_date(first).ok_or_else(|| format!("invalid value date in {first:?}"))?;
+    let rest = &first[6..];
+
+    // The booking date has no year, which is the one of the value date unless the
+    // dates are on both sides of a new year.
+    let (booking, rest) = take_while(rest, |c| c.is_ascii_digit());
+    let booking_date = match booking.len() {
+        4 => {
+            let month: u32 = booking[..2].parse().map_err(|_| "invalid booking date")?;
+            let day: u32 = booking[2..].parse().map_err(|_| "invalid booking date")?;
+            let year = match (value_date.month(), month) {
+                (12, 1) => value_date.year() + 1,
+                (1, 12) => value_date.year() - 1,
+                _ => value_date.year(),
+            };
+
+            Some(
+                chrono::NaiveDate::from_ymd_opt(year, month, day)
+                    .ok_or_else(|| format!("invalid booking date {booking:?}"))?,
+            )
+        }
+        0 => None,
+        _ => return Err(format!("invalid booking date {booking:?}")),
+    };
+
+    let (mark, rest) = take_while(rest, |c| c.is_ascii_alphabetic());
+    let debit = match mark {
+        // The last letter of the mark can be the third letter of the currency code.
+        "D" | "RC" => true,
+        "C" | "RD" => false,
+        mark if mark.len() > 1 => match &mark[..mark.len() - 1] {
+            "D" | "RC" => true,
+            "C" | "RD" => false,
+            _ => return Err(format!("invalid debit or credit mark {mark:?}")),
+        },
+        _ => return Err(format!("invalid debit or credit mark {mark:?}")),
+    };
+
+    let (amount, rest) = take_while(rest, |c| c.is_ascii_digit() || c == ',');
+    let amount = parse_amount(amount, ',').ok_or_else(|| format!("invalid amount {amount:?}"))?;
+
+    let amount = if debit { -amount } else { amount };
+
+    // The transaction type is 4 characters, followed by the reference of the customer
+    // and the reference of the bank after `//`. Customers often reuse their reference,
+    // like an invoice number paid in several times, so it is only unique with the date
+    // and the amount of the entry.
+    let references = rest.get(4..).unwrap_or_default();
+    let (customer, bank) = match references.split_once("//") {
+        Some((customer, bank)) => (customer.trim(), bank.trim()),
+        None => (references.trim(), ""),
+    };
+    let reference = if !bank.is_empty() {
+        Some(bank.to_string())
+    } else if !customer.is_empty() && customer != "NONREF" {
+        Some(format!("{value_date}/{amount}/{customer}"))
+    } else {
+        None
+    };
+
+    Ok(Line {
+        value_date,
+        booking_date,
+        amount,
+        reference,
+    })
+}
+
+/// Get the description of an entry from its `:86:` information. Structured information,
+/// with German `?` subfields or `/NAME/` and `/REMI/` codes, is reduced to the name of
+/// the counterparty and the remittance information.
+fn description(information: &str) -> String {
+    let information = information.replace('\n', "");
+
+    if information.get(3..4) == Some("?") {
+        let mut name = String::new();
+        let mut remittance = String::new();
+
+        for subfield in information[3..].split('?').skip(1) {
+            match subfield.get(..2) {
+                Some("20" | "21" | "22" | "23" | "24" | "25" | "26" | "27" | "28" | "29") => {
+                    remittance.push_str(&subfield[2..])
+                }
+                Some("32" | "33") => name.push_str(&subfield[2..]),
+                _ => {}
+            }
+        }
+
+        return [name.trim(), remittance.trim()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+
+    if information.starts_with('/') {
+        let codes: Vec<&str> = information.split('/').collect();
+        let code = |name: &str| {
+            codes
+                .iter()
+                .position(|code| *code == name)
+                .and_then(|index| codes.get(index + 1))
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+
+        if code("NAME").is_some() || code("REMI").is_some() {
+            return [code("NAME"), code("REMI")]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+    }
+
+    information.trim().to_string()
+}
+
+/// Parse the content of an MT940 file. Nothing is written to the database. Files with
+/// statements of different accounts are rejected.
+#[allow(clippy::result_large_err)]
+pub fn parse(content: &[u8], date: EntryDate) -> Result<Statement, Error> {
+    // MT940 files are mostly ASCII, but banks write them as UTF-8 or Latin-1.
+    let text = match std::str::from_utf8(content) {
+        Ok(text) => text.to_string(),
+        Err(_) => decode(content, "windows-1252")?,
+    };
+    let mut statement = Statement::default();
+    let mut fields = fields(&text).into_iter().peekable();
+    // Index of the opening balance of the current statement, until its first entry.
+    let mut opening = None;
+
+    while let Some(field) = fields.next() {
+        match field.tag.as_str() {
+            "25" => statement.set_account(&field.value)?,
+            "60F" | "60M" => {
+                if let Some(balance) = parse_balance(&field.value) {
+                    opening = Some(statement.balances.len());
+                    statement.balances.push(balance);
+                }
+            }
+            "62F" | "62M" => {
+                opening = None;
+
+                if let Some(balance) = parse_balance(&field.value) {
+                    statement.balances.push(balance);
+                }
+            }
+            "61" => {
+                let information = fields
+                    .next_if(|next| next.tag == "86")
+                    .map(|next| next.value)
+                    .unwrap_or_default();
+
+                statement.rows.push(match parse_line(&field.value) {
+                    Ok(line) => {
+                        let day = match date {
+                            EntryDate::Booking => line.booking_date.unwrap_or(line.value_date),
+                            EntryDate::Value => line.value_date,
+                        };
+
+                        // Banks date opening balances either on the day of the previous
+                        // closing balance or on the first day of the statement, so they
+                        // are checked before the first entry.
+                        if let Some(balance) =
+                            opening.take().map(|index| &mut statement.balances[index])
+                        {
+                            if let Some(before) = day.pred_opt() {
+                                balance.date = balance.date.min(before);
+                            }
+                        }
+
+                        PreviewRow {
+                            line: field.line,
+                            transaction: Some(AddTransactionOptions {
+                                amount: line.amount,
+                                description: description(&information),
+                                date: Some(day.and_time(chrono::NaiveTime::MIN).and_utc()),
+                                external_id: line.reference,
+                                ..Default::default()
+                            }),
+                            error: None,
+                        }
+                    }
+                    Err(error) => PreviewRow {
+                        line: field.line,
+                        transaction: None,
+                        error: Some(error),
+                    },
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(statement)
+}
+
+/// Import an MT940 file on an account, skipping the entries already imported, then
+/// check the opening and closing balances of its statements against the balance of
+/// the account.
+pub async fn import(
+    db: &Surreal<Db>,
+    path: &str,
+    account: RecordId,
+    date: EntryDate,
+) -> Result<ImportSummary, Error> {
+    import_statement(db, account, parse(&read_file(path)?, date)?).await
+}
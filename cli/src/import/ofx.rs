@@ -0,0 +1,189 @@
+//! OFX 1.x files are SGML, where elements holding a value have no end tag, and
+//! OFX 2.x files are XML. Both are read by the same tolerant reader, which only
+//! looks at the statement transactions and the ledger balance. OFX element names
+//! are uppercase.
+
+use std::collections::HashMap;
+
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    import::{
+        decode, import_statement,
+        markup::{declared_encoding, elements, Element},
+        parse_amount, read_file, ImportError, ImportSummary, PreviewRow, Statement,
+        StatementBalance,
+    },
+    transaction::AddTransactionOptions,
+    Error,
+};
+
+/// Get the label of the encoding declared by the header of an OFX file.
+fn encoding(content: &[u8]) -> String {
+    let header = String::from_utf8_lossy(&content[..content.len().min(1024)]);
+
+    for line in header.lines() {
+        let line = line.trim();
+
+        if let Some(encoding) = line.strip_prefix("ENCODING:") {
+            if encoding.trim().eq_ignore_ascii_case("UTF-8") {
+                return "utf-8".to_string();
+            }
+        }
+
+        if let Some(charset) = line.strip_prefix("CHARSET:") {
+            let charset = charset.trim();
+
+            return match charset {
+                "NONE" | "" => "windows-1252".to_string(),
+                charset if charset.chars().all(|c| c.is_ascii_digit()) => {
+                    format!("windows-{charset}")
+                }
+                charset => charset.to_string(),
+            };
+        }
+    }
+
+    declared_encoding(content).unwrap_or_else(|| "utf-8".to_string())
+}
+
+/// Parse an OFX date, like `20260131` or `20260131120000.000[-5:EST]`. Times are in
+/// UTC unless they have an offset in hours, and the day is the one of the statement
+/// in its own offset, so that entries stay in the month of the statement.
+fn parse_date(value: &str) -> Option<chrono::NaiveDate> {
+    let (value, offset) = match value.trim().split_once('[') {
+        Some((value, offset)) => (value, offset.trim_end_matches(']')),
+        None => (value.trim(), "0"),
+    };
+    let date = chrono::NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()?;
+
+    let Some(time) = value.get(8..14) else {
+        return Some(date);
+    };
+    let time = chrono::NaiveTime::parse_from_str(time, "%H%M%S").ok()?;
+    let offset: f64 = offset.split(':').next()?.trim().parse().ok()?;
+    let offset = chrono::FixedOffset::east_opt((offset * 3600.0).round() as i32)?;
+
+    Some(
+        date.and_time(time)
+            .and_local_timezone(offset)
+            .single()?
+            .date_naive(),
+    )
+}
+
+/// Parse an OFX amount, where the last period or comma is the decimal separator.
+fn parse_ofx_amount(value: &str) -> Option<crate::money::Money> {
+    let decimal_separator = value.chars().rev().find(|c| matches!(c, '.' | ','));
+
+    parse_amount(value, decimal_separator.unwrap_or('.'))
+}
+
+fn parse_transaction(fields: &HashMap<String, String>) -> Result<AddTransactionOptions, String> {
+    let field = |name: &str| fields.get(name).map(String::as_str);
+
+    let date = field("DTPOSTED").ok_or("missing DTPOSTED")?;
+    let date = parse_date(date).ok_or_else(|| format!("invalid date {date:?}"))?;
+    let amount = field("TRNAMT").ok_or("missing TRNAMT")?;
+    let amount = parse_ofx_amount(amount).ok_or_else(|| format!("invalid amount {amount:?}"))?;
+
+    let mut description = field("NAME").unwrap_or_default().to_string();
+
+    if let Some(memo) = field("MEMO").filter(|memo| *memo != description) {
+        if !description.is_empty() {
+            description.push(' ');
+        }
+
+        description.push_str(memo);
+    }
+
+    Ok(AddTransactionOptions {
+        amount,
+        description,
+        date: Some(date.and_time(chrono::NaiveTime::MIN).and_utc()),
+        external_id: field("FITID").map(str::to_string),
+        ..Default::default()
+    })
+}
+
+fn parse_balance(fields: &HashMap<String, String>) -> Option<StatementBalance> {
+    Some(StatementBalance {
+        date: parse_date(fields.get("DTASOF")?)?,
+        amount: parse_ofx_amount(fields.get("BALAMT")?)?,
+    })
+}
+
+/// Parse the content of an OFX 1.x or 2.x file. Nothing is written to the database.
+/// Files with several bank or credit card statements are rejected, as their
+/// transactions belong to different accounts.
+#[allow(clippy::result_large_err)]
+pub fn parse(content: &[u8]) -> Result<Statement, Error> {
+    let text = decode(content, &encoding(content))?;
+    let elements = elements(&text);
+    let statements = elements
+        .iter()
+        .filter(|(_, element)| {
+            matches!(element, Element::Start(name) if name == "STMTRS" || name == "CCSTMTRS")
+        })
+        .count();
+
+    if statements > 1 {
+        return Err(Error::Import(ImportError::MultipleStatements(statements)));
+    }
+
+    let mut statement = Statement::default();
+    let mut transaction: Option<(usize, HashMap<String, String>)> = None;
+    let mut ledger: Option<HashMap<String, String>> = None;
+
+    for (line, element) in elements {
+        match element {
+            Element::Start(name) if name == "STMTTRN" => {
+                transaction = Some((line, HashMap::new()));
+            }
+            Element::End(name) if name == "STMTTRN" => {
+                if let Some((line, fields)) = transaction.take() {
+                    statement.rows.push(match parse_transaction(&fields) {
+                        Ok(transaction) => PreviewRow {
+                            line,
+                            transaction: Some(transaction),
+                            error: None,
+                        },
+                        Err(error) => PreviewRow {
+                            line,
+                            transaction: None,
+                            error: Some(error),
+                        },
+                    });
+                }
+            }
+            Element::Start(name) if name == "LEDGERBAL" => ledger = Some(HashMap::new()),
+            Element::End(name) if name == "LEDGERBAL" => {
+                if let Some(balance) = ledger.take().as_ref().and_then(parse_balance) {
+                    statement.balances.push(balance);
+                }
+            }
+            Element::Value(name, value) => {
+                if let Some((_, fields)) = &mut transaction {
+                    fields.insert(name, value);
+                } else if let Some(fields) = &mut ledger {
+                    fields.insert(name, value);
+                } else if name == "ACCTID" {
+                    statement.set_account(&value)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(statement)
+}
+
+/// Import an OFX file on an account, skipping the transactions already imported,
+/// then check the ledger balance of the file against the balance of the account.
+pub async fn import(
+    db: &Surreal<Db>,
+    path: &str,
+    account: RecordId,
+) -> Result<ImportSummary, Error> {
+    import_statement(db, account, parse(&read_file(path)?)?).await
+}
@@ -0,0 +1,486 @@
+use std::collections::HashMap;
+
+use chrono::Datelike;
+
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    account::{self, AccountKind},
+    import::{decode, parse_amount, read_file, ImportError, PreviewRow},
+    money::Money,
+    transaction::{self, AddTransactionOptions, ReadTransactionOptions, Split},
+    Error,
+};
+
+/// How to read or write a QIF file.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct QifOptions {
+    /// Label of the encoding of the file, like `utf-8` or `windows-1252`.
+    pub encoding: String,
+    /// Format of the dates, like `%m/%d/%Y`. Quicken's `'` year separator is read as `/`.
+    pub date_format: String,
+    /// Character separating the decimals. Only the first character is used.
+    pub decimal_separator: String,
+}
+
+impl Default for QifOptions {
+    fn default() -> Self {
+        Self {
+            encoding: "utf-8".to_string(),
+            date_format: "%m/%d/%Y".to_string(),
+            decimal_separator: ".".to_string(),
+        }
+    }
+}
+
+/// Category of the application to use for a category name of QIF files.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct QifCategoryMapping {
+    /// Name of the category in QIF files, like `Food:Groceries`.
+    pub name: String,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub category: RecordId,
+}
+
+/// Transactions read from a QIF file.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct QifPreview {
+    pub rows: Vec<PreviewRow>,
+    /// Categories of the file that match no category, imported as `other`. They can be
+    /// mapped with [`set_category_mapping`] before importing.
+    pub unmapped: Vec<String>,
+}
+
+/// List the mappings of QIF category names, sorted by name.
+pub async fn read_category_mappings(
+    db: &Surreal<Db>,
+) -> Result<Vec<QifCategoryMapping>, surrealdb::Error> {
+    db.query("SELECT name, category FROM qif_category ORDER BY name")
+        .await?
+        .take(0)
+}
+
+/// Map a QIF category name to a category, replacing any previous mapping of the name.
+pub async fn set_category_mapping(
+    db: &Surreal<Db>,
+    mapping: QifCategoryMapping,
+) -> Result<(), surrealdb::Error> {
+    db.query(r#"UPSERT type::thing("qif_category", $name) SET name = $name, category = $category"#)
+        .bind(("name", mapping.name))
+        .bind(("category", mapping.category))
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+pub async fn delete_category_mapping(
+    db: &Surreal<Db>,
+    name: String,
+) -> Result<(), surrealdb::Error> {
+    db.query("DELETE qif_category WHERE name = $name")
+        .bind(("name", name))
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// Get the full name of each category by id, like `Food:Groceries`.
+async fn category_paths(db: &Surreal<Db>) -> Result<HashMap<String, String>, surrealdb::Error> {
+    let categories = transaction::category::read(db).await?;
+    let parents: HashMap<_, _> = categories
+        .iter()
+        .map(|category| (category.id.to_string(), category))
+        .collect();
+
+    Ok(categories
+        .iter()
+        .map(|category| {
+            let mut path = vec![category.name.as_str()];
+            let mut parent = category.parent.as_ref();
+
+            // Stop on cycles, which should not exist but would loop forever.
+            while let Some(current) = parent.and_then(|parent| parents.get(&parent.to_string())) {
+                if path.len() > parents.len() {
+                    break;
+                }
+
+                path.insert(0, current.name.as_str());
+                parent = current.parent.as_ref();
+            }
+
+            (category.id.to_string(), path.join(":"))
+        })
+        .collect())
+}
+
+/// Categories of the application by lowercase QIF name. Mappings take precedence over
+/// the full names of the categories, which take precedence over their own names.
+async fn categories(db: &Surreal<Db>) -> Result<HashMap<String, RecordId>, surrealdb::Error> {
+    let paths = category_paths(db).await?;
+    let mut categories = HashMap::new();
+
+    for category in transaction::category::read(db).await? {
+        categories.insert(category.name.to_lowercase(), category.id.clone());
+
+        if let Some(path) = paths.get(&category.id.to_string()) {
+            categories.insert(path.to_lowercase(), category.id);
+        }
+    }
+
+    for mapping in read_category_mappings(db).await? {
+        categories.insert(mapping.name.to_lowercase(), mapping.category);
+    }
+
+    Ok(categories)
+}
+
+/// Find the category of a QIF category name. The class after `/` is ignored, and
+/// transfers to other accounts, written `[Account]`, are internal movements.
+fn resolve(categories: &HashMap<String, RecordId>, name: &str) -> Option<RecordId> {
+    let name = name.trim();
+
+    if let Some(category) = categories.get(&name.to_lowercase()) {
+        return Some(category.clone());
+    }
+
+    let category = name.split('/').next().unwrap_or_default().trim();
+
+    if category.starts_with('[') && category.ends_with(']') {
+        return Some(("category", "internal-movements").into());
+    }
+
+    categories.get(&category.to_lowercase()).cloned()
+}
+
+/// Fields of a QIF transaction, ended by a `^` line.
+struct Record {
+    /// Line the record starts on.
+    line: usize,
+    /// Type of the section of the record, like `Bank`.
+    section: String,
+    /// Fields of the record by code, in the order of the file.
+    fields: Vec<(char, String)>,
+}
+
+/// Split QIF text into records.
+fn records(text: &str) -> Vec<Record> {
+    let mut records = vec![];
+    let mut section = String::new();
+    let mut fields = vec![];
+    let mut start = 1;
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+
+        if fields.is_empty() {
+            start = index + 1;
+        }
+
+        if let Some(header) = line.strip_prefix('!') {
+            section = header
+                .strip_prefix("Type:")
+                .unwrap_or(header)
+                .trim()
+                .to_string();
+            fields.clear();
+        } else if line.starts_with('^') {
+            records.push(Record {
+                line: start,
+                section: section.clone(),
+                fields: std::mem::take(&mut fields),
+            });
+        } else if let Some(code) = line.chars().next() {
+            fields.push((code, line[code.len_utf8()..].to_string()));
+        }
+    }
+
+    if !fields.is_empty() {
+        records.push(Record {
+            line: start,
+            section,
+            fields,
+        });
+    }
+
+    records
+}
+
+/// Parse a QIF date. Two digit years are in the 2000s after a `'`, like `1/5'26`,
+/// and in the 1900s otherwise, like `1/5/98`.
+fn parse_date(value: &str, format: &str) -> Option<chrono::NaiveDate> {
+    let apostrophe = value.contains('\'');
+    let value: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c == '\'' { '/' } else { c })
+        .collect();
+    let date = chrono::NaiveDate::parse_from_str(&value, format).ok()?;
+
+    match date.year() {
+        year @ 0..=99 if apostrophe => date.with_year(year + 2000),
+        year @ 0..=99 => date.with_year(year + 1900),
+        _ => Some(date),
+    }
+}
+
+fn parse_record(
+    fields: &[(char, String)],
+    options: &QifOptions,
+    categories: &HashMap<String, RecordId>,
+    unmapped: &mut Vec<String>,
+) -> Result<AddTransactionOptions, String> {
+    let decimal_separator = options.decimal_separator.chars().next().unwrap_or('.');
+    let amount = |value: &str| {
+        parse_amount(value, decimal_separator).ok_or_else(|| format!("invalid amount {value:?}"))
+    };
+    let mut category = |name: &str| {
+        let category = resolve(categories, name);
+
+        if category.is_none() && !unmapped.iter().any(|unmapped| unmapped == name) {
+            unmapped.push(name.to_string());
+        }
+
+        category
+    };
+
+    let mut transaction = AddTransactionOptions::default();
+    let mut date = None;
+    let mut total = None;
+    let mut payee = "";
+    let mut memo = "";
+
+    for (code, value) in fields {
+        let value = value.trim();
+
+        match code {
+            'D' => {
+                date = Some(
+                    parse_date(value, &options.date_format)
+                        .ok_or_else(|| format!("invalid date {value:?}"))?,
+                )
+            }
+            'T' | 'U' if total.is_none() => total = Some(amount(value)?),
+            'P' => payee = value,
+            'M' => memo = value,
+            'L' if !value.is_empty() => transaction.category = category(value),
+            'S' => transaction.splits.push(Split {
+                category: category(value).unwrap_or(("category", "other").into()),
+                amount: Money::default(),
+                description: String::new(),
+                tags: vec![],
+            }),
+            'E' => {
+                if let Some(split) = transaction.splits.last_mut() {
+                    split.description = value.to_string();
+                }
+            }
+            '$' => {
+                if let Some(split) = transaction.splits.last_mut() {
+                    split.amount = amount(value)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let date = date.ok_or("missing date")?;
+
+    transaction.amount = total.ok_or("missing amount")?;
+    transaction.date = Some(date.and_time(chrono::NaiveTime::MIN).and_utc());
+    // Memos starting with the payee, like the ones written by `export`, already are
+    // the whole description.
+    transaction.description = if memo.to_lowercase().starts_with(&payee.to_lowercase()) {
+        memo.to_string()
+    } else {
+        [payee, memo]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let splits: Money = transaction.splits.iter().map(|split| split.amount).sum();
+
+    if !transaction.splits.is_empty() && splits != transaction.amount {
+        return Err(format!(
+            "splits add up to {splits} instead of {}",
+            transaction.amount
+        ));
+    }
+
+    Ok(transaction)
+}
+
+/// Parse the transactions of the `Bank`, `CCard` and `Cash` sections of QIF content.
+/// Categories are resolved with the mappings and the categories of the database.
+/// Nothing is written to the database.
+pub async fn parse(
+    db: &Surreal<Db>,
+    content: &[u8],
+    options: &QifOptions,
+) -> Result<QifPreview, Error> {
+    let text = decode(content, &options.encoding)?;
+    let categories = categories(db).await?;
+    let mut unmapped = vec![];
+    let mut rows = vec![];
+
+    for record in records(&text) {
+        let result = match record.section.as_str() {
+            "Bank" | "CCard" | "Cash" => {
+                parse_record(&record.fields, options, &categories, &mut unmapped)
+            }
+            "Invst" | "Oth A" | "Oth L" => Err(format!("unsupported section {}", record.section)),
+            // Lists of accounts, categories, classes or memorized transactions.
+            _ => continue,
+        };
+
+        rows.push(match result {
+            Ok(transaction) => PreviewRow {
+                line: record.line,
+                transaction: Some(transaction),
+                error: None,
+            },
+            Err(error) => PreviewRow {
+                line: record.line,
+                transaction: None,
+                error: Some(error),
+            },
+        });
+    }
+
+    Ok(QifPreview { rows, unmapped })
+}
+
+/// Parse a QIF file. Nothing is written to the database.
+pub async fn preview(
+    db: &Surreal<Db>,
+    path: &str,
+    options: &QifOptions,
+) -> Result<QifPreview, Error> {
+    parse(db, &read_file(path)?, options).await
+}
+
+fn format_amount(amount: Money, options: &QifOptions) -> String {
+    match options.decimal_separator.chars().next() {
+        Some(separator) if separator != '.' => {
+            amount.to_string().replace('.', &separator.to_string())
+        }
+        _ => amount.to_string(),
+    }
+}
+
+/// Write the transactions of an account as QIF, oldest first. Credit card and cash
+/// accounts are written as `CCard` and `Cash` sections, other accounts as `Bank`.
+/// Descriptions are written as memos, after the name of the payee if there is one,
+/// and transfers are written as `[Account]` categories with the other account.
+pub async fn export(
+    db: &Surreal<Db>,
+    account: RecordId,
+    options: &QifOptions,
+) -> Result<Vec<u8>, Error> {
+    let encoding = encoding_rs::Encoding::for_label(options.encoding.trim().as_bytes())
+        .ok_or_else(|| Error::Import(ImportError::UnknownEncoding(options.encoding.clone())))?;
+
+    // Formatting a date with an invalid format would panic.
+    if chrono::format::StrftimeItems::new(&options.date_format)
+        .any(|item| item == chrono::format::Item::Error)
+    {
+        return Err(Error::Import(ImportError::InvalidDateFormat(
+            options.date_format.clone(),
+        )));
+    }
+
+    let section = match account::read(db, account.clone()).await?.data.kind {
+        AccountKind::CreditCard => "CCard",
+        AccountKind::Cash => "Cash",
+        _ => "Bank",
+    };
+    let paths = category_paths(db).await?;
+    let category = |id: &RecordId| paths.get(&id.to_string()).cloned().unwrap_or_default();
+    let payees: HashMap<_, _> = transaction::payee::read(db)
+        .await?
+        .into_iter()
+        .map(|payee| (payee.id.to_string(), payee.name))
+        .collect();
+    let transfers: HashMap<String, String> = db
+        .query(
+            r#"SELECT VALUE [id, (IF from = $account THEN to ELSE from END).name]
+            FROM transfer WHERE from = $account OR to = $account"#,
+        )
+        .bind(("account", account.clone()))
+        .await?
+        .take::<Vec<(RecordId, String)>>(0)?
+        .into_iter()
+        .map(|(id, name)| (id.to_string(), name))
+        .collect();
+    let mut transactions =
+        transaction::read(db, account, Some(ReadTransactionOptions::default())).await?;
+    let mut text = format!("!Type:{section}\n");
+
+    transactions.sort_by_key(|transaction| transaction.inner.date);
+
+    for transaction in transactions {
+        text.push_str(&format!(
+            "D{}\nT{}\n",
+            transaction.inner.date.format(&options.date_format),
+            format_amount(transaction.inner.amount, options),
+        ));
+
+        if let Some(payee) = transaction
+            .payee
+            .as_ref()
+            .and_then(|id| payees.get(&id.to_string()))
+        {
+            text.push_str(&format!("P{}\n", payee.replace('\n', " ")));
+        }
+
+        let target = match transaction
+            .transfer
+            .as_ref()
+            .and_then(|id| transfers.get(&id.to_string()))
+        {
+            Some(account) => format!("[{account}]"),
+            None => category(&transaction.category),
+        };
+
+        text.push_str(&format!(
+            "M{}\nL{target}\n",
+            transaction.inner.description.replace('\n', " "),
+        ));
+
+        for split in &transaction.splits {
+            text.push_str(&format!(
+                "S{}\nE{}\n${}\n",
+                category(&split.category),
+                split.description.replace('\n', " "),
+                format_amount(split.amount, options),
+            ));
+        }
+
+        text.push_str("^\n");
+    }
+
+    let (content, _, _) = encoding.encode(&text);
+
+    Ok(content.into_owned())
+}
+
+/// Write the transactions of an account to a QIF file.
+pub async fn export_to_file(
+    db: &Surreal<Db>,
+    account: RecordId,
+    path: &str,
+    options: &QifOptions,
+) -> Result<(), Error> {
+    let content = export(db, account, options).await?;
+
+    std::fs::write(path, content).map_err(|error| Error::Import(ImportError::Io(error)))
+}
@@ -1,9 +1,18 @@
+use account::reconciliation::ReconciliationError;
 use chrono::offset::LocalResult;
+use import::ImportError;
+use money::Money;
 use surrealdb::RecordId;
+use transaction::{
+    attachment::AttachmentError, category::CategoryError, payee::PayeeError, rule::RuleError,
+};
 
 pub mod account;
 pub mod budget;
+pub mod fx;
+pub mod import;
 pub mod migrations;
+pub mod money;
 pub mod portfolio;
 pub mod settings;
 pub mod transaction;
@@ -50,6 +59,21 @@ impl std::fmt::Display for ChronoLocalResultError {
 pub enum Error {
     Database(surrealdb::Error),
     RecordNotFound,
+    ExchangeRateNotFound {
+        base: String,
+        quote: String,
+    },
+    /// The splits of a transaction do not add up to its amount.
+    InvalidSplits {
+        amount: Money,
+        splits: Money,
+    },
+    Category(CategoryError),
+    Reconciliation(ReconciliationError),
+    Import(ImportError),
+    Rule(RuleError),
+    Payee(PayeeError),
+    Attachment(AttachmentError),
     Time(ChronoLocalResultError),
     TimeFormat(time::error::Format),
 }
@@ -63,6 +87,30 @@ impl Error {
             Self::RecordNotFound => {
                 tracing::error!("Record not found");
             }
+            Self::ExchangeRateNotFound { base, quote } => {
+                tracing::error!(%base, %quote, "Exchange rate not found");
+            }
+            Self::InvalidSplits { amount, splits } => {
+                tracing::error!(%amount, %splits, "Splits do not add up to the transaction amount");
+            }
+            Self::Category(error) => {
+                tracing::error!(%error, "Category error");
+            }
+            Self::Reconciliation(error) => {
+                tracing::error!(%error, "Reconciliation error");
+            }
+            Self::Import(error) => {
+                tracing::error!(%error, "Import error");
+            }
+            Self::Rule(error) => {
+                tracing::error!(%error, "Rule error");
+            }
+            Self::Payee(error) => {
+                tracing::error!(%error, "Payee error");
+            }
+            Self::Attachment(error) => {
+                tracing::error!(%error, "Attachment error");
+            }
             Self::Time(error) => {
                 tracing::error!(%error, "Time error");
             }
@@ -85,6 +133,17 @@ impl std::fmt::Display for Error {
             match self {
                 Error::Database(error) => error.to_string(),
                 Error::RecordNotFound => "Record not found".to_string(),
+                Error::ExchangeRateNotFound { base, quote } =>
+                    format!("No exchange rate found from {base} to {quote}"),
+                Error::InvalidSplits { amount, splits } => format!(
+                    "Splits add up to {splits} instead of the transaction amount of {amount}"
+                ),
+                Error::Category(error) => error.to_string(),
+                Error::Reconciliation(error) => error.to_string(),
+                Error::Import(error) => error.to_string(),
+                Error::Rule(error) => error.to_string(),
+                Error::Payee(error) => error.to_string(),
+                Error::Attachment(error) => error.to_string(),
                 Error::Time(error) => error.to_string(),
                 Error::TimeFormat(error) => error.to_string(),
             }
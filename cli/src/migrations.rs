@@ -1,6 +1,11 @@
 use surrealdb::{engine::local::Db, Surreal};
 
 pub mod m1_account_filter;
+pub mod m2_decimal_amounts;
+pub mod m3_account_metadata;
+pub mod m4_opening_balance;
+pub mod m5_transaction_sort;
+pub mod m6_transaction_filters;
 
 pub trait Migration {
     fn name(&self) -> &'static str;
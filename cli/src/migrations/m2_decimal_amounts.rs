@@ -0,0 +1,21 @@
+use crate::migrations::Migration;
+
+pub struct DecimalAmountsMigration {}
+
+impl Migration for DecimalAmountsMigration {
+    fn name(&self) -> &'static str {
+        "Storing transaction, allocation and budget amounts as decimals"
+    }
+
+    fn version(&self) -> &'static str {
+        "0.6.0"
+    }
+
+    fn up(&self) -> &'static str {
+        include_str!("m2/up.surql")
+    }
+
+    fn down(&self) -> &'static str {
+        include_str!("m2/down.surql")
+    }
+}
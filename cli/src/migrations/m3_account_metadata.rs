@@ -0,0 +1,21 @@
+use crate::migrations::Migration;
+
+pub struct AccountMetadataMigration {}
+
+impl Migration for AccountMetadataMigration {
+    fn name(&self) -> &'static str {
+        "Adding kind, archived flag and position to accounts"
+    }
+
+    fn version(&self) -> &'static str {
+        "0.6.1"
+    }
+
+    fn up(&self) -> &'static str {
+        include_str!("m3/up.surql")
+    }
+
+    fn down(&self) -> &'static str {
+        include_str!("m3/down.surql")
+    }
+}
@@ -0,0 +1,21 @@
+use crate::migrations::Migration;
+
+pub struct OpeningBalanceMigration {}
+
+impl Migration for OpeningBalanceMigration {
+    fn name(&self) -> &'static str {
+        "Adding an opening balance to accounts"
+    }
+
+    fn version(&self) -> &'static str {
+        "0.6.2"
+    }
+
+    fn up(&self) -> &'static str {
+        include_str!("m4/up.surql")
+    }
+
+    fn down(&self) -> &'static str {
+        include_str!("m4/down.surql")
+    }
+}
@@ -0,0 +1,21 @@
+use crate::migrations::Migration;
+
+pub struct TransactionSortMigration {}
+
+impl Migration for TransactionSortMigration {
+    fn name(&self) -> &'static str {
+        "Adding the sort of transactions to accounts"
+    }
+
+    fn version(&self) -> &'static str {
+        "0.6.3"
+    }
+
+    fn up(&self) -> &'static str {
+        include_str!("m5/up.surql")
+    }
+
+    fn down(&self) -> &'static str {
+        include_str!("m5/down.surql")
+    }
+}
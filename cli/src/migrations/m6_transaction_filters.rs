@@ -0,0 +1,21 @@
+use crate::migrations::Migration;
+
+pub struct TransactionFiltersMigration {}
+
+impl Migration for TransactionFiltersMigration {
+    fn name(&self) -> &'static str {
+        "Replacing the category of account filters with a list of categories"
+    }
+
+    fn version(&self) -> &'static str {
+        "0.6.4"
+    }
+
+    fn up(&self) -> &'static str {
+        include_str!("m6/up.surql")
+    }
+
+    fn down(&self) -> &'static str {
+        include_str!("m6/down.surql")
+    }
+}
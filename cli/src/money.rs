@@ -0,0 +1,272 @@
+use rust_decimal::Decimal;
+
+/// An exact monetary amount.
+///
+/// Amounts are fixed-point decimals (an integer number of minor units and a scale),
+/// so adding thousands of transactions never drifts like `f64` does.
+///
+/// An amount does not carry its currency: the currency is the one of the account or
+/// budget it belongs to. Amounts are stored as plain SurrealDB `decimal`s so that queries
+/// can still sum and compare them with `math::sum`, `>=`, etc., which would not work on
+/// an amount stored along its currency. Code mixing accounts must group amounts by
+/// currency itself, like balances and tag usage do.
+///
+/// Serialized as a SurrealDB `decimal` when written to the database, without loss, and
+/// as a JSON number when sent to the frontend.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(#[ts(type = "number")] Decimal);
+
+impl Money {
+    pub const ZERO: Self = Self(Decimal::ZERO);
+
+    /// Create an amount from an integer number of minor units, e.g. `(1234, 2)` is `12.34`.
+    pub fn from_minor_units(units: i64, scale: u32) -> Self {
+        Self(Decimal::new(units, scale))
+    }
+
+    /// Get the amount as an integer number of minor units for the given scale,
+    /// rounding half away from zero.
+    pub fn to_minor_units(self, scale: u32) -> Option<i64> {
+        use rust_decimal::prelude::ToPrimitive;
+
+        let mut rounded = self
+            .0
+            .round_dp_with_strategy(scale, rust_decimal::RoundingStrategy::MidpointAwayFromZero);
+        rounded.rescale(scale);
+        rounded.mantissa().to_i64()
+    }
+
+    /// Get the underlying decimal.
+    pub fn to_decimal(self) -> Decimal {
+        self.0
+    }
+
+    /// Lossy conversion used for charts and other approximate computations.
+    pub fn to_f64(self) -> f64 {
+        use rust_decimal::prelude::ToPrimitive;
+
+        self.0.to_f64().unwrap_or_default()
+    }
+
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn is_sign_negative(self) -> bool {
+        self.0.is_sign_negative() && !self.0.is_zero()
+    }
+
+    /// Round to `scale` decimal places, half away from zero.
+    pub fn round(self, scale: u32) -> Self {
+        Self(
+            self.0.round_dp_with_strategy(
+                scale,
+                rust_decimal::RoundingStrategy::MidpointAwayFromZero,
+            ),
+        )
+    }
+
+    /// Round to the minor unit of an ISO 4217 currency, half away from zero, e.g. to
+    /// cents for `EUR`. Amounts computed from other amounts, like conversions and
+    /// averages, are rounded with the currency they are shown in.
+    pub fn round_to_currency(self, currency: &str) -> Self {
+        self.round(minor_units(currency))
+    }
+}
+
+/// Number of decimal places of the minor unit of an ISO 4217 currency, e.g. 2 for
+/// `EUR` cents and 0 for `JPY`. Unknown currencies default to 2.
+pub fn minor_units(currency: &str) -> u32 {
+    match currency.trim().to_uppercase().as_str() {
+        "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF" | "UGX"
+        | "UYI" | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+        "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+        "CLF" | "UYW" => 4,
+        _ => 2,
+    }
+}
+
+impl From<Decimal> for Money {
+    fn from(value: Decimal) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Money> for Decimal {
+    fn from(value: Money) -> Self {
+        value.0
+    }
+}
+
+impl From<i64> for Money {
+    fn from(value: i64) -> Self {
+        Self(Decimal::from(value))
+    }
+}
+
+impl TryFrom<f64> for Money {
+    type Error = rust_decimal::Error;
+
+    /// Keeps the shortest decimal representation of the float, so `0.1` becomes exactly `0.1`.
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Decimal::try_from(value).map(|value| Self(value.normalize()))
+    }
+}
+
+impl std::str::FromStr for Money {
+    type Err = rust_decimal::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Decimal::from_str_exact(s.trim()).map(Self)
+    }
+}
+
+impl std::fmt::Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::ops::Add for Money {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl std::ops::AddAssign for Money {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl std::ops::Sub for Money {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl std::ops::SubAssign for Money {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl std::ops::Neg for Money {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl std::ops::Mul<Decimal> for Money {
+    type Output = Self;
+
+    fn mul(self, rhs: Decimal) -> Self::Output {
+        Self(self.0 * rhs)
+    }
+}
+
+impl std::iter::Sum for Money {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, money| acc + money)
+    }
+}
+
+impl<'a> std::iter::Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, money| acc + *money)
+    }
+}
+
+impl serde::Serialize for Money {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        decimal::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Money {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        decimal::deserialize(deserializer).map(Self)
+    }
+}
+
+/// Serde helpers for decimals that are not amounts (e.g. exchange rates),
+/// following the same representation as [`Money`].
+///
+/// Use with `#[serde(with = "crate::money::decimal")]`.
+pub mod decimal {
+    use rust_decimal::Decimal;
+    use serde::Serialize;
+
+    pub fn serialize<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use rust_decimal::prelude::ToPrimitive;
+
+        // Note: the frontend only handles numbers, and SurrealDB serializes values in a
+        //       compact, non human readable form. Write an exact SurrealDB decimal in the
+        //       latter case, otherwise decimals would be stored as floats or strings.
+        if serializer.is_human_readable() {
+            serializer.serialize_f64(value.to_f64().unwrap_or_default())
+        } else {
+            surrealdb::sql::Number::Decimal(*value).serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+
+    struct DecimalVisitor;
+
+    impl serde::de::Visitor<'_> for DecimalVisitor {
+        type Value = Decimal;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a decimal number or a string containing a decimal number")
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            Ok(Decimal::from(v))
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(Decimal::from(v))
+        }
+
+        // Floats come from the frontend and from data stored before the decimal migration.
+        fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            Decimal::try_from(v)
+                .map(|v| v.normalize())
+                .map_err(E::custom)
+        }
+
+        // SurrealDB decimals are deserialized as strings.
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            v.parse::<Decimal>()
+                .or_else(|_| Decimal::from_scientific(v))
+                .map_err(E::custom)
+        }
+    }
+}
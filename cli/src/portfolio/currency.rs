@@ -1,6 +1,6 @@
 use surrealdb::{engine::local::Db, Surreal};
 
-use crate::{transaction::AccountWithBalance, Error};
+use crate::{money::Money, transaction::AccountWithBalance, Error};
 
 #[derive(ts_rs::TS)]
 #[ts(export, rename = "CurrencyTileType")]
@@ -22,7 +22,7 @@ pub struct Tile {
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct Currency {
     pub currency: String,
-    pub total_balance: f64,
+    pub total_balance: Money,
     pub accounts: Vec<AccountWithBalance>,
 }
 
@@ -1,6 +1,8 @@
+use std::path::{Path, PathBuf};
+
 use surrealdb::{engine::local::Db, Surreal};
 
-use crate::{Error, Record, TIME_FORMAT};
+use crate::{transaction::attachment, Error, Record, TIME_FORMAT};
 
 #[derive(ts_rs::TS)]
 #[ts(export)]
@@ -50,7 +52,18 @@ pub async fn save(db: &Surreal<Db>, settings: Settings) -> Result<(), Error> {
     Ok(())
 }
 
-pub async fn import_backup(db: &Surreal<Db>, path: &str) -> Result<(), Error> {
+/// Directory of the attached files of a backup, next to the backup file.
+fn attachments_backup_path(backup: &Path) -> PathBuf {
+    let mut name = backup.file_name().unwrap_or_default().to_os_string();
+
+    name.push(".attachments");
+
+    backup.with_file_name(name)
+}
+
+/// Replace the database with a backup, and restore the attached files saved with it
+/// into the `attachments` directory.
+pub async fn import_backup(db: &Surreal<Db>, path: &str, attachments: &Path) -> Result<(), Error> {
     db.query(
         r#"
 REMOVE DATABASE accounts;
@@ -59,10 +72,15 @@ REMOVE NAMESPACE user;
     )
     .await?;
 
-    db.import(path).await.map_err(Error::Database)
+    db.import(path).await.map_err(Error::Database)?;
+
+    attachment::restore(&attachments_backup_path(Path::new(path)), attachments)?;
+    attachment::prune(db, attachments).await
 }
 
-pub async fn export_backup(db: &Surreal<Db>) -> Result<(), Error> {
+/// Export the database in the backups directory, with a copy of the files of the
+/// `attachments` directory that are attached to transactions.
+pub async fn export_backup(db: &Surreal<Db>, attachments: &Path) -> Result<(), Error> {
     let settings: Settings = db
         .select(("settings", "main"))
         .await?
@@ -76,5 +94,7 @@ pub async fn export_backup(db: &Surreal<Db>) -> Result<(), Error> {
             .map_err(Error::TimeFormat)?,
     );
 
-    db.export(path).await.map_err(Error::Database)
+    db.export(&path).await.map_err(Error::Database)?;
+
+    attachment::backup(db, attachments, &attachments_backup_path(&path)).await
 }
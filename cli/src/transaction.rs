@@ -1,9 +1,21 @@
-use surrealdb::{engine::local::Db, RecordId, Surreal};
+use surrealdb::{engine::local::Db, sql::Number, RecordId, Surreal};
 
-use crate::{account::Account, transaction::tag::Tag, Error};
+use crate::{
+    account::{reconciliation::ReconciliationError, Account},
+    money::Money,
+    transaction::tag::Tag,
+    Error,
+};
 
+pub mod attachment;
 pub mod category;
+pub mod duplicate;
+pub mod payee;
+pub mod recurring;
+pub mod rule;
+pub mod suggestion;
 pub mod tag;
+pub mod transfer;
 
 #[derive(ts_rs::TS)]
 #[ts(export)]
@@ -11,11 +23,25 @@ pub mod tag;
 pub struct Transaction {
     #[ts(as = "String")]
     pub date: chrono::DateTime<chrono::Utc>,
-    pub amount: f64,
+    pub amount: Money,
     pub description: String,
     pub tags: Vec<Tag>,
 }
 
+/// Reconciliation state of a transaction against the statements of its account.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Status {
+    /// Not seen on a statement yet.
+    #[default]
+    Pending,
+    /// Seen on the statement of an unfinished reconciliation.
+    Cleared,
+    /// Locked by a finished reconciliation.
+    Reconciled,
+}
+
 #[derive(ts_rs::TS)]
 #[ts(export)]
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -28,26 +54,134 @@ pub struct TransactionWithId {
     pub account: RecordId,
     #[ts(type = "{ tb: string, id: { String: string }}")]
     pub category: RecordId,
+    /// Set on both transactions of a transfer between two accounts.
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub transfer: Option<RecordId>,
+    /// Recurring transaction this transaction was created from.
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub recurring: Option<RecordId>,
+    /// Parts of the transaction attributed to their own category. When not empty,
+    /// the category of the transaction is ignored by budgets.
+    #[serde(default)]
+    pub splits: Vec<Split>,
+    #[serde(default)]
+    pub status: Status,
+    /// Identifier of the transaction in the file it was imported from.
+    #[ts(optional)]
+    pub external_id: Option<String>,
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub payee: Option<RecordId>,
+}
+
+impl TransactionWithId {
+    /// Get the parts of the transaction attributed to each category: one per split,
+    /// or the transaction itself if it is not split. Splits keep the id of their transaction.
+    pub fn by_category(&self) -> Vec<TransactionWithId> {
+        if self.splits.is_empty() {
+            return vec![self.clone()];
+        }
+
+        self.splits
+            .iter()
+            .map(|split| TransactionWithId {
+                inner: Transaction {
+                    date: self.inner.date,
+                    amount: split.amount,
+                    description: if split.description.is_empty() {
+                        self.inner.description.clone()
+                    } else {
+                        split.description.clone()
+                    },
+                    tags: split.tags.clone(),
+                },
+                id: self.id.clone(),
+                account: self.account.clone(),
+                category: split.category.clone(),
+                transfer: self.transfer.clone(),
+                recurring: self.recurring.clone(),
+                splits: vec![],
+                status: self.status,
+                external_id: self.external_id.clone(),
+                payee: self.payee.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Part of a split transaction.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Split {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub category: RecordId,
+    pub amount: Money,
+    pub description: String,
+    pub tags: Vec<Tag>,
+}
+
+/// Get the sum of the splits if it does not match the amount of the transaction.
+fn invalid_splits_total(amount: Money, splits: &[Split]) -> Option<Money> {
+    let total = splits.iter().map(|split| split.amount).sum();
+
+    (!splits.is_empty() && total != amount).then_some(total)
 }
 
 #[derive(ts_rs::TS)]
 #[ts(export)]
-#[derive(Default, Debug, serde::Deserialize)]
+#[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct AddTransactionOptions {
-    pub amount: f64,
+    pub amount: Money,
     #[ts(type = "{ tb: string, id: { String: string }}", optional)]
     pub category: Option<surrealdb::RecordId>,
     pub description: String,
     pub tags: Vec<Tag>,
     #[ts(as = "Option<String>", optional)]
     pub date: Option<chrono::DateTime<chrono::Utc>>,
+    /// Splits of the transaction, which must add up to `amount`.
+    #[serde(default)]
+    pub splits: Vec<Split>,
+    /// Identifier of the transaction in the file it is imported from, used to
+    /// skip transactions that were already imported.
+    #[ts(optional)]
+    #[serde(default)]
+    pub external_id: Option<String>,
+    /// Payee of the transaction, found from the description if not given.
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    #[serde(default)]
+    pub payee: Option<RecordId>,
 }
 
+/// Create a transaction, after applying the rules to it and finding its payee.
 pub async fn create_transaction(
+    db: &Surreal<Db>,
+    account_id: RecordId,
+    mut options: AddTransactionOptions,
+) -> Result<TransactionWithId, Error> {
+    rule::Rules::load(db)
+        .await?
+        .apply(&account_id, &mut options);
+    payee::Payees::load(db).await?.apply(&mut options);
+
+    insert(db, account_id, options).await
+}
+
+/// Create a transaction as is, without applying the rules to it. Its tags missing from
+/// the tag list are added to it.
+pub(crate) async fn insert(
     db: &Surreal<Db>,
     account_id: RecordId,
     options: AddTransactionOptions,
 ) -> Result<TransactionWithId, Error> {
+    if let Some(splits) = invalid_splits_total(options.amount, &options.splits) {
+        return Err(Error::InvalidSplits {
+            amount: options.amount,
+            splits,
+        });
+    }
+
+    tag::create_missing(db, &options.tags).await?;
+
     let query = r#"
     CREATE transaction SET
         date = <datetime>$date,
@@ -55,7 +189,10 @@ pub async fn create_transaction(
         amount = $amount,
         description = $description,
         tags = $tags,
-        account = $account_id"#;
+        account = $account_id,
+        external_id = $external_id,
+        payee = $payee,
+        splits = $splits"#;
 
     let transaction: Option<TransactionWithId> = db
         .query(query)
@@ -68,6 +205,9 @@ pub async fn create_transaction(
         .bind(("description", options.description))
         .bind(("tags", serde_json::json!(options.tags)))
         .bind(("account_id", account_id))
+        .bind(("external_id", options.external_id))
+        .bind(("payee", options.payee))
+        .bind(("splits", options.splits))
         .await?
         .take(0)?;
 
@@ -79,7 +219,16 @@ pub async fn create_transaction(
 #[derive(Debug, serde::Deserialize)]
 pub struct AddTransactionTransferOptions {
     pub description: String,
-    pub amount: f64,
+    /// Amount sent from the `from` account, in its currency.
+    pub amount: Money,
+    /// Amount received on the `to` account, in its currency. Defaults to `amount`
+    /// converted with the exchange rate at the date of the transfer, rounded to the
+    /// minor unit of the currency.
+    #[ts(optional)]
+    pub received: Option<Money>,
+    /// Fee paid on the `from` account on top of `amount`, in its currency.
+    #[ts(optional)]
+    pub fee: Option<Money>,
     #[ts(as = "Option<String>", optional)]
     pub date: Option<chrono::DateTime<chrono::Utc>>,
     pub tags: Vec<Tag>,
@@ -89,31 +238,70 @@ pub struct AddTransactionTransferOptions {
     pub to: RecordId,
 }
 
+/// Create both transactions of a transfer, linked by a shared `transfer` record.
+/// Returns the transaction of the `from` account.
 pub async fn create_transaction_transfer(
     db: &Surreal<Db>,
     options: AddTransactionTransferOptions,
 ) -> Result<TransactionWithId, Error> {
+    let date = options.date.unwrap_or_else(chrono::Utc::now);
+    let fee = options.fee.unwrap_or_default();
+    let received = match options.received {
+        Some(received) => received,
+        None => {
+            let from = crate::account::get_currency(db, options.from.clone()).await?;
+            let to = crate::account::get_currency(db, options.to.clone()).await?;
+
+            if from == to {
+                options.amount
+            } else {
+                crate::fx::convert(db, options.amount, &from, &to, date)
+                    .await?
+                    .round_to_currency(&to)
+            }
+        }
+    };
+
     let query = r#"
+    BEGIN TRANSACTION;
+    LET $transfer = (CREATE ONLY transfer SET
+        from = $from,
+        to = $to,
+        sent = $sent,
+        received = $received,
+        fee = $fee,
+        rate = $rate
+    );
     CREATE transaction SET
         description = $description,
-        amount = -$amount,
+        amount = $debit,
         date = <datetime>$date,
         tags = $tags,
         account = $from,
-        category = $category;
+        category = $category,
+        transfer = $transfer.id;
     CREATE transaction SET
         description = $description,
-        amount = $amount,
+        amount = $received,
         date = <datetime>$date,
         tags = $tags,
         account = $to,
-        category = $category;"#;
+        category = $category,
+        transfer = $transfer.id;
+    COMMIT TRANSACTION;"#;
 
     let transaction: Option<TransactionWithId> = db
         .query(query)
         .bind(("description", options.description))
-        .bind(("amount", options.amount))
-        .bind(("date", options.date.unwrap_or_else(chrono::Utc::now)))
+        .bind(("sent", options.amount))
+        .bind(("received", received))
+        .bind(("fee", fee))
+        .bind((
+            "rate",
+            Number::Decimal(transfer::implied_rate(options.amount, received)),
+        ))
+        .bind(("debit", -(options.amount + fee)))
+        .bind(("date", date))
         .bind(("tags", serde_json::json!(options.tags)))
         .bind(("from", options.from))
         .bind(("to", options.to))
@@ -122,21 +310,53 @@ pub async fn create_transaction_transfer(
             RecordId::from(("category", "internal-movements")),
         ))
         .await?
-        .take(0)?;
+        .take(1)?;
 
     transaction.ok_or(Error::RecordNotFound)
 }
 
+/// Direction of the money of a transaction.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Flow {
+    /// Transactions with a positive amount.
+    Income,
+    /// Transactions with a negative amount.
+    Expense,
+}
+
+/// Filter on the labels of the tags of transactions.
+#[derive(ts_rs::TS)]
+#[ts(export, tag = "type", content = "data")]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum TagFilter {
+    /// Transactions with at least one of the tags.
+    Any(Vec<String>),
+    /// Transactions with all of the tags.
+    All(Vec<String>),
+    /// Transactions without tags.
+    Untagged,
+}
+
 #[derive(ts_rs::TS)]
 #[ts(export)]
 #[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ReadTransactionOptions {
     #[ts(as = "Option<String>", optional)]
     pub search: Option<String>,
-    // FIXME: Cannot store a record id here because of serde. Using the id string directly.
-    //        See https://github.com/surrealdb/surrealdb/issues/4844
-    #[ts(as = "Option<String>", optional)]
-    pub category: Option<String>,
+    /// Get transactions in one of these categories, or with a split in one of them.
+    /// All categories if empty.
+    #[ts(type = "{ tb: string, id: { String: string }}[]")]
+    #[serde(default)]
+    pub categories: Vec<RecordId>,
+    /// Also get transactions in the descendants of `categories`.
+    #[serde(default)]
+    pub include_subcategories: bool,
+    /// Only get transactions left in the default `other` category, without splits.
+    #[serde(default)]
+    pub uncategorized: bool,
     #[ts(as = "Option<String>", optional)]
     pub start: Option<surrealdb::Datetime>,
     #[ts(as = "Option<String>", optional)]
@@ -145,6 +365,16 @@ pub struct ReadTransactionOptions {
     /// Cannot be combined with `start` and `end` options.
     #[ts(optional)]
     pub last_x_days: Option<usize>,
+    /// Minimum amount, included. Expenses have negative amounts.
+    #[ts(optional)]
+    pub min_amount: Option<Money>,
+    /// Maximum amount, included.
+    #[ts(optional)]
+    pub max_amount: Option<Money>,
+    #[ts(optional)]
+    pub flow: Option<Flow>,
+    #[ts(optional)]
+    pub tags: Option<TagFilter>,
 }
 
 pub async fn read(
@@ -156,7 +386,7 @@ pub async fn read(
         Some(filter) => filter,
         None => {
             let filter: Option<ReadTransactionOptions> = db
-                .query(r#"SELECT filter FROM type::thing("account", $account_id)"#)
+                .query(r#"SELECT VALUE filter FROM ONLY type::thing("account", $account_id)"#)
                 .bind(("account_id", account_id.clone()))
                 .await?
                 .take(0)?;
@@ -164,32 +394,96 @@ pub async fn read(
         }
     };
 
-    let mut query = "SELECT * FROM transaction WHERE account = $account_id".to_string();
+    let query = format!(
+        "SELECT * FROM transaction WHERE account = $account_id{} ORDER BY date DESC",
+        filter_conditions(&filter)
+    );
+
+    let transactions: Vec<TransactionWithId> = bind_filter(db, db.query(query), filter)
+        .await?
+        .bind(("account_id", account_id))
+        .await?
+        .take(0)?;
+
+    Ok(transactions)
+}
+
+/// Get the conditions of a filter, to append to a `WHERE` clause.
+fn filter_conditions(filter: &ReadTransactionOptions) -> String {
+    let mut conditions = String::new();
 
     if filter.search.is_some() {
-        query.push_str(" AND string::lowercase($search) IN string::lowercase(description)");
+        conditions.push_str(" AND string::lowercase($search) IN string::lowercase(description)");
+    }
+
+    if !filter.categories.is_empty() {
+        conditions
+            .push_str(" AND (category IN $categories OR splits.category ANYINSIDE $categories)");
     }
 
-    if filter.category.is_some() {
-        query.push_str(" AND category = type::thing('category', $category)");
+    if filter.uncategorized {
+        conditions.push_str(" AND category = category:other AND !splits");
     }
 
     if filter.last_x_days.is_some() {
-        query.push_str(" AND date >= time::now() - $last_x_days AND date <= time::now()");
+        conditions.push_str(" AND date >= time::now() - $last_x_days AND date <= time::now()");
     } else {
         if filter.start.is_some() {
-            query.push_str(" AND date >= $start");
+            conditions.push_str(" AND date >= $start");
         }
 
         if filter.end.is_some() {
-            query.push_str(" AND date <= $end");
+            conditions.push_str(" AND date <= $end");
         }
     }
 
-    query.push_str(" ORDER BY date DESC");
+    if filter.min_amount.is_some() {
+        conditions.push_str(" AND amount >= $min_amount");
+    }
 
-    let transactions: Vec<TransactionWithId> = db
-        .query(query)
+    if filter.max_amount.is_some() {
+        conditions.push_str(" AND amount <= $max_amount");
+    }
+
+    match filter.flow {
+        Some(Flow::Income) => conditions.push_str(" AND amount > 0"),
+        Some(Flow::Expense) => conditions.push_str(" AND amount < 0"),
+        None => {}
+    }
+
+    match filter.tags {
+        Some(TagFilter::Any(_)) => conditions.push_str(" AND tags.label ANYINSIDE $tags"),
+        Some(TagFilter::All(_)) => conditions.push_str(" AND $tags ALLINSIDE tags.label"),
+        Some(TagFilter::Untagged) => conditions.push_str(" AND !tags"),
+        None => {}
+    }
+
+    conditions
+}
+
+/// Bind the parameters of the conditions of [`filter_conditions`]. The descendants of
+/// the categories of the filter are read if requested.
+async fn bind_filter<'a>(
+    db: &Surreal<Db>,
+    query: surrealdb::method::Query<'a, Db>,
+    filter: ReadTransactionOptions,
+) -> Result<surrealdb::method::Query<'a, Db>, surrealdb::Error> {
+    let mut categories = vec![];
+
+    for id in filter.categories {
+        if filter.include_subcategories {
+            categories.extend(category::with_descendants(db, id).await?);
+        } else {
+            categories.push(id);
+        }
+    }
+
+    let tags = match filter.tags {
+        Some(TagFilter::Any(tags) | TagFilter::All(tags)) => tags,
+        Some(TagFilter::Untagged) | None => vec![],
+    };
+
+    Ok(query
         .bind((
             "last_x_days",
             filter
@@ -198,44 +492,426 @@ pub async fn read(
                 .unwrap_or_default(),
         ))
         .bind(("search", filter.search.unwrap_or_default()))
-        .bind(("category", filter.category.unwrap_or("other".into())))
+        .bind(("categories", categories))
         .bind(("start", filter.start.unwrap_or_default()))
         .bind(("end", filter.end.unwrap_or_default()))
-        .bind(("account_id", account_id))
+        .bind(("min_amount", filter.min_amount.unwrap_or_default()))
+        .bind(("max_amount", filter.max_amount.unwrap_or_default()))
+        .bind(("tags", tags)))
+}
+
+/// Field transactions are sorted by.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SortKey {
+    #[default]
+    Date,
+    Amount,
+    Description,
+    /// Sort by the name of the category.
+    Category,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SortDirection {
+    Ascending,
+    #[default]
+    Descending,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TransactionSort {
+    pub key: SortKey,
+    pub direction: SortDirection,
+}
+
+impl TransactionSort {
+    /// Get the `ORDER BY` clause of the sort. Transactions with the same value are sorted
+    /// by date then id, so that pages do not overlap.
+    fn order_by(&self) -> String {
+        let direction = match self.direction {
+            SortDirection::Ascending => "ASC",
+            SortDirection::Descending => "DESC",
+        };
+
+        match self.key {
+            SortKey::Date => format!("ORDER BY date {direction}, id {direction}"),
+            SortKey::Amount => format!("ORDER BY amount {direction}, date DESC, id DESC"),
+            SortKey::Description => {
+                format!("ORDER BY description COLLATE {direction}, date DESC, id DESC")
+            }
+            SortKey::Category => {
+                format!("ORDER BY category_name COLLATE {direction}, date DESC, id DESC")
+            }
+        }
+    }
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReadTransactionPageOptions {
+    /// Filter of the transactions, the filter saved on the account if not set.
+    #[ts(optional)]
+    pub filter: Option<ReadTransactionOptions>,
+    /// Sort of the transactions, the sort saved on the account if not set.
+    #[ts(optional)]
+    pub sort: Option<TransactionSort>,
+    /// Number of transactions to skip.
+    #[serde(default)]
+    pub offset: usize,
+    /// Maximum number of transactions of the page, all of them if not set.
+    #[ts(optional)]
+    pub limit: Option<usize>,
+}
+
+/// A page of the transactions of an account.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TransactionPage {
+    pub transactions: Vec<TransactionWithId>,
+    /// Number of transactions matching the filter, on all pages.
+    pub total: usize,
+    /// Sum of the amounts of the transactions matching the filter, on all pages.
+    pub sum: Money,
+}
+
+#[derive(Default, serde::Deserialize)]
+struct PageSummary {
+    total: usize,
+    sum: Option<Money>,
+}
+
+/// Read a page of the transactions of an account, with the number and sum of all the
+/// transactions matching the filter.
+pub async fn read_page(
+    db: &Surreal<Db>,
+    account_id: RecordId,
+    options: ReadTransactionPageOptions,
+) -> Result<TransactionPage, Error> {
+    let (filter, sort) = match (options.filter, options.sort) {
+        (Some(filter), Some(sort)) => (filter, sort),
+        (filter, sort) => {
+            let account = crate::account::read(db, account_id.clone()).await?;
+
+            (
+                filter.unwrap_or(account.filter),
+                sort.unwrap_or(account.data.sort),
+            )
+        }
+    };
+
+    page(
+        db,
+        Some(vec![account_id]),
+        filter,
+        sort,
+        options.offset,
+        options.limit,
+    )
+    .await
+}
+
+/// Read a page of the transactions of the given accounts, or of all accounts, with the
+/// number and sum of all the transactions matching the filter.
+async fn page(
+    db: &Surreal<Db>,
+    accounts: Option<Vec<RecordId>>,
+    filter: ReadTransactionOptions,
+    sort: TransactionSort,
+    offset: usize,
+    limit: Option<usize>,
+) -> Result<TransactionPage, Error> {
+    let scope = if accounts.is_some() {
+        "account IN $accounts"
+    } else {
+        "true"
+    };
+    let conditions = format!("{scope}{}", filter_conditions(&filter));
+    let mut query = format!(
+        r#"
+    SELECT count() AS total, math::sum(amount) AS sum FROM transaction WHERE {conditions} GROUP ALL;
+    SELECT *, category.name AS category_name FROM transaction WHERE {conditions} {}"#,
+        sort.order_by()
+    );
+
+    if limit.is_some() {
+        query.push_str(" LIMIT $limit");
+    }
+
+    query.push_str(" START $offset;");
+
+    let mut response = bind_filter(db, db.query(query), filter)
+        .await?
+        .bind(("accounts", accounts.unwrap_or_default()))
+        .bind(("limit", limit.unwrap_or_default()))
+        .bind(("offset", offset))
+        .await?;
+    let summary: Option<PageSummary> = response.take(0)?;
+    let summary = summary.unwrap_or_default();
+
+    Ok(TransactionPage {
+        transactions: response.take(1)?,
+        total: summary.total,
+        sum: summary.sum.unwrap_or_default(),
+    })
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SearchTransactionOptions {
+    #[serde(default)]
+    pub filter: ReadTransactionOptions,
+    /// Accounts to search, all of them if empty.
+    #[ts(type = "{ tb: string, id: { String: string }}[]")]
+    #[serde(default)]
+    pub accounts: Vec<RecordId>,
+    #[serde(default)]
+    pub sort: TransactionSort,
+    /// Number of transactions to skip.
+    #[serde(default)]
+    pub offset: usize,
+    /// Maximum number of transactions of the page, all of them if not set.
+    #[ts(optional)]
+    pub limit: Option<usize>,
+}
+
+/// A transaction found by a search, with its account.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SearchHit {
+    pub transaction: TransactionWithId,
+    /// Name of the account, empty if the account does not exist anymore.
+    pub account_name: String,
+    /// Currency of the account, empty if the account does not exist anymore.
+    pub currency: String,
+}
+
+/// A page of the transactions found by a search.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SearchResult {
+    pub hits: Vec<SearchHit>,
+    /// Number of transactions found, on all pages.
+    pub total: usize,
+}
+
+/// Search the transactions of several accounts, or of all accounts. Amounts are not
+/// summed, since accounts can have different currencies.
+pub async fn search(
+    db: &Surreal<Db>,
+    options: SearchTransactionOptions,
+) -> Result<SearchResult, Error> {
+    let accounts = (!options.accounts.is_empty()).then_some(options.accounts);
+    let page = page(
+        db,
+        accounts,
+        options.filter,
+        options.sort,
+        options.offset,
+        options.limit,
+    )
+    .await?;
+    let accounts: Vec<Account> = db.select("account").await?;
+    // Hits are kept even without their account, so that they match the total.
+    let hits = page
+        .transactions
+        .into_iter()
+        .map(|transaction| {
+            let account = accounts
+                .iter()
+                .find(|account| account.id == transaction.account);
+
+            SearchHit {
+                account_name: account
+                    .map(|account| account.data.name.clone())
+                    .unwrap_or_default(),
+                currency: account
+                    .map(|account| account.data.currency.clone())
+                    .unwrap_or_default(),
+                transaction,
+            }
+        })
+        .collect();
+
+    Ok(SearchResult {
+        hits,
+        total: page.total,
+    })
+}
+
+/// Fail if the transaction or the other side of its transfer is reconciled.
+async fn check_not_reconciled(db: &Surreal<Db>, transaction: RecordId) -> Result<(), Error> {
+    let query = r#"
+    LET $transfer = $transaction.transfer;
+    RETURN (SELECT VALUE id FROM transaction
+        WHERE (id = $transaction OR (transfer != NONE AND transfer = $transfer))
+        AND status = "Reconciled")[0];"#;
+
+    let reconciled: Option<RecordId> = db
+        .query(query)
+        .bind(("transaction", transaction))
+        .await?
+        .take(1)?;
+
+    match reconciled {
+        Some(transaction) => Err(Error::Reconciliation(ReconciliationError::Reconciled(
+            transaction,
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Fail if a reconciled transaction matches `condition`, before changing transactions
+/// in bulk. `binding` is the parameter used by the condition.
+pub(crate) async fn check_none_reconciled(
+    db: &Surreal<Db>,
+    condition: &str,
+    binding: impl serde::Serialize + 'static,
+) -> Result<(), Error> {
+    let reconciled: Option<RecordId> = db
+        .query(format!(
+            r#"RETURN (SELECT VALUE id FROM transaction WHERE status = "Reconciled" AND ({condition}))[0]"#
+        ))
+        .bind(binding)
         .await?
         .take(0)?;
 
-    Ok(transactions)
+    match reconciled {
+        Some(transaction) => Err(Error::Reconciliation(ReconciliationError::Reconciled(
+            transaction,
+        ))),
+        None => Ok(()),
+    }
 }
 
+/// Update a transaction. If the transaction is part of a transfer, the amounts of the
+/// transfer and the date and amount of its other side are updated in the same
+/// database transaction.
+///
+/// Reconciled transactions are only updated if `force` is set.
 pub async fn update(
     db: &Surreal<Db>,
     transaction: TransactionWithId,
-) -> Result<(), surrealdb::Error> {
-    let query = r#"
+    force: bool,
+) -> Result<(), Error> {
+    if !force {
+        check_not_reconciled(db, transaction.id.clone()).await?;
+    }
+
+    if let Some(splits) = invalid_splits_total(transaction.inner.amount, &transaction.splits) {
+        return Err(Error::InvalidSplits {
+            amount: transaction.inner.amount,
+            splits,
+        });
+    }
+
+    let transfer: Option<transfer::TransferRecord> = match &transaction.transfer {
+        Some(transfer) => db.select(transfer.clone()).await?,
+        None => None,
+    };
+
+    let mut query = r#"
+    BEGIN TRANSACTION;
     UPDATE $transaction SET
         date = <datetime>$date,
         category = $category,
         amount = $amount,
         description = $description,
-        tags = $tags"#;
+        tags = $tags,
+        payee = $payee,
+        splits = $splits;"#
+        .to_string();
+
+    if transfer.is_some() {
+        query.push_str(
+            r#"
+    UPDATE $transfer SET
+        sent = $sent,
+        received = $received,
+        rate = $rate;
+    UPDATE transaction SET
+        date = <datetime>$date,
+        amount = $other_amount
+    WHERE transfer = $transfer AND id != $transaction;"#,
+        );
+    }
+
+    query.push_str("\n    COMMIT TRANSACTION;");
 
-    db.query(query)
-        .bind(("transaction", transaction.id))
+    let mut request = db
+        .query(query)
+        .bind(("transaction", transaction.id.clone()))
         .bind(("date", transaction.inner.date))
-        .bind(("category", transaction.category))
+        .bind(("category", transaction.category.clone()))
         .bind(("amount", transaction.inner.amount))
-        .bind(("description", transaction.inner.description))
+        .bind(("description", transaction.inner.description.clone()))
         .bind(("tags", serde_json::json!(transaction.inner.tags)))
-        .await?;
+        .bind(("payee", transaction.payee.clone()))
+        .bind(("splits", transaction.splits.clone()));
+
+    if let Some(transfer) = transfer {
+        let (sent, received) = transfer.amounts_after_update(&transaction);
+        let other_amount = if transaction.account == transfer.from {
+            received
+        } else {
+            -(sent + transfer.fee)
+        };
+
+        request = request
+            .bind(("transfer", transfer.id))
+            .bind(("sent", sent))
+            .bind(("received", received))
+            .bind((
+                "rate",
+                Number::Decimal(transfer::implied_rate(sent, received)),
+            ))
+            .bind(("other_amount", other_amount));
+    }
+
+    request.await?.check()?;
 
     Ok(())
 }
 
-pub async fn delete(db: &Surreal<Db>, transaction: RecordId) -> Result<(), surrealdb::Error> {
-    let _: Option<TransactionWithId> = db.delete(transaction).await?;
+/// Delete a transaction, and the other side of the transfer if it is part of one.
+/// Reconciled transactions are only deleted if `force` is set. Their attachments are
+/// deleted too and returned, so that their files can be removed with
+/// [`attachment::remove_files`].
+pub async fn delete(
+    db: &Surreal<Db>,
+    transaction: RecordId,
+    force: bool,
+) -> Result<Vec<attachment::Attachment>, Error> {
+    if !force {
+        check_not_reconciled(db, transaction.clone()).await?;
+    }
 
-    Ok(())
+    let query = r#"
+    BEGIN TRANSACTION;
+    LET $transfer = $transaction.transfer;
+    DELETE attachment WHERE transaction = $transaction OR ($transfer != NONE AND transaction.transfer = $transfer) RETURN BEFORE;
+    DELETE transaction WHERE transfer != NONE AND transfer = $transfer;
+    DELETE transfer WHERE id = $transfer;
+    DELETE $transaction;
+    COMMIT TRANSACTION;"#;
+
+    let attachments: Vec<attachment::Attachment> = db
+        .query(query)
+        .bind(("transaction", transaction))
+        .await?
+        .take(1)?;
+
+    Ok(attachments)
 }
 
 #[derive(ts_rs::TS)]
@@ -243,7 +919,7 @@ pub async fn delete(db: &Surreal<Db>, transaction: RecordId) -> Result<(), surre
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct AccountWithBalance {
     pub account: Account,
-    pub balance: f64,
+    pub balance: Money,
 }
 
 #[derive(ts_rs::TS)]
@@ -251,30 +927,39 @@ pub struct AccountWithBalance {
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct CurrencyBalance {
     pub currency: String,
-    pub total_balance: f64,
+    pub total_balance: Money,
     pub accounts: Vec<AccountWithBalance>,
 }
 
 // TODO: remove the following
 pub async fn balances_by_currency(
     db: &Surreal<Db>,
+    options: crate::account::ReadAccountsOptions,
 ) -> Result<Vec<CurrencyBalance>, surrealdb::Error> {
-    let query = r#"
+    let query = format!(
+        r#"
         SELECT 
             math::sum(balance) as total_balance,
-            array::group([{account: account, balance: balance}]) as accounts,
+            array::group([{{account: account, balance: balance}}]) as accounts,
             account.currency as currency
         FROM (
             SELECT
-                math::sum(amount) as balance,
-                account
-            FROM transaction
-            GROUP BY account
+                opening_balance + math::sum((SELECT VALUE amount FROM transaction WHERE account = $parent.id)) as balance,
+                id as account
+            FROM account
+            WHERE {}
             FETCH account
         ) 
-        GROUP BY currency"#;
+        GROUP BY currency"#,
+        crate::account::ACCOUNTS_CONDITION
+    );
 
-    let currencies: Vec<CurrencyBalance> = db.query(query).await?.take(0)?;
+    let currencies: Vec<CurrencyBalance> = db
+        .query(query)
+        .bind(("include_archived", options.include_archived))
+        .bind(("kinds", options.kinds))
+        .await?
+        .take(0)?;
 
     Ok(currencies)
 }
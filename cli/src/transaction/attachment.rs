@@ -0,0 +1,288 @@
+//! Files attached to transactions, like receipts and invoices.
+//!
+//! Attached files are copied into a directory managed by the application, named after
+//! the SHA-256 hash of their content, so that a file attached twice is only stored once.
+//! The `attachment` table records the original name, hash, MIME type and size of each file.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{transaction::TransactionWithId, Error};
+
+#[derive(Debug)]
+pub enum AttachmentError {
+    /// The file could not be read, copied or removed.
+    Io(std::io::Error),
+    /// The path to attach does not point to a file.
+    NotAFile(PathBuf),
+}
+
+impl std::fmt::Display for AttachmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttachmentError::Io(error) => write!(f, "failed to access attachment: {error}"),
+            AttachmentError::NotAFile(path) => write!(f, "{} is not a file", path.display()),
+        }
+    }
+}
+
+impl From<std::io::Error> for AttachmentError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Attachment {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub id: RecordId,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub transaction: RecordId,
+    /// Name of the file when it was attached.
+    pub name: String,
+    /// SHA-256 hash of the content, hex encoded.
+    pub hash: String,
+    pub mime: String,
+    /// Size of the file, in bytes.
+    #[ts(type = "number")]
+    pub size: u64,
+    #[ts(as = "String")]
+    pub added: chrono::DateTime<chrono::Utc>,
+}
+
+impl Attachment {
+    /// Name of the file in the attachments directory: the hash of its content, with the
+    /// extension of the original file so that it opens with the right application.
+    pub fn file_name(&self) -> String {
+        file_name(&self.name, &self.hash)
+    }
+}
+
+fn file_name(name: &str, hash: &str) -> String {
+    match Path::new(name).extension() {
+        Some(extension) => format!("{hash}.{}", extension.to_string_lossy()),
+        None => hash.to_string(),
+    }
+}
+
+/// Attach a file to a transaction, copying it into the attachments directory. The file
+/// is copied before the attachment is recorded, and removed again if recording fails,
+/// so that a recorded attachment always has its file.
+pub async fn add(
+    db: &Surreal<Db>,
+    directory: &Path,
+    transaction: RecordId,
+    path: &Path,
+) -> Result<Attachment, Error> {
+    let _: TransactionWithId = db
+        .select(transaction.clone())
+        .await?
+        .ok_or(Error::RecordNotFound)?;
+
+    if !path.is_file() {
+        return Err(Error::Attachment(AttachmentError::NotAFile(
+            path.to_path_buf(),
+        )));
+    }
+
+    let content = std::fs::read(path).map_err(|error| Error::Attachment(error.into()))?;
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let hash = format!("{:x}", Sha256::digest(&content));
+    let stored = directory.join(file_name(&name, &hash));
+    // Files already stored are shared with other attachments and must be kept.
+    let copied = !stored.exists();
+
+    if copied {
+        std::fs::create_dir_all(directory)
+            .and_then(|()| std::fs::write(&stored, &content))
+            .map_err(|error| Error::Attachment(error.into()))?;
+    }
+
+    let attachment = create(
+        db,
+        transaction,
+        name,
+        hash,
+        mime_guess::from_path(path)
+            .first_or_octet_stream()
+            .to_string(),
+        content.len() as u64,
+    )
+    .await;
+
+    if attachment.is_err() && copied {
+        let _ = std::fs::remove_file(&stored);
+    }
+
+    attachment
+}
+
+async fn create(
+    db: &Surreal<Db>,
+    transaction: RecordId,
+    name: String,
+    hash: String,
+    mime: String,
+    size: u64,
+) -> Result<Attachment, Error> {
+    let attachment: Option<Attachment> = db
+        .query(
+            r#"CREATE attachment SET
+        transaction = $transaction,
+        name = $name,
+        hash = $hash,
+        mime = $mime,
+        size = $size,
+        added = time::now()"#,
+        )
+        .bind(("transaction", transaction))
+        .bind(("name", name))
+        .bind(("hash", hash))
+        .bind(("mime", mime))
+        .bind(("size", size))
+        .await?
+        .take(0)?;
+
+    attachment.ok_or(Error::RecordNotFound)
+}
+
+/// List the attachments of a transaction, oldest first.
+pub async fn read(
+    db: &Surreal<Db>,
+    transaction: RecordId,
+) -> Result<Vec<Attachment>, surrealdb::Error> {
+    db.query("SELECT * FROM attachment WHERE transaction = $transaction ORDER BY added ASC")
+        .bind(("transaction", transaction))
+        .await?
+        .take(0)
+}
+
+/// Get the path of the stored file of an attachment.
+pub async fn path(
+    db: &Surreal<Db>,
+    directory: &Path,
+    attachment: RecordId,
+) -> Result<PathBuf, Error> {
+    let attachment: Attachment = db.select(attachment).await?.ok_or(Error::RecordNotFound)?;
+
+    Ok(directory.join(attachment.file_name()))
+}
+
+/// Remove an attachment from its transaction. Its file is removed from the attachments
+/// directory unless it is attached to another transaction.
+pub async fn remove(db: &Surreal<Db>, directory: &Path, attachment: RecordId) -> Result<(), Error> {
+    let attachment: Attachment = db.delete(attachment).await?.ok_or(Error::RecordNotFound)?;
+
+    remove_files(db, directory, &[attachment]).await
+}
+
+/// Remove the files of deleted attachments from the attachments directory, except the
+/// ones still attached with the same content and extension. Other files of the
+/// directory are left as they are.
+pub async fn remove_files(
+    db: &Surreal<Db>,
+    directory: &Path,
+    attachments: &[Attachment],
+) -> Result<(), Error> {
+    for attachment in attachments {
+        let file_name = attachment.file_name();
+        let same_hash: Vec<Attachment> = db
+            .query("SELECT * FROM attachment WHERE hash = $hash")
+            .bind(("hash", attachment.hash.clone()))
+            .await?
+            .take(0)?;
+
+        if same_hash.iter().any(|other| other.file_name() == file_name) {
+            continue;
+        }
+
+        match std::fs::remove_file(directory.join(&file_name)) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                return Err(Error::Attachment(error.into()))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+async fn file_names(db: &Surreal<Db>) -> Result<HashSet<String>, Error> {
+    let attachments: Vec<Attachment> = db.query("SELECT * FROM attachment").await?.take(0)?;
+
+    Ok(attachments.iter().map(Attachment::file_name).collect())
+}
+
+/// Remove the files of the attachments directory that are not attached anymore, like
+/// the ones of a restored backup that were not attached in the backup.
+pub async fn prune(db: &Surreal<Db>, directory: &Path) -> Result<(), Error> {
+    if !directory.exists() {
+        return Ok(());
+    }
+
+    let attached = file_names(db).await?;
+    let entries = std::fs::read_dir(directory).map_err(|error| Error::Attachment(error.into()))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|error| Error::Attachment(error.into()))?;
+
+        if !attached.contains(entry.file_name().to_string_lossy().as_ref()) {
+            std::fs::remove_file(entry.path()).map_err(|error| Error::Attachment(error.into()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Copy the attached files into `destination`.
+pub(crate) async fn backup(
+    db: &Surreal<Db>,
+    directory: &Path,
+    destination: &Path,
+) -> Result<(), Error> {
+    let attached = file_names(db).await?;
+
+    if attached.is_empty() {
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(destination).map_err(|error| Error::Attachment(error.into()))?;
+
+    for file_name in attached {
+        std::fs::copy(directory.join(&file_name), destination.join(&file_name))
+            .map_err(|error| Error::Attachment(error.into()))?;
+    }
+
+    Ok(())
+}
+
+/// Copy the files of a backup into the attachments directory.
+#[allow(clippy::result_large_err)]
+pub(crate) fn restore(source: &Path, directory: &Path) -> Result<(), Error> {
+    if !source.is_dir() {
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(directory).map_err(|error| Error::Attachment(error.into()))?;
+
+    for entry in std::fs::read_dir(source).map_err(|error| Error::Attachment(error.into()))? {
+        let entry = entry.map_err(|error| Error::Attachment(error.into()))?;
+        let stored = directory.join(entry.file_name());
+
+        if !stored.exists() {
+            std::fs::copy(entry.path(), stored).map_err(|error| Error::Attachment(error.into()))?;
+        }
+    }
+
+    Ok(())
+}
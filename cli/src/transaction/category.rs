@@ -1,4 +1,36 @@
-use surrealdb::{engine::local::Db, Surreal};
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::Error;
+
+/// Categories used by the application itself, which cannot be deleted.
+pub const PROTECTED_CATEGORIES: [&str; 2] = ["other", "internal-movements"];
+
+#[derive(Debug)]
+pub enum CategoryError {
+    /// A category with this id already exists.
+    AlreadyExists(RecordId),
+    /// The parent of this category would be one of its descendants.
+    Cycle(RecordId),
+    /// Transactions of a deleted category cannot be reassigned to the category itself.
+    InvalidReplacement(RecordId),
+    /// The category is used by the application and cannot be deleted.
+    Protected(RecordId),
+}
+
+impl std::fmt::Display for CategoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CategoryError::AlreadyExists(id) => write!(f, "category {id} already exists"),
+            CategoryError::Cycle(id) => {
+                write!(f, "category {id} cannot be a descendant of itself")
+            }
+            CategoryError::InvalidReplacement(id) => {
+                write!(f, "category {id} cannot replace itself")
+            }
+            CategoryError::Protected(id) => write!(f, "category {id} cannot be deleted"),
+        }
+    }
+}
 
 #[derive(ts_rs::TS)]
 #[ts(export)]
@@ -37,6 +69,197 @@ pub struct CategoryWithId {
     pub parent: Option<surrealdb::RecordId>,
 }
 
+#[derive(Debug, serde::Serialize)]
+struct Category {
+    name: String,
+    icon: Icon,
+    color: String,
+    parent: Option<RecordId>,
+}
+
 pub async fn read(db: &Surreal<Db>) -> Result<Vec<CategoryWithId>, surrealdb::Error> {
     db.select("category").await
 }
+
+/// Get a category and all of its descendants.
+pub async fn with_descendants(
+    db: &Surreal<Db>,
+    category: RecordId,
+) -> Result<Vec<RecordId>, surrealdb::Error> {
+    let categories = read(db).await?;
+    let mut ids = vec![category];
+    let mut index = 0;
+
+    while let Some(id) = ids.get(index).cloned() {
+        ids.extend(
+            categories
+                .iter()
+                .filter(|category| category.parent.as_ref() == Some(&id))
+                .filter(|category| !ids.contains(&category.id))
+                .map(|category| category.id.clone())
+                .collect::<Vec<_>>(),
+        );
+        index += 1;
+    }
+
+    Ok(ids)
+}
+
+/// Check that `parent` exists and is not `category` or one of its descendants. A cycle
+/// already among the ancestors of `parent`, like one written by hand, is rejected too.
+async fn check_parent(
+    db: &Surreal<Db>,
+    category: Option<&RecordId>,
+    parent: &RecordId,
+) -> Result<(), Error> {
+    let categories = read(db).await?;
+    let mut visited = vec![];
+    let mut ancestor = Some(parent);
+
+    while let Some(id) = ancestor {
+        if Some(id) == category || visited.contains(&id) {
+            return Err(Error::Category(CategoryError::Cycle(id.clone())));
+        }
+
+        visited.push(id);
+
+        ancestor = categories
+            .iter()
+            .find(|category| &category.id == id)
+            .ok_or(Error::RecordNotFound)?
+            .parent
+            .as_ref();
+    }
+
+    Ok(())
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, serde::Deserialize)]
+pub struct CreateCategoryOptions {
+    /// Identifier of the category, generated if not given.
+    #[ts(optional)]
+    pub id: Option<String>,
+    pub name: String,
+    pub icon: Icon,
+    pub color: String,
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub parent: Option<RecordId>,
+}
+
+pub async fn create(
+    db: &Surreal<Db>,
+    options: CreateCategoryOptions,
+) -> Result<CategoryWithId, Error> {
+    if let Some(parent) = &options.parent {
+        check_parent(db, None, parent).await?;
+    }
+
+    let content = Category {
+        name: options.name,
+        icon: options.icon,
+        color: options.color,
+        parent: options.parent,
+    };
+
+    let category: Option<CategoryWithId> = match options.id {
+        Some(id) => {
+            let id = RecordId::from(("category", id));
+            let existing: Option<CategoryWithId> = db.select(id.clone()).await?;
+
+            if existing.is_some() {
+                return Err(Error::Category(CategoryError::AlreadyExists(id)));
+            }
+
+            db.create(id).content(content).await?
+        }
+        None => db.create("category").content(content).await?,
+    };
+
+    category.ok_or(Error::RecordNotFound)
+}
+
+/// Rename, recolor or move a category.
+pub async fn update(db: &Surreal<Db>, category: CategoryWithId) -> Result<(), Error> {
+    if let Some(parent) = &category.parent {
+        check_parent(db, Some(&category.id), parent).await?;
+    }
+
+    let updated: Option<CategoryWithId> = db
+        .update(category.id)
+        .content(Category {
+            name: category.name,
+            icon: category.icon,
+            color: category.color,
+            parent: category.parent,
+        })
+        .await?;
+
+    updated.map(|_| ()).ok_or(Error::RecordNotFound)
+}
+
+/// Delete a category. Its transactions, splits, recurring transactions, allocations,
+/// rules, payees, account filters and QIF category mappings are moved to
+/// `replacement`, and its children are moved to its parent. Reconciled transactions
+/// are only moved if `force` is set.
+pub async fn delete(
+    db: &Surreal<Db>,
+    category: RecordId,
+    replacement: RecordId,
+    force: bool,
+) -> Result<(), Error> {
+    if PROTECTED_CATEGORIES
+        .iter()
+        .any(|id| RecordId::from(("category", *id)) == category)
+    {
+        return Err(Error::Category(CategoryError::Protected(category)));
+    }
+
+    if category == replacement {
+        return Err(Error::Category(CategoryError::InvalidReplacement(category)));
+    }
+
+    for id in [&category, &replacement] {
+        let existing: Option<CategoryWithId> = db.select(id.clone()).await?;
+        existing.ok_or(Error::RecordNotFound)?;
+    }
+
+    if !force {
+        super::check_none_reconciled(
+            db,
+            "category = $category OR $category IN splits.category",
+            ("category", category.clone()),
+        )
+        .await?;
+    }
+
+    let query = r#"
+    BEGIN TRANSACTION;
+    UPDATE transaction SET category = $replacement WHERE category = $category;
+    UPDATE transaction SET splits = (
+        SELECT VALUE IF category = $category THEN {
+            category: $replacement,
+            amount: amount,
+            description: description,
+            tags: tags,
+        } ELSE $this END FROM $parent.splits
+    ) WHERE $category IN splits.category;
+    UPDATE recurring SET category = $replacement WHERE category = $category;
+    UPDATE allocation SET category = $replacement WHERE category = $category;
+    UPDATE rule SET category = $replacement WHERE category = $category;
+    UPDATE payee SET category = $replacement WHERE category = $category;
+    UPDATE qif_category SET category = $replacement WHERE category = $category;
+    UPDATE account SET filter.categories = array::union(array::complement(filter.categories, [$category]), [$replacement]) WHERE $category IN filter.categories;
+    UPDATE category SET parent = $category.parent WHERE parent = $category;
+    DELETE $category;
+    COMMIT TRANSACTION;"#;
+
+    db.query(query)
+        .bind(("category", category))
+        .bind(("replacement", replacement))
+        .await?
+        .check()?;
+
+    Ok(())
+}
@@ -0,0 +1,365 @@
+//! Detection of transactions recorded twice on an account, like the ones of overlapping
+//! statements imported one after the other.
+//!
+//! Two transactions can only be duplicates if they are on the same account, with the same
+//! amount, and dated at most [`DuplicateOptions::max_days`] apart. Their score is then
+//! raised by the proximity of their dates and the similarity of their descriptions.
+//! Transactions with an external id are only duplicates of transactions with the same
+//! external id, since banks give a different id to each of their entries.
+
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    money::Money,
+    transaction::{AddTransactionOptions, TransactionWithId},
+    Error,
+};
+
+/// Score of two transactions with the same amount, before their dates and descriptions
+/// are compared.
+const AMOUNT_SCORE: f64 = 0.4;
+const DATE_WEIGHT: f64 = 0.3;
+const DESCRIPTION_WEIGHT: f64 = 0.3;
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct DuplicateOptions {
+    /// Maximum number of days between the dates of two duplicates.
+    pub max_days: u32,
+    /// Score, between 0 and 1, from which transactions are duplicates. Imports skip them.
+    pub skip_score: f64,
+    /// Score, between 0 and 1, from which transactions are possible duplicates to review.
+    pub flag_score: f64,
+}
+
+impl Default for DuplicateOptions {
+    fn default() -> Self {
+        Self {
+            max_days: 3,
+            skip_score: 0.9,
+            flag_score: 0.7,
+        }
+    }
+}
+
+/// Transactions of an account that are possibly the same.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DuplicateGroup {
+    /// Transactions of the group, oldest first.
+    pub transactions: Vec<TransactionWithId>,
+    /// Highest score between two transactions of the group.
+    pub score: f64,
+}
+
+/// Imported transaction that is possibly a duplicate of a transaction of the account.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PossibleDuplicate {
+    pub transaction: TransactionWithId,
+    pub existing: TransactionWithId,
+    pub score: f64,
+}
+
+/// Fields of a transaction compared by the detection.
+struct Fields<'a> {
+    account: &'a RecordId,
+    amount: Money,
+    date: chrono::NaiveDate,
+    description: &'a str,
+    external_id: Option<&'a str>,
+}
+
+impl<'a> From<&'a TransactionWithId> for Fields<'a> {
+    fn from(transaction: &'a TransactionWithId) -> Self {
+        Self {
+            account: &transaction.account,
+            amount: transaction.inner.amount,
+            date: transaction.inner.date.date_naive(),
+            description: &transaction.inner.description,
+            external_id: transaction.external_id.as_deref(),
+        }
+    }
+}
+
+/// Lowercase alphanumeric characters of a description.
+fn normalize(description: &str) -> Vec<char> {
+    description
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Get the similarity of two descriptions between 0 and 1, as the Sørensen-Dice
+/// coefficient of their pairs of characters. Case, spaces and punctuation are ignored.
+fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize(a), normalize(b));
+    let a: Vec<&[char]> = a.windows(2).collect();
+    let mut b: Vec<&[char]> = b.windows(2).collect();
+    let total = a.len() + b.len();
+
+    if total == 0 {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+
+    let mut common = 0;
+
+    for pair in a {
+        if let Some(index) = b.iter().position(|other| *other == pair) {
+            b.swap_remove(index);
+            common += 1;
+        }
+    }
+
+    (2 * common) as f64 / total as f64
+}
+
+fn score(a: &Fields, b: &Fields, options: &DuplicateOptions) -> f64 {
+    if a.account != b.account || a.amount != b.amount {
+        return 0.0;
+    }
+
+    if let (Some(a), Some(b)) = (a.external_id, b.external_id) {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+
+    let days = (a.date - b.date).num_days().unsigned_abs();
+
+    if days > u64::from(options.max_days) {
+        return 0.0;
+    }
+
+    let date = 1.0 - days as f64 / (f64::from(options.max_days) + 1.0);
+
+    AMOUNT_SCORE
+        + DATE_WEIGHT * date
+        + DESCRIPTION_WEIGHT * similarity(a.description, b.description)
+}
+
+/// Matches transactions about to be imported against the transactions of an account.
+pub(crate) struct Detector {
+    options: DuplicateOptions,
+    account: RecordId,
+    existing: Vec<TransactionWithId>,
+    /// Whether each existing transaction was already matched, so that two identical
+    /// imported transactions are not both matched to the same one.
+    matched: Vec<bool>,
+}
+
+impl Detector {
+    /// Read the transactions of the account that could be duplicates of the given ones.
+    pub(crate) async fn load(
+        db: &Surreal<Db>,
+        account: RecordId,
+        transactions: &[AddTransactionOptions],
+        options: DuplicateOptions,
+    ) -> Result<Self, Error> {
+        let now = chrono::Utc::now();
+        let dates = transactions
+            .iter()
+            .map(|transaction| transaction.date.unwrap_or(now));
+        let margin = chrono::Days::new(u64::from(options.max_days) + 1);
+        let (Some(start), Some(end)) = (dates.clone().min(), dates.max()) else {
+            return Ok(Self {
+                options,
+                account,
+                existing: vec![],
+                matched: vec![],
+            });
+        };
+
+        let existing: Vec<TransactionWithId> = db
+            .query(
+                "SELECT * FROM transaction WHERE account = $account AND date >= <datetime>$start AND date <= <datetime>$end",
+            )
+            .bind(("account", account.clone()))
+            .bind(("start", start - margin))
+            .bind(("end", end + margin))
+            .await?
+            .take(0)?;
+
+        Ok(Self {
+            options,
+            account,
+            matched: vec![false; existing.len()],
+            existing,
+        })
+    }
+
+    /// Find the unmatched transaction of the account with the highest score for the
+    /// given transaction, if it is at least the flag score, and mark it as matched.
+    pub(crate) fn find(
+        &mut self,
+        transaction: &AddTransactionOptions,
+    ) -> Option<(TransactionWithId, f64)> {
+        let fields = Fields {
+            account: &self.account,
+            amount: transaction.amount,
+            date: transaction
+                .date
+                .unwrap_or_else(chrono::Utc::now)
+                .date_naive(),
+            description: &transaction.description,
+            external_id: transaction.external_id.as_deref(),
+        };
+
+        let (index, score) = self
+            .existing
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.matched[*index])
+            .map(|(index, existing)| (index, score(&fields, &existing.into(), &self.options)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .filter(|(_, score)| *score >= self.options.flag_score)?;
+
+        self.matched[index] = true;
+
+        Some((self.existing[index].clone(), score))
+    }
+
+    pub(crate) fn options(&self) -> &DuplicateOptions {
+        &self.options
+    }
+}
+
+/// Find the groups of possible duplicates among the transactions of an account.
+pub async fn scan(
+    db: &Surreal<Db>,
+    account: RecordId,
+    options: DuplicateOptions,
+) -> Result<Vec<DuplicateGroup>, Error> {
+    let transactions: Vec<TransactionWithId> = db
+        .query("SELECT * FROM transaction WHERE account = $account ORDER BY date ASC")
+        .bind(("account", account))
+        .await?
+        .take(0)?;
+
+    // Each transaction points to another transaction of its group, the root of a group
+    // pointing to itself.
+    let mut parents: Vec<usize> = (0..transactions.len()).collect();
+    let mut scores = vec![0.0_f64; transactions.len()];
+
+    fn root(parents: &mut [usize], mut index: usize) -> usize {
+        while parents[index] != index {
+            parents[index] = parents[parents[index]];
+            index = parents[index];
+        }
+
+        index
+    }
+
+    for (i, transaction) in transactions.iter().enumerate() {
+        let fields = Fields::from(transaction);
+
+        for (j, other) in transactions.iter().enumerate().skip(i + 1) {
+            let other = Fields::from(other);
+
+            // Transactions are sorted by date, so the next ones are too far apart.
+            if (other.date - fields.date).num_days() > i64::from(options.max_days) {
+                break;
+            }
+
+            let score = score(&fields, &other, &options);
+
+            if score >= options.flag_score {
+                let (a, b) = (root(&mut parents, i), root(&mut parents, j));
+                parents[b] = a;
+                scores[a] = scores[a].max(scores[b]).max(score);
+            }
+        }
+    }
+
+    let mut groups: Vec<DuplicateGroup> = vec![];
+    let mut group_of_root = std::collections::HashMap::new();
+
+    for (index, transaction) in transactions.into_iter().enumerate() {
+        let root = root(&mut parents, index);
+        let group = *group_of_root.entry(root).or_insert_with(|| {
+            groups.push(DuplicateGroup {
+                transactions: vec![],
+                score: scores[root],
+            });
+            groups.len() - 1
+        });
+
+        groups[group].transactions.push(transaction);
+    }
+
+    groups.retain(|group| group.transactions.len() > 1);
+
+    Ok(groups)
+}
+
+/// Merge duplicates into the transaction to keep. The tags and attachments of the
+/// duplicates are added to it, and it takes the external id of a duplicate if it has
+/// none, so that importing the duplicate again is skipped. The duplicates are then
+/// deleted, and the transfers they are part of are removed.
+///
+/// Fails if a duplicate is not on the account of the transaction to keep, or if the
+/// transaction to keep or a duplicate is reconciled, unless `force` is set.
+pub async fn merge(
+    db: &Surreal<Db>,
+    keep: RecordId,
+    duplicates: Vec<RecordId>,
+    force: bool,
+) -> Result<TransactionWithId, Error> {
+    let kept: TransactionWithId = db
+        .select(keep.clone())
+        .await?
+        .ok_or(Error::RecordNotFound)?;
+
+    if !force {
+        super::check_not_reconciled(db, kept.id.clone()).await?;
+    }
+    let mut tags = kept.inner.tags.clone();
+    let mut external_id = kept.external_id.clone();
+
+    for duplicate in &duplicates {
+        let duplicate: TransactionWithId = db
+            .select(duplicate.clone())
+            .await?
+            .filter(|duplicate: &TransactionWithId| {
+                duplicate.account == kept.account && duplicate.id != kept.id
+            })
+            .ok_or(Error::RecordNotFound)?;
+
+        if !force {
+            super::check_not_reconciled(db, duplicate.id.clone()).await?;
+        }
+
+        for tag in duplicate.inner.tags {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        external_id = external_id.or(duplicate.external_id);
+    }
+
+    // Duplicates of a transfer are unlinked from it, like when deleting an account, so
+    // the other side of the transfer stays as a regular transaction.
+    let query = r#"
+    BEGIN TRANSACTION;
+    LET $transfers = (SELECT VALUE transfer FROM $duplicates WHERE transfer != NONE);
+    UPDATE transaction SET transfer = NONE WHERE transfer IN $transfers;
+    DELETE transfer WHERE id IN $transfers;
+    UPDATE attachment SET transaction = $keep WHERE transaction IN $duplicates;
+    DELETE transaction WHERE id IN $duplicates;
+    UPDATE $keep SET tags = $tags, external_id = $external_id;
+    COMMIT TRANSACTION;"#;
+
+    let merged: Option<TransactionWithId> = db
+        .query(query)
+        .bind(("keep", keep))
+        .bind(("duplicates", duplicates))
+        .bind(("tags", serde_json::json!(tags)))
+        .bind(("external_id", external_id))
+        .await?
+        .take(5)?;
+
+    merged.ok_or(Error::RecordNotFound)
+}
@@ -0,0 +1,369 @@
+//! Payees are the merchants and people transactions are made with. Raw bank
+//! descriptions, like `CB CARREFOUR 1234 PARIS`, are matched to a payee with its
+//! aliases or patterns, so that reports can group transactions by payee.
+
+use std::collections::HashMap;
+
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    money::Money,
+    transaction::{AddTransactionOptions, TransactionWithId},
+    Error,
+};
+
+#[derive(Debug)]
+pub enum PayeeError {
+    /// A pattern of a payee is not a valid regular expression.
+    InvalidPattern(String),
+}
+
+impl std::fmt::Display for PayeeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PayeeError::InvalidPattern(error) => write!(f, "invalid payee pattern: {error}"),
+        }
+    }
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Payee {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub id: RecordId,
+    pub name: String,
+    /// Other names of the payee in bank descriptions, like `carrefour market`. The name
+    /// of the payee is an alias too. Case, numbers and punctuation are ignored.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Regular expressions matching bank descriptions of the payee. Case is ignored.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// Category of new transactions with the payee, unless one is chosen.
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub category: Option<RecordId>,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct CreatePayeeOptions {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub category: Option<RecordId>,
+}
+
+/// Normalize a bank description to compare it to aliases: lowercase words, without
+/// numbers and punctuation.
+pub fn normalize(description: &str) -> String {
+    description
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[allow(clippy::result_large_err)]
+fn compile(patterns: &[String]) -> Result<Vec<regex::Regex>, Error> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            regex::RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|error| Error::Payee(PayeeError::InvalidPattern(error.to_string())))
+        })
+        .collect()
+}
+
+const SET_PAYEE: &str = r#"
+        name = $name,
+        aliases = $aliases,
+        patterns = $patterns,
+        category = $category"#;
+
+pub async fn create(db: &Surreal<Db>, options: CreatePayeeOptions) -> Result<Payee, Error> {
+    compile(&options.patterns)?;
+
+    let payee: Option<Payee> = db
+        .query(format!("CREATE payee SET {SET_PAYEE}"))
+        .bind(("name", options.name))
+        .bind(("aliases", options.aliases))
+        .bind(("patterns", options.patterns))
+        .bind(("category", options.category))
+        .await?
+        .take(0)?;
+
+    payee.ok_or(Error::RecordNotFound)
+}
+
+/// List the payees, by name.
+pub async fn read(db: &Surreal<Db>) -> Result<Vec<Payee>, surrealdb::Error> {
+    db.query("SELECT * FROM payee ORDER BY name ASC")
+        .await?
+        .take(0)
+}
+
+pub async fn update(db: &Surreal<Db>, payee: Payee) -> Result<(), Error> {
+    compile(&payee.patterns)?;
+
+    db.query(format!("UPDATE $payee SET {SET_PAYEE}"))
+        .bind(("payee", payee.id))
+        .bind(("name", payee.name))
+        .bind(("aliases", payee.aliases))
+        .bind(("patterns", payee.patterns))
+        .bind(("category", payee.category))
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// Delete a payee. Its transactions are kept, without payee. Reconciled transactions are
+/// only changed if `force` is set.
+pub async fn delete(db: &Surreal<Db>, payee: RecordId, force: bool) -> Result<(), Error> {
+    if !force {
+        super::check_none_reconciled(db, "payee = $payee", ("payee", payee.clone())).await?;
+    }
+
+    let query = r#"
+    BEGIN TRANSACTION;
+    UPDATE transaction SET payee = NONE WHERE payee = $payee;
+    DELETE $payee;
+    COMMIT TRANSACTION;"#;
+
+    db.query(query).bind(("payee", payee)).await?.check()?;
+
+    Ok(())
+}
+
+/// Payees ready to be matched with descriptions.
+pub(crate) struct Payees(Vec<(Payee, Vec<regex::Regex>)>);
+
+impl Payees {
+    pub(crate) async fn load(db: &Surreal<Db>) -> Result<Self, Error> {
+        let mut payees = vec![];
+
+        for payee in read(db).await? {
+            // Payees are validated when saved, so an invalid one was written by hand.
+            match compile(&payee.patterns) {
+                Ok(patterns) => payees.push((payee, patterns)),
+                Err(error) => error.trace(),
+            }
+        }
+
+        Ok(Self(payees))
+    }
+
+    /// Find the payee of a description. Patterns are checked first, then the longest
+    /// matching alias wins, so `carrefour market` is preferred over `carrefour`.
+    pub(crate) fn find(&self, description: &str) -> Option<&Payee> {
+        if let Some((payee, _)) = self
+            .0
+            .iter()
+            .find(|(_, patterns)| patterns.iter().any(|pattern| pattern.is_match(description)))
+        {
+            return Some(payee);
+        }
+
+        let description = format!(" {} ", normalize(description));
+
+        self.0
+            .iter()
+            .flat_map(|(payee, _)| {
+                std::iter::once(&payee.name)
+                    .chain(&payee.aliases)
+                    .map(|alias| normalize(alias))
+                    .filter(|alias| {
+                        !alias.is_empty() && description.contains(&format!(" {alias} "))
+                    })
+                    .map(move |alias| (payee, alias.len()))
+            })
+            .max_by_key(|(_, length)| *length)
+            .map(|(payee, _)| payee)
+    }
+
+    /// Set the payee of a transaction about to be created from its description, unless
+    /// one is chosen, then set the category of the payee unless one is chosen.
+    pub(crate) fn apply(&self, options: &mut AddTransactionOptions) {
+        if options.payee.is_none() {
+            options.payee = self
+                .find(&options.description)
+                .map(|payee| payee.id.clone());
+        }
+
+        if options.category.is_none() {
+            options.category = options.payee.as_ref().and_then(|id| {
+                self.0
+                    .iter()
+                    .find(|(payee, _)| payee.id == *id)
+                    .and_then(|(payee, _)| payee.category.clone())
+            });
+        }
+    }
+}
+
+/// Set the payee of the transactions without one, from their description. Reconciled
+/// transactions are skipped. Returns the number of transactions with a new payee.
+pub async fn assign(db: &Surreal<Db>) -> Result<usize, Error> {
+    let payees = Payees::load(db).await?;
+    let transactions: Vec<TransactionWithId> = db
+        .query(r#"SELECT * FROM transaction WHERE payee = NONE AND status != "Reconciled""#)
+        .await?
+        .take(0)?;
+    let mut assigned = 0;
+
+    for transaction in transactions {
+        if let Some(payee) = payees.find(&transaction.inner.description) {
+            db.query("UPDATE $transaction SET payee = $payee")
+                .bind(("transaction", transaction.id))
+                .bind(("payee", payee.id.clone()))
+                .await?
+                .check()?;
+            assigned += 1;
+        }
+    }
+
+    Ok(assigned)
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Default, Debug, Clone, serde::Deserialize)]
+pub struct PayeeSpendingOptions {
+    /// Only get the spending of this payee.
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub payee: Option<RecordId>,
+    /// Only count the transactions of this account.
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub account: Option<RecordId>,
+    #[ts(as = "Option<String>", optional)]
+    pub start: Option<chrono::DateTime<chrono::Utc>>,
+    #[ts(as = "Option<String>", optional)]
+    pub end: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Transactions made with a payee in one currency.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PayeeSpending {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub payee: RecordId,
+    pub name: String,
+    /// Currency of the accounts of the transactions.
+    pub currency: String,
+    /// Sum of the amounts of the transactions, negative for expenses.
+    pub total: Money,
+    pub count: usize,
+    pub average: Money,
+    /// Date of the first transaction.
+    #[ts(as = "String")]
+    pub first: chrono::DateTime<chrono::Utc>,
+    /// Date of the last transaction.
+    #[ts(as = "String")]
+    pub last: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(serde::Deserialize)]
+struct PayeeTransaction {
+    payee: RecordId,
+    currency: String,
+    amount: Money,
+    date: chrono::DateTime<chrono::Utc>,
+}
+
+/// Get the spending of each payee with transactions, sorted by name. Amounts in
+/// different currencies are not added up: a payee gets one spending per currency.
+pub async fn spending(
+    db: &Surreal<Db>,
+    options: PayeeSpendingOptions,
+) -> Result<Vec<PayeeSpending>, Error> {
+    let mut query =
+        "SELECT payee, account.currency AS currency, amount, date FROM transaction WHERE payee != NONE"
+            .to_string();
+
+    if options.payee.is_some() {
+        query.push_str(" AND payee = $payee");
+    }
+
+    if options.account.is_some() {
+        query.push_str(" AND account = $account");
+    }
+
+    if options.start.is_some() {
+        query.push_str(" AND date >= <datetime>$start");
+    }
+
+    if options.end.is_some() {
+        query.push_str(" AND date <= <datetime>$end");
+    }
+
+    let transactions: Vec<PayeeTransaction> = db
+        .query(query)
+        .bind(("payee", options.payee))
+        .bind(("account", options.account))
+        .bind(("start", options.start))
+        .bind(("end", options.end))
+        .await?
+        .take(0)?;
+    let mut spending: Vec<PayeeSpending> = vec![];
+    let mut indexes: HashMap<(String, String), usize> = HashMap::new();
+
+    for transaction in transactions {
+        let key = (transaction.payee.to_string(), transaction.currency.clone());
+
+        match indexes.get(&key) {
+            Some(index) => {
+                let payee = &mut spending[*index];
+                payee.total += transaction.amount;
+                payee.count += 1;
+                payee.first = payee.first.min(transaction.date);
+                payee.last = payee.last.max(transaction.date);
+            }
+            None => {
+                indexes.insert(key, spending.len());
+                spending.push(PayeeSpending {
+                    payee: transaction.payee,
+                    name: String::new(),
+                    currency: transaction.currency,
+                    total: transaction.amount,
+                    count: 1,
+                    average: Money::ZERO,
+                    first: transaction.date,
+                    last: transaction.date,
+                });
+            }
+        }
+    }
+
+    let names: HashMap<String, String> = read(db)
+        .await?
+        .into_iter()
+        .map(|payee| (payee.id.to_string(), payee.name))
+        .collect();
+
+    for payee in &mut spending {
+        payee.name = names
+            .get(&payee.payee.to_string())
+            .cloned()
+            .unwrap_or_default();
+        payee.average =
+            Money::from(payee.total.to_decimal() / rust_decimal::Decimal::from(payee.count))
+                .round_to_currency(&payee.currency);
+    }
+
+    spending.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then_with(|| a.currency.cmp(&b.currency))
+    });
+
+    Ok(spending)
+}
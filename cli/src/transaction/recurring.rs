@@ -0,0 +1,398 @@
+use chrono::{Datelike, NaiveDate};
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    money::Money,
+    transaction::{
+        payee::Payees,
+        rule::Rules,
+        tag::{self, Tag},
+        AddTransactionOptions, TransactionWithId,
+    },
+    Error,
+};
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Unit {
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+/// How often a recurring transaction occurs. Occurrences are computed from the start
+/// date of the recurring transaction, so a monthly schedule starting on the 31st
+/// falls on the last day of shorter months without drifting.
+#[derive(ts_rs::TS)]
+#[ts(export, tag = "type", content = "data")]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum Schedule {
+    Daily,
+    Weekly,
+    /// On the given day of each month, or the last day of the month if it is shorter.
+    Monthly {
+        day: u32,
+    },
+    /// On the last day of each month that is not a saturday or a sunday.
+    LastBusinessDay,
+    Yearly,
+    /// Every `interval` days, weeks, months or years.
+    Every {
+        interval: u32,
+        unit: Unit,
+    },
+}
+
+fn add_months(date: NaiveDate, months: u32) -> Option<NaiveDate> {
+    date.checked_add_months(chrono::Months::new(months))
+}
+
+fn last_day_of_month(date: NaiveDate) -> Option<NaiveDate> {
+    add_months(date.with_day(1)?, 1)?.pred_opt()
+}
+
+impl Schedule {
+    /// Get the `n`th occurrence of the schedule, the first one being `n = 0`.
+    /// Monthly schedules can return an occurrence before `start` for `n = 0`.
+    fn nth(&self, start: NaiveDate, n: u32) -> Option<NaiveDate> {
+        match self {
+            Schedule::Daily => start.checked_add_days(chrono::Days::new(n.into())),
+            Schedule::Weekly => start.checked_add_days(chrono::Days::new(7 * u64::from(n))),
+            Schedule::Monthly { day } => {
+                let month = add_months(start.with_day(1)?, n)?;
+                let last = last_day_of_month(month)?;
+
+                month.with_day((*day).clamp(1, last.day()))
+            }
+            Schedule::LastBusinessDay => {
+                let mut date = last_day_of_month(add_months(start.with_day(1)?, n)?)?;
+
+                while matches!(date.weekday(), chrono::Weekday::Sat | chrono::Weekday::Sun) {
+                    date = date.pred_opt()?;
+                }
+
+                Some(date)
+            }
+            Schedule::Yearly => add_months(start, 12 * n),
+            Schedule::Every { interval, unit } => {
+                let steps = interval.max(&1).checked_mul(n)?;
+
+                match unit {
+                    Unit::Days => start.checked_add_days(chrono::Days::new(steps.into())),
+                    Unit::Weeks => start.checked_add_days(chrono::Days::new(7 * u64::from(steps))),
+                    Unit::Months => add_months(start, steps),
+                    Unit::Years => add_months(start, steps.checked_mul(12)?),
+                }
+            }
+        }
+    }
+}
+
+/// Template of a transaction that repeats on a schedule.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Recurring {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub id: RecordId,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub account: RecordId,
+    pub amount: Money,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub category: RecordId,
+    pub description: String,
+    pub tags: Vec<Tag>,
+    pub schedule: Schedule,
+    /// Date of the first occurrence. Occurrences are created at the same time of day.
+    #[ts(as = "String")]
+    pub start: chrono::DateTime<chrono::Utc>,
+    /// No occurrence is created after this date.
+    #[ts(as = "Option<String>", optional)]
+    pub end: Option<chrono::DateTime<chrono::Utc>>,
+    /// Date of the last occurrence created as a transaction. Only later occurrences are
+    /// created, so deleting or moving a created transaction does not bring it back.
+    #[ts(as = "Option<String>", optional)]
+    #[serde(default)]
+    pub last_materialized: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Recurring {
+    /// Get the dates of the occurrences between `from` and `to`, both included.
+    pub fn occurrences(
+        &self,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<chrono::DateTime<chrono::Utc>> {
+        let to = self.end.map_or(to, |end| end.min(to));
+        let start = self.start.date_naive();
+        let time = self.start.time();
+        let mut occurrences = vec![];
+
+        for n in 0.. {
+            let Some(date) = self.schedule.nth(start, n) else {
+                break;
+            };
+            let date = date.and_time(time).and_utc();
+
+            if date > to {
+                break;
+            }
+
+            if date >= self.start && date >= from {
+                occurrences.push(date);
+            }
+        }
+
+        occurrences
+    }
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, serde::Deserialize)]
+pub struct CreateRecurringOptions {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub account: RecordId,
+    pub amount: Money,
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub category: Option<RecordId>,
+    pub description: String,
+    pub tags: Vec<Tag>,
+    pub schedule: Schedule,
+    #[ts(as = "String")]
+    pub start: chrono::DateTime<chrono::Utc>,
+    #[ts(as = "Option<String>", optional)]
+    pub end: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+pub async fn create(db: &Surreal<Db>, options: CreateRecurringOptions) -> Result<Recurring, Error> {
+    let query = r#"
+    CREATE recurring SET
+        account = $account,
+        amount = $amount,
+        category = $category,
+        description = $description,
+        tags = $tags,
+        schedule = $schedule,
+        start = <datetime>$start,
+        end = <option<datetime>>$end"#;
+
+    let recurring: Option<Recurring> = db
+        .query(query)
+        .bind(("account", options.account))
+        .bind(("amount", options.amount))
+        .bind((
+            "category",
+            options.category.unwrap_or(("category", "other").into()),
+        ))
+        .bind(("description", options.description))
+        .bind(("tags", serde_json::json!(options.tags)))
+        .bind(("schedule", options.schedule))
+        .bind(("start", options.start))
+        .bind(("end", options.end))
+        .await?
+        .take(0)?;
+
+    recurring.ok_or(Error::RecordNotFound)
+}
+
+/// List recurring transactions, of a single account if given.
+pub async fn read(
+    db: &Surreal<Db>,
+    account: Option<RecordId>,
+) -> Result<Vec<Recurring>, surrealdb::Error> {
+    match account {
+        Some(account) => db
+            .query("SELECT * FROM recurring WHERE account = $account ORDER BY start")
+            .bind(("account", account))
+            .await?
+            .take(0),
+        None => db
+            .query("SELECT * FROM recurring ORDER BY start")
+            .await?
+            .take(0),
+    }
+}
+
+pub async fn update(db: &Surreal<Db>, recurring: Recurring) -> Result<(), surrealdb::Error> {
+    let query = r#"
+    UPDATE $recurring SET
+        account = $account,
+        amount = $amount,
+        category = $category,
+        description = $description,
+        tags = $tags,
+        schedule = $schedule,
+        start = <datetime>$start,
+        end = <option<datetime>>$end"#;
+
+    db.query(query)
+        .bind(("recurring", recurring.id))
+        .bind(("account", recurring.account))
+        .bind(("amount", recurring.amount))
+        .bind(("category", recurring.category))
+        .bind(("description", recurring.description))
+        .bind(("tags", serde_json::json!(recurring.tags)))
+        .bind(("schedule", recurring.schedule))
+        .bind(("start", recurring.start))
+        .bind(("end", recurring.end))
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// Delete a recurring transaction. Transactions already created from it are kept.
+pub async fn delete(db: &Surreal<Db>, recurring: RecordId) -> Result<(), surrealdb::Error> {
+    let _: Option<Recurring> = db.delete(recurring).await?;
+
+    Ok(())
+}
+
+/// An upcoming occurrence of a recurring transaction.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Occurrence {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub recurring: RecordId,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub account: RecordId,
+    #[ts(as = "String")]
+    pub date: chrono::DateTime<chrono::Utc>,
+    pub amount: Money,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub category: RecordId,
+    pub description: String,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, serde::Deserialize)]
+pub struct PreviewRecurringOptions {
+    /// Only preview occurrences of this account.
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub account: Option<RecordId>,
+    /// Last date to preview.
+    #[ts(as = "String")]
+    pub until: chrono::DateTime<chrono::Utc>,
+}
+
+/// List the occurrences from the start of today until the date of the options that
+/// were not created as transactions yet, sorted by date.
+pub async fn preview(
+    db: &Surreal<Db>,
+    options: PreviewRecurringOptions,
+) -> Result<Vec<Occurrence>, surrealdb::Error> {
+    let today = chrono::Utc::now()
+        .date_naive()
+        .and_time(chrono::NaiveTime::MIN)
+        .and_utc();
+    let mut occurrences: Vec<Occurrence> = read(db, options.account)
+        .await?
+        .into_iter()
+        .flat_map(|recurring| {
+            recurring
+                .occurrences(today, options.until)
+                .into_iter()
+                .filter(|date| recurring.last_materialized.is_none_or(|last| *date > last))
+                .map(|date| Occurrence {
+                    recurring: recurring.id.clone(),
+                    account: recurring.account.clone(),
+                    date,
+                    amount: recurring.amount,
+                    category: recurring.category.clone(),
+                    description: recurring.description.clone(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    occurrences.sort_by_key(|occurrence| occurrence.date);
+
+    Ok(occurrences)
+}
+
+/// Create the transactions of all occurrences due until `until` that are after the
+/// last occurrence created for each recurring transaction, so this can run as often as
+/// needed. Occurrences go through the rules and get their payee like transactions
+/// created by hand, and recurring transactions without a category take the one of the
+/// rules or of the payee. Returns the created transactions.
+pub async fn materialize(
+    db: &Surreal<Db>,
+    until: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<TransactionWithId>, Error> {
+    let rules = Rules::load(db).await?;
+    let payees = Payees::load(db).await?;
+    let other: RecordId = ("category", "other").into();
+    let mut transactions = vec![];
+
+    for recurring in read(db, None).await? {
+        let last_materialized = match recurring.last_materialized {
+            Some(last) => Some(last),
+            // Note: recurring transactions materialized before the watermark was stored
+            //       start after their latest transaction.
+            None => db
+                .query("SELECT VALUE date FROM transaction WHERE recurring = $recurring ORDER BY date DESC LIMIT 1")
+                .bind(("recurring", recurring.id.clone()))
+                .await?
+                .take(0)?,
+        };
+
+        for date in recurring
+            .occurrences(recurring.start, until)
+            .into_iter()
+            .filter(|date| last_materialized.is_none_or(|last| *date > last))
+        {
+            let mut options = AddTransactionOptions {
+                amount: recurring.amount,
+                category: Some(recurring.category.clone()).filter(|category| *category != other),
+                description: recurring.description.clone(),
+                tags: recurring.tags.clone(),
+                date: Some(date),
+                ..Default::default()
+            };
+
+            rules.apply(&recurring.account, &mut options);
+            payees.apply(&mut options);
+            tag::create_missing(db, &options.tags).await?;
+
+            let query = r#"
+            BEGIN TRANSACTION;
+            CREATE transaction SET
+                date = <datetime>$date,
+                category = $category,
+                amount = $amount,
+                description = $description,
+                tags = $tags,
+                account = $account,
+                payee = $payee,
+                recurring = $recurring;
+            UPDATE $recurring SET last_materialized = <datetime>$date;
+            COMMIT TRANSACTION;"#;
+
+            let transaction: Option<TransactionWithId> = db
+                .query(query)
+                .bind(("date", date))
+                .bind((
+                    "category",
+                    options.category.unwrap_or_else(|| other.clone()),
+                ))
+                .bind(("amount", options.amount))
+                .bind(("description", options.description))
+                .bind(("tags", serde_json::json!(options.tags)))
+                .bind(("account", recurring.account.clone()))
+                .bind(("payee", options.payee))
+                .bind(("recurring", recurring.id.clone()))
+                .await?
+                .take(0)?;
+
+            transactions.push(transaction.ok_or(Error::RecordNotFound)?);
+        }
+    }
+
+    Ok(transactions)
+}
@@ -0,0 +1,396 @@
+//! Rules set the category, add tags or rewrite the description of transactions when
+//! they are created or imported.
+//!
+//! Rules are applied by decreasing priority. The category and the description are set
+//! by the first matching rule that changes them, and the tags of all matching rules
+//! are added.
+
+use chrono::Datelike;
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    money::Money,
+    transaction::{tag::Tag, AddTransactionOptions, Status, TransactionWithId},
+    Error,
+};
+
+#[derive(Debug)]
+pub enum RuleError {
+    /// The regular expression of a rule is not valid.
+    InvalidRegex(String),
+}
+
+impl std::fmt::Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleError::InvalidRegex(error) => write!(f, "invalid regular expression: {error}"),
+        }
+    }
+}
+
+/// How the description of a transaction is matched. Case is ignored.
+#[derive(ts_rs::TS)]
+#[ts(export, tag = "type", content = "data")]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum DescriptionMatch {
+    Contains(String),
+    Regex(String),
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Rule {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub id: RecordId,
+    pub name: String,
+    /// Rules with a higher priority are applied first.
+    pub priority: i64,
+    #[ts(optional)]
+    pub description: Option<DescriptionMatch>,
+    /// Lowest amount of the transactions to match, included.
+    #[ts(optional)]
+    pub min_amount: Option<Money>,
+    /// Highest amount of the transactions to match, included.
+    #[ts(optional)]
+    pub max_amount: Option<Money>,
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub account: Option<RecordId>,
+    /// Days of the month of the transactions to match, any day if empty.
+    #[serde(default)]
+    pub days_of_month: Vec<u32>,
+    /// Category set on matching transactions.
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub category: Option<RecordId>,
+    /// Tags added to matching transactions.
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    /// Description set on matching transactions. With a regular expression, `$1` or
+    /// `${name}` are replaced by the groups it captured.
+    #[ts(optional)]
+    pub rename: Option<String>,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct CreateRuleOptions {
+    pub name: String,
+    pub priority: i64,
+    #[ts(optional)]
+    pub description: Option<DescriptionMatch>,
+    #[ts(optional)]
+    pub min_amount: Option<Money>,
+    #[ts(optional)]
+    pub max_amount: Option<Money>,
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub account: Option<RecordId>,
+    #[serde(default)]
+    pub days_of_month: Vec<u32>,
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub category: Option<RecordId>,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    #[ts(optional)]
+    pub rename: Option<String>,
+}
+
+/// Compile the description matcher of a rule.
+#[allow(clippy::result_large_err)]
+fn compile(description: Option<&DescriptionMatch>) -> Result<Option<regex::Regex>, Error> {
+    let pattern = match description {
+        Some(DescriptionMatch::Contains(text)) => regex::escape(text),
+        Some(DescriptionMatch::Regex(pattern)) => pattern.clone(),
+        None => return Ok(None),
+    };
+
+    regex::RegexBuilder::new(&pattern)
+        .case_insensitive(true)
+        .build()
+        .map(Some)
+        .map_err(|error| Error::Rule(RuleError::InvalidRegex(error.to_string())))
+}
+
+const SET_RULE: &str = r#"
+        name = $name,
+        priority = $priority,
+        description = $description,
+        min_amount = $min_amount,
+        max_amount = $max_amount,
+        account = $account,
+        days_of_month = $days_of_month,
+        category = $category,
+        tags = $tags,
+        rename = $rename"#;
+
+pub async fn create(db: &Surreal<Db>, options: CreateRuleOptions) -> Result<Rule, Error> {
+    compile(options.description.as_ref())?;
+
+    let rule: Option<Rule> = db
+        .query(format!("CREATE rule SET {SET_RULE}"))
+        .bind(("name", options.name))
+        .bind(("priority", options.priority))
+        .bind(("description", options.description))
+        .bind(("min_amount", options.min_amount))
+        .bind(("max_amount", options.max_amount))
+        .bind(("account", options.account))
+        .bind(("days_of_month", options.days_of_month))
+        .bind(("category", options.category))
+        .bind(("tags", serde_json::json!(options.tags)))
+        .bind(("rename", options.rename))
+        .await?
+        .take(0)?;
+
+    rule.ok_or(Error::RecordNotFound)
+}
+
+/// List the rules, by decreasing priority.
+pub async fn read(db: &Surreal<Db>) -> Result<Vec<Rule>, surrealdb::Error> {
+    db.query("SELECT * FROM rule ORDER BY priority DESC, name ASC")
+        .await?
+        .take(0)
+}
+
+pub async fn update(db: &Surreal<Db>, rule: Rule) -> Result<(), Error> {
+    compile(rule.description.as_ref())?;
+
+    db.query(format!("UPDATE $rule SET {SET_RULE}"))
+        .bind(("rule", rule.id))
+        .bind(("name", rule.name))
+        .bind(("priority", rule.priority))
+        .bind(("description", rule.description))
+        .bind(("min_amount", rule.min_amount))
+        .bind(("max_amount", rule.max_amount))
+        .bind(("account", rule.account))
+        .bind(("days_of_month", rule.days_of_month))
+        .bind(("category", rule.category))
+        .bind(("tags", serde_json::json!(rule.tags)))
+        .bind(("rename", rule.rename))
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// Delete a rule. Transactions it already changed are kept as they are.
+pub async fn delete(db: &Surreal<Db>, rule: RecordId) -> Result<(), surrealdb::Error> {
+    let _: Option<Rule> = db.delete(rule).await?;
+
+    Ok(())
+}
+
+/// Changes of the rules matching a transaction.
+#[derive(Default)]
+pub(crate) struct Changes {
+    pub(crate) category: Option<RecordId>,
+    pub(crate) description: Option<String>,
+    pub(crate) tags: Vec<Tag>,
+}
+
+impl Changes {
+    /// Add the tags of the rules that are not already in `tags`.
+    fn add_tags(&self, tags: &mut Vec<Tag>) {
+        for tag in &self.tags {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+    }
+}
+
+/// Rules ready to be applied to transactions.
+pub(crate) struct Rules(Vec<(Rule, Option<regex::Regex>)>);
+
+impl Rules {
+    pub(crate) async fn load(db: &Surreal<Db>) -> Result<Self, Error> {
+        let mut rules = vec![];
+
+        for rule in read(db).await? {
+            // Rules are validated when saved, so an invalid one was written by hand.
+            match compile(rule.description.as_ref()) {
+                Ok(regex) => rules.push((rule, regex)),
+                Err(error) => error.trace(),
+            }
+        }
+
+        Ok(Self(rules))
+    }
+
+    /// Get the changes of the rules matching a transaction.
+    pub(crate) fn matching(
+        &self,
+        account: &RecordId,
+        amount: Money,
+        date: chrono::NaiveDate,
+        description: &str,
+    ) -> Changes {
+        let mut changes = Changes::default();
+
+        for (rule, regex) in &self.0 {
+            if rule.account.as_ref().is_some_and(|other| other != account)
+                || rule.min_amount.is_some_and(|min| amount < min)
+                || rule.max_amount.is_some_and(|max| amount > max)
+                || (!rule.days_of_month.is_empty() && !rule.days_of_month.contains(&date.day()))
+            {
+                continue;
+            }
+
+            let captures = match regex {
+                Some(regex) => match regex.captures(description) {
+                    Some(captures) => Some(captures),
+                    None => continue,
+                },
+                None => None,
+            };
+
+            if changes.category.is_none() {
+                changes.category = rule.category.clone();
+            }
+
+            if changes.description.is_none() {
+                changes.description = rule.rename.as_ref().map(|rename| match &captures {
+                    Some(captures)
+                        if matches!(rule.description, Some(DescriptionMatch::Regex(_))) =>
+                    {
+                        let mut description = String::new();
+                        captures.expand(rename, &mut description);
+                        description
+                    }
+                    _ => rename.clone(),
+                });
+            }
+
+            for tag in &rule.tags {
+                if !changes.tags.contains(tag) {
+                    changes.tags.push(tag.clone());
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Apply the rules to a transaction about to be created. A category chosen for the
+    /// transaction is kept, and transactions with splits keep the categories of their
+    /// splits.
+    pub(crate) fn apply(&self, account: &RecordId, options: &mut AddTransactionOptions) {
+        let date = options.date.unwrap_or_else(chrono::Utc::now).date_naive();
+        let changes = self.matching(account, options.amount, date, &options.description);
+
+        if options.category.is_none() && options.splits.is_empty() {
+            options.category = changes.category.clone();
+        }
+
+        if let Some(description) = &changes.description {
+            options.description = description.clone();
+        }
+
+        changes.add_tags(&mut options.tags);
+    }
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ApplyRulesOptions {
+    /// Only apply the rules to the transactions of this account.
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub account: Option<RecordId>,
+    #[ts(as = "String")]
+    pub start: chrono::DateTime<chrono::Utc>,
+    #[ts(as = "String")]
+    pub end: chrono::DateTime<chrono::Utc>,
+    /// Only return the changes, without updating the transactions.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Change of a transaction by the rules.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RuleChange {
+    /// The transaction before the change.
+    pub transaction: TransactionWithId,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub category: RecordId,
+    pub description: String,
+    pub tags: Vec<Tag>,
+}
+
+/// Apply the rules to the transactions dated between the start and end of the options,
+/// both included, and return the changes. The categories of the transactions are
+/// replaced by the ones of the rules, except for transactions with splits, which keep
+/// the categories of their splits. Transfers and reconciled transactions are left as
+/// they are. Tags added by the rules are added to the tag list.
+pub async fn apply(db: &Surreal<Db>, options: ApplyRulesOptions) -> Result<Vec<RuleChange>, Error> {
+    let rules = Rules::load(db).await?;
+    let mut query = "SELECT * FROM transaction WHERE date >= <datetime>$start AND date <= <datetime>$end AND transfer = NONE".to_string();
+
+    if options.account.is_some() {
+        query.push_str(" AND account = $account");
+    }
+
+    query.push_str(" ORDER BY date ASC");
+
+    let transactions: Vec<TransactionWithId> = db
+        .query(query)
+        .bind(("start", options.start))
+        .bind(("end", options.end))
+        .bind(("account", options.account))
+        .await?
+        .take(0)?;
+    let mut changes = vec![];
+
+    for transaction in transactions {
+        if transaction.status == Status::Reconciled {
+            continue;
+        }
+
+        let matching = rules.matching(
+            &transaction.account,
+            transaction.inner.amount,
+            transaction.inner.date.date_naive(),
+            &transaction.inner.description,
+        );
+        let category = matching
+            .category
+            .clone()
+            .filter(|_| transaction.splits.is_empty())
+            .unwrap_or_else(|| transaction.category.clone());
+        let description = matching
+            .description
+            .clone()
+            .unwrap_or_else(|| transaction.inner.description.clone());
+        let mut tags = transaction.inner.tags.clone();
+        matching.add_tags(&mut tags);
+
+        if category == transaction.category
+            && description == transaction.inner.description
+            && tags.len() == transaction.inner.tags.len()
+        {
+            continue;
+        }
+
+        if !options.dry_run {
+            super::tag::create_missing(db, &matching.tags).await?;
+            db.query("UPDATE $transaction SET category = $category, description = $description, tags = $tags")
+                .bind(("transaction", transaction.id.clone()))
+                .bind(("category", category.clone()))
+                .bind(("description", description.clone()))
+                .bind(("tags", serde_json::json!(tags)))
+                .await?
+                .check()?;
+        }
+
+        changes.push(RuleChange {
+            transaction,
+            category,
+            description,
+            tags,
+        });
+    }
+
+    Ok(changes)
+}
@@ -0,0 +1,194 @@
+//! Category suggestions for new transactions, learned from the categories of past
+//! transactions with similar descriptions.
+//!
+//! Suggestions come from a naive Bayes classifier over the words of the descriptions.
+//! Transfers and transactions left in the default `other` category are not learned from.
+
+use std::collections::{HashMap, HashSet};
+
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{
+    transaction::{tag::Tag, TransactionWithId},
+    Error,
+};
+
+/// A category suggested for a description.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CategorySuggestion {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub category: RecordId,
+    /// Probability of the category, between 0 and 1.
+    pub confidence: f64,
+    /// Tags of at least half of the past transactions of the category sharing a word
+    /// with the description.
+    pub tags: Vec<Tag>,
+}
+
+/// Get the words of a description. Numbers, like dates and references, are ignored.
+fn words(description: &str) -> HashSet<String> {
+    description
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| word.chars().count() > 1)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Past transactions of a category.
+struct Class {
+    category: RecordId,
+    transactions: usize,
+    /// Number of transactions of the category with each word.
+    words: HashMap<String, usize>,
+    total_words: usize,
+    examples: Vec<(HashSet<String>, Vec<Tag>)>,
+}
+
+/// Classifier trained on the transactions of the database.
+pub struct Model {
+    classes: Vec<Class>,
+    vocabulary: HashSet<String>,
+    transactions: usize,
+}
+
+impl Model {
+    /// Train the model on all categorized transactions.
+    pub async fn train(db: &Surreal<Db>) -> Result<Self, Error> {
+        let transactions: Vec<TransactionWithId> = db
+            .query("SELECT * FROM transaction WHERE transfer = NONE")
+            .await?
+            .take(0)?;
+        let other = RecordId::from(("category", "other"));
+        let mut classes: Vec<Class> = vec![];
+        let mut indexes: HashMap<String, usize> = HashMap::new();
+        let mut model = Self {
+            classes: vec![],
+            vocabulary: HashSet::new(),
+            transactions: 0,
+        };
+
+        for transaction in transactions.iter().flat_map(TransactionWithId::by_category) {
+            if transaction.category == other {
+                continue;
+            }
+
+            let index = *indexes
+                .entry(transaction.category.to_string())
+                .or_insert_with(|| {
+                    classes.push(Class {
+                        category: transaction.category.clone(),
+                        transactions: 0,
+                        words: HashMap::new(),
+                        total_words: 0,
+                        examples: vec![],
+                    });
+                    classes.len() - 1
+                });
+            let class = &mut classes[index];
+            let words = words(&transaction.inner.description);
+
+            for word in &words {
+                *class.words.entry(word.clone()).or_default() += 1;
+                model.vocabulary.insert(word.clone());
+            }
+
+            class.transactions += 1;
+            class.total_words += words.len();
+            class.examples.push((words, transaction.inner.tags));
+            model.transactions += 1;
+        }
+
+        model.classes = classes;
+
+        Ok(model)
+    }
+
+    /// Suggest categories for a description, by decreasing confidence. Descriptions
+    /// without any word seen in past transactions get no suggestions.
+    pub fn suggest(&self, description: &str, limit: usize) -> Vec<CategorySuggestion> {
+        let words: Vec<String> = words(description)
+            .into_iter()
+            .filter(|word| self.vocabulary.contains(word))
+            .collect();
+
+        if words.is_empty() {
+            return vec![];
+        }
+
+        // Log probabilities with Laplace smoothing, to avoid underflows on long descriptions.
+        let vocabulary = self.vocabulary.len() as f64;
+        let scores: Vec<f64> = self
+            .classes
+            .iter()
+            .map(|class| {
+                let prior = (class.transactions as f64 / self.transactions as f64).ln();
+
+                words.iter().fold(prior, |score, word| {
+                    let count = class.words.get(word).copied().unwrap_or_default() as f64;
+
+                    score + ((count + 1.0) / (class.total_words as f64 + vocabulary)).ln()
+                })
+            })
+            .collect();
+        let max = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let total: f64 = scores.iter().map(|score| (score - max).exp()).sum();
+
+        let mut suggestions: Vec<CategorySuggestion> = self
+            .classes
+            .iter()
+            .zip(scores)
+            .map(|(class, score)| CategorySuggestion {
+                category: class.category.clone(),
+                confidence: (score - max).exp() / total,
+                tags: class.tags(&words),
+            })
+            .collect();
+
+        suggestions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        suggestions.truncate(limit);
+
+        suggestions
+    }
+}
+
+impl Class {
+    /// Get the tags of at least half of the transactions sharing a word with `words`.
+    fn tags(&self, words: &[String]) -> Vec<Tag> {
+        let similar: Vec<&Vec<Tag>> = self
+            .examples
+            .iter()
+            .filter(|(example, _)| words.iter().any(|word| example.contains(word)))
+            .map(|(_, tags)| tags)
+            .collect();
+        let mut counts: Vec<(Tag, usize)> = vec![];
+
+        for tag in similar.iter().copied().flatten() {
+            match counts.iter_mut().find(|(other, _)| other == tag) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((tag.clone(), 1)),
+            }
+        }
+
+        counts
+            .into_iter()
+            .filter(|(_, count)| count * 2 >= similar.len())
+            .map(|(tag, _)| tag)
+            .collect()
+    }
+}
+
+/// Suggest categories for each description, trained on the transactions of the database.
+pub async fn suggest(
+    db: &Surreal<Db>,
+    descriptions: &[String],
+    limit: usize,
+) -> Result<Vec<Vec<CategorySuggestion>>, Error> {
+    let model = Model::train(db).await?;
+
+    Ok(descriptions
+        .iter()
+        .map(|description| model.suggest(description, limit))
+        .collect())
+}
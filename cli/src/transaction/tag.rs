@@ -1,6 +1,6 @@
-use surrealdb::{engine::local::Db, Surreal};
+use surrealdb::{engine::local::Db, RecordId, Surreal};
 
-use crate::Record;
+use crate::{money::Money, Error, Record};
 
 #[derive(ts_rs::TS)]
 #[ts(export)]
@@ -27,3 +27,244 @@ pub async fn create(db: &Surreal<Db>, tags: Vec<Tag>) -> Result<(), surrealdb::E
 
     Ok(())
 }
+
+/// Add the tags missing from the tag list, like the tags added by rules, keeping the
+/// color of the tags already in the list.
+pub(crate) async fn create_missing(db: &Surreal<Db>, tags: &[Tag]) -> Result<(), surrealdb::Error> {
+    if tags.is_empty() {
+        return Ok(());
+    }
+
+    let query = r#"
+    FOR $tag IN $tags {
+        UPSERT type::thing("tag", $tag.label) SET label = $tag.label, color = color ?? $tag.color;
+    };"#;
+
+    db.query(query)
+        .bind(("tags", serde_json::json!(tags)))
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// Condition on the `transaction` table matching transactions with one of the `$labels`
+/// tags, on themselves or on one of their splits.
+const WITH_LABELS: &str =
+    "tags.label ANYINSIDE $labels OR array::flatten(splits.tags.label) ANYINSIDE $labels";
+
+/// Replace the tags with the given labels by `into` in the tag list, transactions,
+/// splits, recurring transactions and rules. Reconciled transactions are only changed
+/// if `force` is set.
+async fn replace(
+    db: &Surreal<Db>,
+    mut labels: Vec<String>,
+    into: Tag,
+    force: bool,
+) -> Result<(), Error> {
+    if !force {
+        super::check_none_reconciled(db, WITH_LABELS, ("labels", labels.clone())).await?;
+    }
+
+    // Also replace `into` itself so every copy of the tag gets its color.
+    labels.push(into.label.clone());
+
+    let query = r#"
+    BEGIN TRANSACTION;
+    UPSERT type::thing("tag", $into.label) CONTENT $into;
+    UPDATE transaction SET tags = array::distinct(
+        (SELECT VALUE IF label IN $labels THEN $into ELSE $this END FROM $parent.tags)
+    ) WHERE tags.label ANYINSIDE $labels;
+    UPDATE transaction SET splits = (
+        SELECT VALUE {
+            category: category,
+            amount: amount,
+            description: description,
+            tags: array::distinct(
+                (SELECT VALUE IF label IN $labels THEN $into ELSE $this END FROM $parent.tags)
+            ),
+        } FROM $parent.splits
+    ) WHERE array::flatten(splits.tags.label) ANYINSIDE $labels;
+    UPDATE recurring SET tags = array::distinct(
+        (SELECT VALUE IF label IN $labels THEN $into ELSE $this END FROM $parent.tags)
+    ) WHERE tags.label ANYINSIDE $labels;
+    UPDATE rule SET tags = array::distinct(
+        (SELECT VALUE IF label IN $labels THEN $into ELSE $this END FROM $parent.tags)
+    ) WHERE tags.label ANYINSIDE $labels;
+    DELETE tag WHERE label IN $labels AND label != $into.label;
+    COMMIT TRANSACTION;"#;
+
+    db.query(query)
+        .bind(("labels", labels))
+        .bind(("into", serde_json::json!(into)))
+        .await?
+        .check()?;
+
+    Ok(())
+}
+
+/// Rename a tag everywhere it is used. Renaming a tag to the label of another
+/// existing tag merges both tags.
+pub async fn rename(
+    db: &Surreal<Db>,
+    label: String,
+    new_label: String,
+    force: bool,
+) -> Result<(), Error> {
+    let tag: Option<Tag> = db.select(("tag", &label)).await?;
+    let tag = tag.ok_or(Error::RecordNotFound)?;
+    let existing: Option<Tag> = db.select(("tag", &new_label)).await?;
+
+    replace(
+        db,
+        vec![label],
+        existing.unwrap_or(Tag {
+            label: new_label,
+            color: tag.color,
+        }),
+        force,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Change the color of a tag everywhere it is used. The color is only shown, so the
+/// copies of the tag on reconciled transactions are recolored too.
+pub async fn recolor(db: &Surreal<Db>, label: String, color: Option<String>) -> Result<(), Error> {
+    let tag: Option<Tag> = db.select(("tag", &label)).await?;
+    tag.ok_or(Error::RecordNotFound)?;
+
+    replace(db, vec![], Tag { label, color }, true).await?;
+
+    Ok(())
+}
+
+/// Replace the tags with the given labels by the `into` tag everywhere they are used.
+pub async fn merge(
+    db: &Surreal<Db>,
+    labels: Vec<String>,
+    into: String,
+    force: bool,
+) -> Result<(), Error> {
+    let into: Option<Tag> = db.select(("tag", &into)).await?;
+
+    replace(db, labels, into.ok_or(Error::RecordNotFound)?, force).await?;
+
+    Ok(())
+}
+
+/// Delete a tag and remove it from every transaction, split, recurring transaction and
+/// rule. Reconciled transactions are only changed if `force` is set.
+pub async fn delete(db: &Surreal<Db>, label: String, force: bool) -> Result<(), Error> {
+    if !force {
+        super::check_none_reconciled(db, WITH_LABELS, ("labels", vec![label.clone()])).await?;
+    }
+
+    let query = r#"
+    BEGIN TRANSACTION;
+    UPDATE transaction SET tags = (SELECT VALUE $this FROM $parent.tags WHERE label != $label)
+        WHERE $label IN tags.label;
+    UPDATE transaction SET splits = (
+        SELECT VALUE {
+            category: category,
+            amount: amount,
+            description: description,
+            tags: (SELECT VALUE $this FROM $parent.tags WHERE label != $label),
+        } FROM $parent.splits
+    ) WHERE $label IN array::flatten(splits.tags.label);
+    UPDATE recurring SET tags = (SELECT VALUE $this FROM $parent.tags WHERE label != $label)
+        WHERE $label IN tags.label;
+    UPDATE rule SET tags = (SELECT VALUE $this FROM $parent.tags WHERE label != $label)
+        WHERE $label IN tags.label;
+    DELETE type::thing("tag", $label);
+    COMMIT TRANSACTION;"#;
+
+    db.query(query).bind(("label", label)).await?.check()?;
+
+    Ok(())
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TagTotal {
+    pub currency: String,
+    pub total: Money,
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TagUsage {
+    pub tag: Tag,
+    /// Number of transactions with the tag, or with a split with the tag.
+    pub count: usize,
+    /// Sum of the amounts of the transactions and splits with the tag, for each currency.
+    pub totals: Vec<TagTotal>,
+    #[ts(as = "Option<String>", optional)]
+    pub last_used: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TagCurrencyUsage {
+    label: String,
+    currency: String,
+    /// Transactions with the tag, on themselves or on one of their splits.
+    transactions: Vec<RecordId>,
+    total: Money,
+    last_used: chrono::DateTime<chrono::Utc>,
+}
+
+/// Get the usage of every tag, most used first. The tags of the splits of a transaction
+/// replace its own tags, and are counted with the amount of their split.
+pub async fn usage(db: &Surreal<Db>) -> Result<Vec<TagUsage>, surrealdb::Error> {
+    let query = r#"
+    LET $transactions = SELECT id, tags.label AS label, amount, date, account.currency AS currency
+        FROM transaction
+        WHERE !splits
+        SPLIT label;
+    LET $splits = SELECT id, split.tags.label AS label, split.amount AS amount, date, currency
+        FROM (
+            SELECT id, splits AS split, date, account.currency AS currency
+            FROM transaction
+            WHERE splits
+            SPLIT split
+        )
+        SPLIT label;
+    SELECT
+        label,
+        currency,
+        array::group([id]) AS transactions,
+        math::sum(amount) AS total,
+        time::max(date) AS last_used
+    FROM array::concat($transactions, $splits)
+    GROUP BY label, currency"#;
+
+    let usages: Vec<TagCurrencyUsage> = db.query(query).await?.take(2)?;
+
+    let mut tags: Vec<TagUsage> = read(db)
+        .await?
+        .into_iter()
+        .map(|tag| {
+            let usages = usages.iter().filter(|usage| usage.label == tag.label);
+
+            TagUsage {
+                count: usages.clone().map(|usage| usage.transactions.len()).sum(),
+                totals: usages
+                    .clone()
+                    .map(|usage| TagTotal {
+                        currency: usage.currency.clone(),
+                        total: usage.total,
+                    })
+                    .collect(),
+                last_used: usages.map(|usage| usage.last_used).max(),
+                tag,
+            }
+        })
+        .collect();
+
+    tags.sort_by_key(|usage| std::cmp::Reverse(usage.count));
+
+    Ok(tags)
+}
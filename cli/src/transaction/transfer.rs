@@ -0,0 +1,107 @@
+use rust_decimal::Decimal;
+use surrealdb::{engine::local::Db, RecordId, Surreal};
+
+use crate::{money::Money, transaction::TransactionWithId, Error};
+
+/// Links both transactions of a transfer between two accounts.
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Transfer {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub id: RecordId,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub from: RecordId,
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub to: RecordId,
+    /// Amount sent, in the currency of the `from` account, without the fee.
+    pub sent: Money,
+    /// Amount received, in the currency of the `to` account.
+    pub received: Money,
+    /// Fee paid on the `from` account on top of the sent amount.
+    pub fee: Money,
+    /// Implied exchange rate, `received / sent`.
+    #[ts(type = "number")]
+    #[serde(with = "crate::money::decimal")]
+    pub rate: Decimal,
+    /// Transaction on the `from` account.
+    pub from_transaction: TransactionWithId,
+    /// Transaction on the `to` account.
+    pub to_transaction: TransactionWithId,
+}
+
+/// The transfer record, without its transactions.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub(crate) struct TransferRecord {
+    pub id: RecordId,
+    pub from: RecordId,
+    pub sent: Money,
+    pub received: Money,
+    pub fee: Money,
+}
+
+impl TransferRecord {
+    /// Compute the sent and received amounts after one of the transactions of the transfer
+    /// changed to `transaction`. Transfers without conversion keep both amounts equal,
+    /// otherwise the amount of the other side is kept as is.
+    pub(crate) fn amounts_after_update(&self, transaction: &TransactionWithId) -> (Money, Money) {
+        let mirrored = self.sent == self.received;
+
+        if transaction.account == self.from {
+            let sent = -transaction.inner.amount - self.fee;
+            (sent, if mirrored { sent } else { self.received })
+        } else {
+            let received = transaction.inner.amount;
+            (if mirrored { received } else { self.sent }, received)
+        }
+    }
+}
+
+/// Exchange rate implied by the amounts of a transfer.
+pub(crate) fn implied_rate(sent: Money, received: Money) -> Decimal {
+    received
+        .to_decimal()
+        .checked_div(sent.to_decimal())
+        .unwrap_or(Decimal::ONE)
+}
+
+#[derive(ts_rs::TS)]
+#[ts(export)]
+#[derive(Debug, serde::Deserialize)]
+pub struct ReadTransferOptions {
+    #[ts(type = "{ tb: string, id: { String: string }}")]
+    pub account: RecordId,
+    /// Only get transfers between `account` and this account, in both directions.
+    #[ts(type = "{ tb: string, id: { String: string }}", optional)]
+    pub other_account: Option<RecordId>,
+}
+
+/// List transfers from or to an account, most recent first.
+pub async fn read(db: &Surreal<Db>, options: ReadTransferOptions) -> Result<Vec<Transfer>, Error> {
+    let mut query = r#"
+    SELECT
+        *,
+        (SELECT * FROM ONLY transaction WHERE transfer = $parent.id AND account = $parent.from LIMIT 1) AS from_transaction,
+        (SELECT * FROM ONLY transaction WHERE transfer = $parent.id AND account = $parent.to LIMIT 1) AS to_transaction
+    FROM transfer"#
+        .to_string();
+
+    if options.other_account.is_some() {
+        query.push_str(
+            " WHERE (from = $account AND to = $other) OR (from = $other AND to = $account)",
+        );
+    } else {
+        query.push_str(" WHERE from = $account OR to = $account");
+    }
+
+    let mut transfers: Vec<Transfer> = db
+        .query(query)
+        .bind(("account", options.account))
+        .bind(("other", options.other_account))
+        .await?
+        .take(0)?;
+
+    transfers.sort_by_key(|transfer| std::cmp::Reverse(transfer.from_transaction.inner.date));
+
+    Ok(transfers)
+}
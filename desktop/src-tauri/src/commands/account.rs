@@ -1,8 +1,12 @@
 use surrealdb::engine::local::Db;
 use surrealdb::{RecordId, Surreal};
 use tauri::State;
+use thunes_cli::account::assertion::{
+    BalanceAssertion, CreateBalanceAssertionOptions, FailedBalanceAssertion,
+};
 use thunes_cli::account::BalanceOptions;
-use thunes_cli::account::{Account, AccountIdentifiers};
+use thunes_cli::account::{Account, AccountIdentifiers, ReadAccountsOptions};
+use thunes_cli::money::Money;
 
 pub type Accounts = std::collections::HashMap<String, Account>;
 
@@ -10,10 +14,11 @@ pub type Accounts = std::collections::HashMap<String, Account>;
 #[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
 pub async fn list_accounts(
     database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: Option<ReadAccountsOptions>,
 ) -> Result<Vec<AccountIdentifiers>, String> {
     let database = database.lock().await;
 
-    thunes_cli::account::list_accounts(&database)
+    thunes_cli::account::list_accounts(&database, options.unwrap_or_default())
         .await
         .map_err(|error| {
             tracing::error!(%error, "database error");
@@ -25,10 +30,11 @@ pub async fn list_accounts(
 #[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
 pub async fn list_accounts_with_details(
     database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: Option<ReadAccountsOptions>,
 ) -> Result<Vec<Account>, String> {
     let database = database.lock().await;
 
-    thunes_cli::account::list_accounts_with_details(&database)
+    thunes_cli::account::list_accounts_with_details(&database, options.unwrap_or_default())
         .await
         .map_err(|error| {
             tracing::error!(%error, "database error");
@@ -106,7 +112,7 @@ pub async fn get_balance(
     database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
     account_id: RecordId,
     options: Option<BalanceOptions>,
-) -> Result<f64, String> {
+) -> Result<Money, String> {
     let database = database.lock().await;
 
     thunes_cli::account::balance(&database, account_id, options.unwrap_or_default())
@@ -132,3 +138,67 @@ pub async fn get_currency_from_account(
             "failed to get currency".into()
         })
 }
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn add_balance_assertion(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: CreateBalanceAssertionOptions,
+) -> Result<BalanceAssertion, String> {
+    let database = database.lock().await;
+
+    thunes_cli::account::assertion::create(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to add balance assertion".into()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_balance_assertions(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    account_id: RecordId,
+) -> Result<Vec<BalanceAssertion>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::account::assertion::read(&database, account_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to get balance assertions".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn delete_balance_assertion(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    assertion: RecordId,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::account::assertion::delete(&database, assertion)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to delete balance assertion".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn check_balance_assertions(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    account_id: RecordId,
+) -> Result<Vec<FailedBalanceAssertion>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::account::assertion::check(&database, account_id)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to check balance assertions".into()
+        })
+}
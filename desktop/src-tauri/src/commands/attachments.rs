@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+
+use surrealdb::engine::local::Db;
+use surrealdb::{RecordId, Surreal};
+use tauri::State;
+use tauri_plugin_opener::OpenerExt;
+use thunes_cli::transaction::attachment::Attachment;
+
+/// Directory where the files attached to transactions are copied, next to the database.
+pub struct AttachmentsDirectory(pub PathBuf);
+
+#[tauri::command]
+#[tracing::instrument(skip(database, directory), ret(level = tracing::Level::DEBUG))]
+pub async fn add_attachment(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    directory: State<'_, AttachmentsDirectory>,
+    transaction: RecordId,
+    path: PathBuf,
+) -> Result<Attachment, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::attachment::add(&database, &directory.0, transaction, &path)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to add attachment".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_attachments(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    transaction: RecordId,
+) -> Result<Vec<Attachment>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::attachment::read(&database, transaction)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to get attachments".to_string()
+        })
+}
+
+/// Open an attachment with the default application for its type.
+#[tauri::command]
+#[tracing::instrument(skip(app, database, directory), ret(level = tracing::Level::DEBUG))]
+pub async fn open_attachment<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    directory: State<'_, AttachmentsDirectory>,
+    attachment: RecordId,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    let path = thunes_cli::transaction::attachment::path(&database, &directory.0, attachment)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to get attachment".to_string()
+        })?;
+
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|error| {
+            tracing::error!(%error, "opener error");
+            "failed to open attachment".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database, directory), ret(level = tracing::Level::DEBUG))]
+pub async fn remove_attachment(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    directory: State<'_, AttachmentsDirectory>,
+    attachment: RecordId,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::attachment::remove(&database, &directory.0, attachment)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to remove attachment".to_string()
+        })
+}
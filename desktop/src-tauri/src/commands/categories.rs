@@ -1,6 +1,6 @@
-use surrealdb::{engine::local::Db, Surreal};
+use surrealdb::{engine::local::Db, RecordId, Surreal};
 use tauri::State;
-use thunes_cli::transaction::category::CategoryWithId;
+use thunes_cli::transaction::category::{CategoryWithId, CreateCategoryOptions};
 
 #[tauri::command]
 #[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
@@ -16,3 +16,59 @@ pub async fn get_categories(
             "failed to get category".to_string()
         })
 }
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn add_category(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: CreateCategoryOptions,
+) -> Result<CategoryWithId, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::category::create(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to add category".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn update_category(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    category: CategoryWithId,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::category::update(&database, category)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to update category".to_string()
+        })
+}
+
+/// Delete a category and move everything using it to `replacement`.
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn delete_category(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    category: RecordId,
+    replacement: RecordId,
+    force: Option<bool>,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::category::delete(
+        &database,
+        category,
+        replacement,
+        force.unwrap_or_default(),
+    )
+    .await
+    .map_err(|error| {
+        error.trace();
+        "failed to delete category".to_string()
+    })
+}
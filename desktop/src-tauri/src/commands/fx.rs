@@ -0,0 +1,86 @@
+use surrealdb::engine::local::Db;
+use surrealdb::{RecordId, Surreal};
+use tauri::State;
+use thunes_cli::fx::{
+    ConversionOptions, CreateExchangeRateOptions, ExchangeRate, NetWorth, ReadExchangeRateOptions,
+};
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn add_exchange_rate(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: CreateExchangeRateOptions,
+) -> Result<ExchangeRate, String> {
+    let database = database.lock().await;
+
+    thunes_cli::fx::create(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to add exchange rate".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn list_exchange_rates(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: Option<ReadExchangeRateOptions>,
+) -> Result<Vec<ExchangeRate>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::fx::read(&database, options.unwrap_or_default())
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to list exchange rates".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn update_exchange_rate(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    rate: ExchangeRate,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::fx::update(&database, rate)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to update exchange rate".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn delete_exchange_rate(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    rate: RecordId,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::fx::delete(&database, rate)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to delete exchange rate".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_net_worth(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: ConversionOptions,
+) -> Result<NetWorth, String> {
+    let database = database.lock().await;
+
+    thunes_cli::fx::net_worth(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to get net worth".to_string()
+        })
+}
@@ -0,0 +1,232 @@
+use surrealdb::engine::local::Db;
+use surrealdb::{RecordId, Surreal};
+use tauri::State;
+use thunes_cli::import::csv::{CreateCsvProfileOptions, CsvOptions, CsvProfile};
+use thunes_cli::import::qif::{QifCategoryMapping, QifOptions, QifPreview};
+use thunes_cli::import::{EntryDate, ImportSummary, PreviewRow};
+use thunes_cli::transaction::AddTransactionOptions;
+
+#[tauri::command]
+#[tracing::instrument(ret(level = tracing::Level::DEBUG))]
+pub async fn preview_csv(path: String, options: CsvOptions) -> Result<Vec<PreviewRow>, String> {
+    thunes_cli::import::csv::preview(&path, &options).map_err(|error| {
+        error.trace();
+        "failed to read csv file".to_string()
+    })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn import_transactions(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    account_id: RecordId,
+    transactions: Vec<AddTransactionOptions>,
+) -> Result<ImportSummary, String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::create(&database, account_id, transactions)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to import transactions".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn import_ofx(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    path: String,
+    account_id: RecordId,
+) -> Result<ImportSummary, String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::ofx::import(&database, &path, account_id)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to import ofx file".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn import_camt(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    path: String,
+    account_id: RecordId,
+    date: Option<EntryDate>,
+) -> Result<ImportSummary, String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::camt::import(&database, &path, account_id, date.unwrap_or_default())
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to import camt.053 file".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn import_mt940(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    path: String,
+    account_id: RecordId,
+    date: Option<EntryDate>,
+) -> Result<ImportSummary, String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::mt940::import(&database, &path, account_id, date.unwrap_or_default())
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to import mt940 file".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn preview_qif(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    path: String,
+    options: QifOptions,
+) -> Result<QifPreview, String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::qif::preview(&database, &path, &options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to read qif file".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn export_qif(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    account_id: RecordId,
+    path: String,
+    options: QifOptions,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::qif::export_to_file(&database, account_id, &path, &options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to export qif file".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_qif_category_mappings(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+) -> Result<Vec<QifCategoryMapping>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::qif::read_category_mappings(&database)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to get qif category mappings".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn set_qif_category_mapping(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    mapping: QifCategoryMapping,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::qif::set_category_mapping(&database, mapping)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to set qif category mapping".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn delete_qif_category_mapping(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    name: String,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::qif::delete_category_mapping(&database, name)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to delete qif category mapping".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn add_csv_profile(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: CreateCsvProfileOptions,
+) -> Result<CsvProfile, String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::csv::create_profile(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to add csv profile".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_csv_profiles(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    account_id: RecordId,
+) -> Result<Vec<CsvProfile>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::csv::read_profiles(&database, account_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to get csv profiles".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn update_csv_profile(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    profile: CsvProfile,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::csv::update_profile(&database, profile)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to update csv profile".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn delete_csv_profile(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    profile: RecordId,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::import::csv::delete_profile(&database, profile)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to delete csv profile".to_string()
+        })
+}
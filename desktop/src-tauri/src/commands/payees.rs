@@ -0,0 +1,101 @@
+use surrealdb::engine::local::Db;
+use surrealdb::{RecordId, Surreal};
+use tauri::State;
+use thunes_cli::transaction::payee::{
+    CreatePayeeOptions, Payee, PayeeSpending, PayeeSpendingOptions,
+};
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn add_payee(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: CreatePayeeOptions,
+) -> Result<Payee, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::payee::create(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to add payee".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_payees(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+) -> Result<Vec<Payee>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::payee::read(&database)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to get payees".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn update_payee(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    payee: Payee,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::payee::update(&database, payee)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to update payee".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn delete_payee(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    payee: RecordId,
+    force: Option<bool>,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::payee::delete(&database, payee, force.unwrap_or_default())
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to delete payee".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn assign_payees(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+) -> Result<usize, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::payee::assign(&database)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to assign payees".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_payee_spending(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: PayeeSpendingOptions,
+) -> Result<Vec<PayeeSpending>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::payee::spending(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to get payee spending".to_string()
+        })
+}
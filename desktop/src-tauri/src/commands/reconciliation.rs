@@ -0,0 +1,103 @@
+use surrealdb::engine::local::Db;
+use surrealdb::{RecordId, Surreal};
+use tauri::State;
+use thunes_cli::account::reconciliation::{
+    Reconciliation, ReconciliationSummary, StartReconciliationOptions,
+};
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn start_reconciliation(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: StartReconciliationOptions,
+) -> Result<ReconciliationSummary, String> {
+    let database = database.lock().await;
+
+    thunes_cli::account::reconciliation::start(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to start reconciliation".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_reconciliations(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    account_id: RecordId,
+) -> Result<Vec<Reconciliation>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::account::reconciliation::read(&database, account_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to get reconciliations".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_reconciliation_summary(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    reconciliation: RecordId,
+) -> Result<ReconciliationSummary, String> {
+    let database = database.lock().await;
+
+    thunes_cli::account::reconciliation::summary(&database, reconciliation)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to get reconciliation summary".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn toggle_cleared(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    reconciliation: RecordId,
+    transaction: RecordId,
+) -> Result<ReconciliationSummary, String> {
+    let database = database.lock().await;
+
+    thunes_cli::account::reconciliation::toggle(&database, reconciliation, transaction)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to toggle transaction".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn finish_reconciliation(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    reconciliation: RecordId,
+) -> Result<ReconciliationSummary, String> {
+    let database = database.lock().await;
+
+    thunes_cli::account::reconciliation::finish(&database, reconciliation)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to finish reconciliation".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn cancel_reconciliation(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    reconciliation: RecordId,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::account::reconciliation::cancel(&database, reconciliation)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to cancel reconciliation".to_string()
+        })
+}
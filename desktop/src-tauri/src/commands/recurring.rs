@@ -0,0 +1,103 @@
+use surrealdb::engine::local::Db;
+use surrealdb::{RecordId, Surreal};
+use tauri::State;
+use thunes_cli::transaction::recurring::{
+    CreateRecurringOptions, Occurrence, PreviewRecurringOptions, Recurring,
+};
+use thunes_cli::transaction::TransactionWithId;
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn add_recurring_transaction(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: CreateRecurringOptions,
+) -> Result<Recurring, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::recurring::create(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to add recurring transaction".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn list_recurring_transactions(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    account: Option<RecordId>,
+) -> Result<Vec<Recurring>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::recurring::read(&database, account)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to list recurring transactions".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn update_recurring_transaction(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    recurring: Recurring,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::recurring::update(&database, recurring)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to update recurring transaction".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn delete_recurring_transaction(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    recurring: RecordId,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::recurring::delete(&database, recurring)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to delete recurring transaction".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn preview_recurring_transactions(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: PreviewRecurringOptions,
+) -> Result<Vec<Occurrence>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::recurring::preview(&database, options)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to preview recurring transactions".to_string()
+        })
+}
+
+/// Create the transactions of all occurrences due until now.
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn apply_recurring_transactions(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+) -> Result<Vec<TransactionWithId>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::recurring::materialize(&database, chrono::Utc::now())
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to apply recurring transactions".to_string()
+        })
+}
@@ -0,0 +1,83 @@
+use surrealdb::engine::local::Db;
+use surrealdb::{RecordId, Surreal};
+use tauri::State;
+use thunes_cli::transaction::rule::{ApplyRulesOptions, CreateRuleOptions, Rule, RuleChange};
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn add_rule(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: CreateRuleOptions,
+) -> Result<Rule, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::rule::create(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to add rule".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_rules(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+) -> Result<Vec<Rule>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::rule::read(&database)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to get rules".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn update_rule(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    rule: Rule,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::rule::update(&database, rule)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to update rule".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn delete_rule(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    rule: RecordId,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::rule::delete(&database, rule)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to delete rule".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn apply_rules(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: ApplyRulesOptions,
+) -> Result<Vec<RuleChange>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::rule::apply(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to apply rules".to_string()
+        })
+}
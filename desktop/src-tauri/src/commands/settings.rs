@@ -2,6 +2,8 @@ use surrealdb::{engine::local::Db, Surreal};
 use tauri::State;
 use thunes_cli::settings::Settings;
 
+use crate::commands::attachments::AttachmentsDirectory;
+
 pub const TIME_FORMAT: &[time::format_description::FormatItem<'_>] =
     time_macros::format_description!("[year]-[month]-[day]T[hour]:[minute]:[second].surql");
 
@@ -37,14 +39,15 @@ pub async fn save_settings(
 }
 
 #[tauri::command]
-#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+#[tracing::instrument(skip(database, attachments), ret(level = tracing::Level::DEBUG))]
 pub async fn import_backup(
     database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    attachments: State<'_, AttachmentsDirectory>,
     path: &str,
 ) -> Result<(), String> {
     let database = database.lock().await;
 
-    thunes_cli::settings::import_backup(&database, path)
+    thunes_cli::settings::import_backup(&database, path, &attachments.0)
         .await
         .map_err(|error| {
             error.trace();
@@ -53,13 +56,14 @@ pub async fn import_backup(
 }
 
 #[tauri::command]
-#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+#[tracing::instrument(skip(database, attachments), ret(level = tracing::Level::DEBUG))]
 pub async fn export_backup(
     database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    attachments: State<'_, AttachmentsDirectory>,
 ) -> Result<(), String> {
     let database = database.lock().await;
 
-    thunes_cli::settings::export_backup(&database)
+    thunes_cli::settings::export_backup(&database, &attachments.0)
         .await
         .map_err(|error| {
             error.trace();
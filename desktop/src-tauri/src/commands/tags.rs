@@ -1,7 +1,7 @@
 use surrealdb::engine::local::Db;
 use surrealdb::Surreal;
 use tauri::State;
-use thunes_cli::transaction::tag::Tag;
+use thunes_cli::transaction::tag::{Tag, TagUsage};
 
 #[tauri::command]
 pub async fn get_tags(
@@ -31,3 +31,88 @@ pub async fn add_tags(
             "failed to add tags".to_string()
         })
 }
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn rename_tag(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    label: String,
+    new_label: String,
+    force: Option<bool>,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::tag::rename(&database, label, new_label, force.unwrap_or_default())
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to rename tag".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn recolor_tag(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    label: String,
+    color: Option<String>,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::tag::recolor(&database, label, color)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to recolor tag".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn merge_tags(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    labels: Vec<String>,
+    into: String,
+    force: Option<bool>,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::tag::merge(&database, labels, into, force.unwrap_or_default())
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to merge tags".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn delete_tag(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    label: String,
+    force: Option<bool>,
+) -> Result<(), String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::tag::delete(&database, label, force.unwrap_or_default())
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to delete tag".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_tags_usage(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+) -> Result<Vec<TagUsage>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::tag::usage(&database)
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "database error");
+            "failed to get tags usage".to_string()
+        })
+}
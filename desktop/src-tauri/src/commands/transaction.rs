@@ -2,11 +2,17 @@ use surrealdb::engine::local::Db;
 use surrealdb::{RecordId, Surreal};
 use tauri::State;
 use thunes_cli::budget::category::{ReadCategoryOptions, ReadCategoryResult};
+use thunes_cli::transaction::duplicate::{DuplicateGroup, DuplicateOptions};
+use thunes_cli::transaction::suggestion::CategorySuggestion;
+use thunes_cli::transaction::transfer::{ReadTransferOptions, Transfer};
 use thunes_cli::transaction::{
     AddTransactionOptions, AddTransactionTransferOptions, CurrencyBalance, ReadTransactionOptions,
+    ReadTransactionPageOptions, SearchResult, SearchTransactionOptions, TransactionPage,
     TransactionWithId,
 };
 
+use crate::commands::attachments::AttachmentsDirectory;
+
 #[tauri::command]
 #[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
 pub async fn add_transaction(
@@ -40,6 +46,22 @@ pub async fn add_transaction_transfer(
         })
 }
 
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_transfers(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: ReadTransferOptions,
+) -> Result<Vec<Transfer>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::transfer::read(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to get transfers".to_string()
+        })
+}
+
 #[tauri::command]
 #[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
 pub async fn get_transactions(
@@ -57,35 +79,138 @@ pub async fn get_transactions(
         })
 }
 
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_transaction_page(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    account_id: RecordId,
+    options: ReadTransactionPageOptions,
+) -> Result<TransactionPage, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::read_page(&database, account_id, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to get transactions".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn search_transactions(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: SearchTransactionOptions,
+) -> Result<SearchResult, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::search(&database, options)
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to search transactions".to_string()
+        })
+}
+
 #[tauri::command]
 #[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
 pub async fn update_transaction(
     database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
     transaction: TransactionWithId,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let database = database.lock().await;
 
-    thunes_cli::transaction::update(&database, transaction)
+    thunes_cli::transaction::update(&database, transaction, force.unwrap_or_default())
         .await
         .map_err(|error| {
-            tracing::error!(%error, "database error");
+            error.trace();
             "failed to update transaction".to_string()
         })
 }
 
 #[tauri::command]
-#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+#[tracing::instrument(skip(database, directory), ret(level = tracing::Level::DEBUG))]
 pub async fn delete_transaction(
     database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    directory: State<'_, AttachmentsDirectory>,
     transaction: RecordId,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let database = database.lock().await;
 
-    thunes_cli::transaction::delete(&database, transaction)
+    let attachments =
+        thunes_cli::transaction::delete(&database, transaction, force.unwrap_or_default())
+            .await
+            .map_err(|error| {
+                error.trace();
+                "failed to update transaction".to_string()
+            })?;
+
+    // Remove the files of the attachments of the transaction.
+    thunes_cli::transaction::attachment::remove_files(&database, &directory.0, &attachments)
         .await
         .map_err(|error| {
-            tracing::error!(%error, "database error");
-            "failed to update transaction".to_string()
+            error.trace();
+            "failed to remove attachments".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn get_duplicate_transactions(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    account_id: RecordId,
+    options: Option<DuplicateOptions>,
+) -> Result<Vec<DuplicateGroup>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::duplicate::scan(&database, account_id, options.unwrap_or_default())
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to get duplicate transactions".to_string()
+        })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn merge_duplicate_transactions(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    keep: RecordId,
+    duplicates: Vec<RecordId>,
+    force: Option<bool>,
+) -> Result<TransactionWithId, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::duplicate::merge(
+        &database,
+        keep,
+        duplicates,
+        force.unwrap_or_default(),
+    )
+    .await
+    .map_err(|error| {
+        error.trace();
+        "failed to merge duplicate transactions".to_string()
+    })
+}
+
+/// Suggest categories for each description, three at most unless `limit` is given.
+#[tauri::command]
+#[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
+pub async fn suggest_categories(
+    database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    descriptions: Vec<String>,
+    limit: Option<usize>,
+) -> Result<Vec<Vec<CategorySuggestion>>, String> {
+    let database = database.lock().await;
+
+    thunes_cli::transaction::suggestion::suggest(&database, &descriptions, limit.unwrap_or(3))
+        .await
+        .map_err(|error| {
+            error.trace();
+            "failed to suggest categories".to_string()
         })
 }
 
@@ -93,10 +218,11 @@ pub async fn delete_transaction(
 #[tracing::instrument(skip(database), ret(level = tracing::Level::DEBUG))]
 pub async fn get_all_balance(
     database: State<'_, tokio::sync::Mutex<Surreal<Db>>>,
+    options: Option<thunes_cli::account::ReadAccountsOptions>,
 ) -> Result<Vec<CurrencyBalance>, String> {
     let database = database.lock().await;
 
-    thunes_cli::transaction::balances_by_currency(&database)
+    thunes_cli::transaction::balances_by_currency(&database, options.unwrap_or_default())
         .await
         .map_err(|error| {
             tracing::error!(%error, "database error");
@@ -1,16 +1,28 @@
 use tauri::{App, Manager};
 use thunes_cli::{
-    migrations::{m1_account_filter::AccountFilterMigration, run_migrations},
+    migrations::{
+        m1_account_filter::AccountFilterMigration, m2_decimal_amounts::DecimalAmountsMigration,
+        m3_account_metadata::AccountMetadataMigration, m4_opening_balance::OpeningBalanceMigration,
+        m5_transaction_sort::TransactionSortMigration,
+        m6_transaction_filters::TransactionFiltersMigration, run_migrations,
+    },
     settings::Settings,
     Record,
 };
 
 pub mod commands {
     pub mod account;
+    pub mod attachments;
     pub mod budget;
     pub mod categories;
     pub mod currency;
+    pub mod fx;
+    pub mod import;
+    pub mod payees;
     pub mod portfolio;
+    pub mod reconciliation;
+    pub mod recurring;
+    pub mod rules;
     pub mod settings;
     pub mod tags;
     pub mod transaction;
@@ -44,6 +56,8 @@ fn setup(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>> {
 
     path.push("store");
 
+    let attachments = path.with_file_name("attachments");
+
     let mut data_dir = path_resolver.app_data_dir()?;
 
     data_dir.push("backups");
@@ -59,9 +73,19 @@ fn setup(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>> {
             .await
             .map_err(|error| error.to_string())?;
 
-        run_migrations(vec![Box::new(AccountFilterMigration {})], &db)
-            .await
-            .map_err(|()| "failed to run migrations, check the logs".to_string())?;
+        run_migrations(
+            vec![
+                Box::new(AccountFilterMigration {}),
+                Box::new(DecimalAmountsMigration {}),
+                Box::new(AccountMetadataMigration {}),
+                Box::new(OpeningBalanceMigration {}),
+                Box::new(TransactionSortMigration {}),
+                Box::new(TransactionFiltersMigration {}),
+            ],
+            &db,
+        )
+        .await
+        .map_err(|()| "failed to run migrations, check the logs".to_string())?;
 
         // FIXME: move db seeding to an install script.
         // Categories
@@ -97,10 +121,18 @@ fn setup(app: &mut App) -> std::result::Result<(), Box<dyn std::error::Error>> {
             }
         }
 
+        // Create the transactions of recurring transactions that are due since the last start.
+        if let Err(error) =
+            thunes_cli::transaction::recurring::materialize(&db, chrono::Utc::now()).await
+        {
+            error.trace();
+        }
+
         Ok(db)
     }))??;
 
     app.manage(tokio::sync::Mutex::new(db));
+    app.manage(commands::attachments::AttachmentsDirectory(attachments));
 
     Ok(())
 }
@@ -110,6 +142,7 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![
             commands::account::get_account,
             commands::account::update_account,
@@ -118,14 +151,65 @@ pub fn run() {
             commands::account::add_account,
             commands::account::delete_account,
             commands::account::get_currency_from_account,
+            commands::account::add_balance_assertion,
+            commands::account::get_balance_assertions,
+            commands::account::delete_balance_assertion,
+            commands::account::check_balance_assertions,
+            commands::reconciliation::start_reconciliation,
+            commands::reconciliation::get_reconciliations,
+            commands::reconciliation::get_reconciliation_summary,
+            commands::reconciliation::toggle_cleared,
+            commands::reconciliation::finish_reconciliation,
+            commands::reconciliation::cancel_reconciliation,
             commands::account::get_balance,
             commands::transaction::get_all_balance,
             commands::transaction::add_transaction,
             commands::transaction::add_transaction_transfer,
+            commands::transaction::get_transfers,
             commands::transaction::get_transactions,
+            commands::transaction::get_transaction_page,
+            commands::transaction::search_transactions,
             commands::transaction::get_transactions_by_category,
             commands::transaction::update_transaction,
             commands::transaction::delete_transaction,
+            commands::transaction::get_duplicate_transactions,
+            commands::transaction::merge_duplicate_transactions,
+            commands::transaction::suggest_categories,
+            commands::attachments::add_attachment,
+            commands::attachments::get_attachments,
+            commands::attachments::open_attachment,
+            commands::attachments::remove_attachment,
+            commands::import::preview_csv,
+            commands::import::import_transactions,
+            commands::import::import_ofx,
+            commands::import::import_camt,
+            commands::import::import_mt940,
+            commands::import::preview_qif,
+            commands::import::export_qif,
+            commands::import::get_qif_category_mappings,
+            commands::import::set_qif_category_mapping,
+            commands::import::delete_qif_category_mapping,
+            commands::import::add_csv_profile,
+            commands::import::get_csv_profiles,
+            commands::import::update_csv_profile,
+            commands::import::delete_csv_profile,
+            commands::recurring::add_recurring_transaction,
+            commands::recurring::list_recurring_transactions,
+            commands::recurring::update_recurring_transaction,
+            commands::recurring::delete_recurring_transaction,
+            commands::recurring::preview_recurring_transactions,
+            commands::recurring::apply_recurring_transactions,
+            commands::rules::add_rule,
+            commands::rules::get_rules,
+            commands::rules::update_rule,
+            commands::rules::delete_rule,
+            commands::rules::apply_rules,
+            commands::payees::add_payee,
+            commands::payees::get_payees,
+            commands::payees::update_payee,
+            commands::payees::delete_payee,
+            commands::payees::assign_payees,
+            commands::payees::get_payee_spending,
             commands::budget::list_budgets,
             commands::budget::add_budget,
             commands::budget::get_budget,
@@ -142,7 +226,15 @@ pub fn run() {
             commands::budget::delete_budget_allocation,
             commands::tags::get_tags,
             commands::tags::add_tags,
+            commands::tags::rename_tag,
+            commands::tags::recolor_tag,
+            commands::tags::merge_tags,
+            commands::tags::delete_tag,
+            commands::tags::get_tags_usage,
             commands::categories::get_categories,
+            commands::categories::add_category,
+            commands::categories::update_category,
+            commands::categories::delete_category,
             commands::settings::get_settings,
             commands::settings::save_settings,
             commands::settings::export_backup,
@@ -154,6 +246,11 @@ pub fn run() {
             commands::portfolio::list_tiles,
             commands::portfolio::remove_tile,
             commands::portfolio::update_tile,
+            commands::fx::add_exchange_rate,
+            commands::fx::list_exchange_rates,
+            commands::fx::update_exchange_rate,
+            commands::fx::delete_exchange_rate,
+            commands::fx::get_net_worth,
         ])
         .setup(setup)
         .run(tauri::generate_context!())
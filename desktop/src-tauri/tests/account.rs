@@ -3,11 +3,22 @@ mod common;
 
 #[cfg(test)]
 mod tests {
+    use chrono::TimeZone;
     use tauri::Manager;
-    use thunes_cli::account::{Account, AddAccountOptions};
-    use thunes_lib::commands::account::{
-        add_account, delete_account, get_account, list_accounts, list_accounts_with_details,
-        update_account,
+    use thunes_cli::{
+        account::{
+            assertion::CreateBalanceAssertionOptions, Account, AccountKind, AddAccountOptions,
+            BalanceOptions, ReadAccountsOptions,
+        },
+        money::Money,
+        transaction::{tag::Tag, AddTransactionOptions, AddTransactionTransferOptions, Split},
+    };
+    use thunes_lib::commands::{
+        account::{
+            add_account, add_balance_assertion, check_balance_assertions, delete_account,
+            get_account, get_balance, list_accounts, list_accounts_with_details, update_account,
+        },
+        transaction::{add_transaction, add_transaction_transfer},
     };
 
     async fn setup() -> (tauri::App<tauri::test::MockRuntime>, Account) {
@@ -17,6 +28,7 @@ mod tests {
             AddAccountOptions {
                 currency: "EUR".to_string(),
                 name: "My Account".to_string(),
+                kind: AccountKind::Checking,
             },
         )
         .await
@@ -33,6 +45,7 @@ mod tests {
             AddAccountOptions {
                 currency: "$".to_string(),
                 name: "My FREEDOM Account".to_string(),
+                kind: AccountKind::Checking,
             },
         )
         .await
@@ -48,7 +61,7 @@ mod tests {
     pub async fn test_account_list() {
         let (app, _account) = setup().await;
 
-        let accounts = list_accounts(app.state())
+        let accounts = list_accounts(app.state(), None)
             .await
             .expect("failed to list accounts");
 
@@ -60,7 +73,7 @@ mod tests {
     pub async fn test_account_list_with_details() {
         let (app, _account) = setup().await;
 
-        let accounts = list_accounts_with_details(app.state())
+        let accounts = list_accounts_with_details(app.state(), None)
             .await
             .expect("failed to list accounts");
 
@@ -69,6 +82,62 @@ mod tests {
         assert_eq!(accounts[0].data.currency, "EUR".to_string());
     }
 
+    #[tokio::test]
+    pub async fn test_account_list_archived() {
+        let (app, mut account) = setup().await;
+
+        account.data.archived = true;
+        update_account(app.state(), account)
+            .await
+            .expect("failed to update account");
+
+        let accounts = list_accounts(app.state(), None)
+            .await
+            .expect("failed to list accounts");
+        let archived = list_accounts(
+            app.state(),
+            Some(ReadAccountsOptions {
+                include_archived: true,
+                ..Default::default()
+            }),
+        )
+        .await
+        .expect("failed to list accounts");
+
+        assert!(accounts.is_empty());
+        assert_eq!(archived.len(), 1);
+    }
+
+    #[tokio::test]
+    pub async fn test_account_list_by_kind() {
+        let (app, account) = setup().await;
+        let loan = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Mortgage".to_string(),
+                kind: AccountKind::Loan,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let accounts = list_accounts_with_details(
+            app.state(),
+            Some(ReadAccountsOptions {
+                kinds: vec![AccountKind::Loan],
+                ..Default::default()
+            }),
+        )
+        .await
+        .expect("failed to list accounts");
+
+        assert_eq!(account.data.position, 0);
+        assert_eq!(loan.data.position, 1);
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].id, loan.id);
+    }
+
     #[tokio::test]
     pub async fn test_account_get() {
         let (app, account) = setup().await;
@@ -106,4 +175,270 @@ mod tests {
             .expect("failed to create account");
         assert!(get_account(app.state(), account.id).await.is_err())
     }
+
+    fn date(month: u32, day: u32, hour: u32) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc
+            .with_ymd_and_hms(2026, month, day, hour, 0, 0)
+            .unwrap()
+    }
+
+    fn tags(labels: &[&str]) -> Vec<Tag> {
+        labels
+            .iter()
+            .map(|label| Tag {
+                label: label.to_string(),
+                color: None,
+            })
+            .collect()
+    }
+
+    /// Create an account with a few transactions, a split transaction and a transfer.
+    /// Its balance without any option is 56.
+    async fn setup_balance() -> (tauri::App<tauri::test::MockRuntime>, Account) {
+        let (app, account) = setup().await;
+        let savings = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Savings".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        for (amount, category, labels, date) in [
+            (100, "other", tags(&["salary"]), date(1, 1, 0)),
+            (-10, "transport", tags(&["work"]), date(1, 31, 23)),
+            (
+                -5,
+                "transport-taxi",
+                tags(&["salary", "night"]),
+                date(2, 1, 0),
+            ),
+        ] {
+            add_transaction(
+                app.state(),
+                account.id.clone(),
+                AddTransactionOptions {
+                    amount: Money::from(amount),
+                    category: Some(("category", category).into()),
+                    description: String::new(),
+                    tags: labels,
+                    date: Some(date),
+                    splits: vec![],
+                    external_id: None,
+                    payee: None,
+                },
+            )
+            .await
+            .expect("failed to add transaction");
+        }
+
+        add_transaction(
+            app.state(),
+            account.id.clone(),
+            AddTransactionOptions {
+                amount: Money::from(-9),
+                category: None,
+                description: "Trip".to_string(),
+                tags: vec![],
+                date: Some(date(2, 2, 0)),
+                splits: vec![
+                    Split {
+                        category: ("category", "transport-taxi").into(),
+                        amount: Money::from(-4),
+                        description: String::new(),
+                        tags: vec![],
+                    },
+                    Split {
+                        category: ("category", "other").into(),
+                        amount: Money::from(-5),
+                        description: String::new(),
+                        tags: vec![],
+                    },
+                ],
+                external_id: None,
+                payee: None,
+            },
+        )
+        .await
+        .expect("failed to add transaction");
+
+        add_transaction_transfer(
+            app.state(),
+            AddTransactionTransferOptions {
+                description: "Savings".to_string(),
+                amount: Money::from(20),
+                received: None,
+                fee: None,
+                date: Some(date(2, 3, 0)),
+                tags: vec![],
+                from: account.id.clone(),
+                to: savings.id.clone(),
+            },
+        )
+        .await
+        .expect("failed to add transfer");
+
+        (app, account)
+    }
+
+    async fn balance(
+        app: &tauri::App<tauri::test::MockRuntime>,
+        account: &Account,
+        options: BalanceOptions,
+    ) -> Money {
+        get_balance(app.state(), account.id.clone(), Some(options))
+            .await
+            .expect("failed to get balance")
+    }
+
+    #[tokio::test]
+    pub async fn test_balance_without_options() {
+        let (app, account) = setup_balance().await;
+
+        let total = get_balance(app.state(), account.id.clone(), None)
+            .await
+            .expect("failed to get balance");
+
+        assert_eq!(total, Money::from(56));
+        assert_eq!(
+            balance(&app, &account, BalanceOptions::default()).await,
+            total
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_balance_tags() {
+        let (app, account) = setup_balance().await;
+
+        let salary = BalanceOptions {
+            tags: vec!["salary".to_string()],
+            ..Default::default()
+        };
+        let any = BalanceOptions {
+            tags: vec!["work".to_string(), "night".to_string()],
+            ..Default::default()
+        };
+        let unknown = BalanceOptions {
+            tags: vec!["unknown".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(balance(&app, &account, salary).await, Money::from(95));
+        assert_eq!(balance(&app, &account, any).await, Money::from(-15));
+        assert_eq!(balance(&app, &account, unknown).await, Money::ZERO);
+    }
+
+    #[tokio::test]
+    pub async fn test_balance_category() {
+        let (app, account) = setup_balance().await;
+
+        let transport = BalanceOptions {
+            category: Some(("category", "transport").into()),
+            ..Default::default()
+        };
+        let taxi = BalanceOptions {
+            category: Some(("category", "transport-taxi").into()),
+            ..Default::default()
+        };
+
+        assert_eq!(balance(&app, &account, transport).await, Money::from(-19));
+        assert_eq!(balance(&app, &account, taxi).await, Money::from(-9));
+    }
+
+    #[tokio::test]
+    pub async fn test_balance_period() {
+        let (app, account) = setup_balance().await;
+
+        let options = BalanceOptions {
+            period_start: Some(date(1, 31, 23).into()),
+            period_end: Some(date(2, 1, 0).into()),
+            ..Default::default()
+        };
+
+        assert_eq!(balance(&app, &account, options).await, Money::from(-15));
+    }
+
+    #[tokio::test]
+    pub async fn test_balance_as_of() {
+        let (app, account) = setup_balance().await;
+
+        let options = BalanceOptions {
+            as_of: chrono::NaiveDate::from_ymd_opt(2026, 1, 31),
+            ..Default::default()
+        };
+
+        assert_eq!(balance(&app, &account, options).await, Money::from(90));
+    }
+
+    #[tokio::test]
+    pub async fn test_balance_exclude_internal_movements() {
+        let (app, account) = setup_balance().await;
+
+        let options = BalanceOptions {
+            exclude_internal_movements: true,
+            ..Default::default()
+        };
+
+        assert_eq!(balance(&app, &account, options).await, Money::from(76));
+    }
+
+    #[tokio::test]
+    pub async fn test_balance_opening_balance() {
+        let (app, mut account) = setup_balance().await;
+
+        account.data.opening_balance = "1234.56".parse().unwrap();
+        account.data.opening_date = Some(date(1, 1, 0).into());
+        update_account(app.state(), account.clone())
+            .await
+            .expect("failed to update account");
+
+        let before_opening = BalanceOptions {
+            as_of: chrono::NaiveDate::from_ymd_opt(2025, 12, 31),
+            ..Default::default()
+        };
+        let tags = BalanceOptions {
+            tags: vec!["salary".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            balance(&app, &account, BalanceOptions::default()).await,
+            "1290.56".parse().unwrap()
+        );
+        assert_eq!(balance(&app, &account, before_opening).await, Money::ZERO);
+        assert_eq!(balance(&app, &account, tags).await, Money::from(95));
+    }
+
+    #[tokio::test]
+    pub async fn test_balance_assertions() {
+        let (app, account) = setup_balance().await;
+
+        for (month, day, balance) in [(1, 30, 100), (1, 31, 100), (2, 3, 56)] {
+            add_balance_assertion(
+                app.state(),
+                CreateBalanceAssertionOptions {
+                    account: account.id.clone(),
+                    date: chrono::NaiveDate::from_ymd_opt(2026, month, day).unwrap(),
+                    balance: Money::from(balance),
+                },
+            )
+            .await
+            .expect("failed to add balance assertion");
+        }
+
+        let failed = check_balance_assertions(app.state(), account.id.clone())
+            .await
+            .expect("failed to check balance assertions");
+
+        assert_eq!(failed.len(), 1);
+        assert_eq!(
+            failed[0].assertion.date,
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap()
+        );
+        assert_eq!(failed[0].computed, Money::from(90));
+        assert_eq!(failed[0].difference, Money::from(-10));
+    }
 }
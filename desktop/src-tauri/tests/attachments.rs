@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use surrealdb::{engine::local::Db, Surreal};
+    use tauri::Manager;
+    use thunes_cli::{
+        account::{Account, AccountKind, AddAccountOptions},
+        money::Money,
+        settings::Settings,
+        transaction::{AddTransactionOptions, TransactionWithId},
+        Record,
+    };
+    use thunes_lib::commands::{
+        account::add_account,
+        attachments::{add_attachment, get_attachments, remove_attachment, AttachmentsDirectory},
+        settings::{export_backup, import_backup},
+        transaction::{add_transaction, delete_transaction},
+    };
+
+    async fn setup() -> (
+        tauri::App<tauri::test::MockRuntime>,
+        Account,
+        std::path::PathBuf,
+    ) {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "My Account".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        // Files to attach, next to the attachments directory of the test.
+        let mut files = app.state::<AttachmentsDirectory>().0.clone();
+
+        files.set_extension("files");
+        std::fs::create_dir_all(&files).expect("failed to create files directory");
+
+        (app, account, files)
+    }
+
+    async fn transaction(
+        app: &tauri::App<tauri::test::MockRuntime>,
+        account: &Account,
+        description: &str,
+    ) -> TransactionWithId {
+        add_transaction(
+            app.state(),
+            account.id.clone(),
+            AddTransactionOptions {
+                amount: Money::from(-10),
+                description: description.to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("failed to add transaction")
+    }
+
+    fn stored_files(app: &tauri::App<tauri::test::MockRuntime>) -> usize {
+        std::fs::read_dir(&app.state::<AttachmentsDirectory>().0)
+            .map(|entries| entries.count())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    pub async fn test_attachments() {
+        let (app, account, files) = setup().await;
+        let receipt = files.join("receipt.pdf");
+
+        std::fs::write(&receipt, b"%PDF-1.4 receipt").expect("failed to write file");
+
+        let laptop = transaction(&app, &account, "Laptop").await;
+        let refund = transaction(&app, &account, "Laptop refund").await;
+
+        assert!(add_attachment(
+            app.state(),
+            app.state(),
+            laptop.id.clone(),
+            files.join("missing.pdf")
+        )
+        .await
+        .is_err());
+
+        let attachment =
+            add_attachment(app.state(), app.state(), laptop.id.clone(), receipt.clone())
+                .await
+                .expect("failed to add attachment");
+
+        assert_eq!(attachment.name, "receipt.pdf");
+        assert_eq!(attachment.mime, "application/pdf");
+        assert_eq!(attachment.size, 16);
+
+        // The same file attached twice is stored once.
+        let copy = add_attachment(app.state(), app.state(), refund.id.clone(), receipt)
+            .await
+            .expect("failed to add attachment");
+
+        assert_eq!(copy.hash, attachment.hash);
+        assert_eq!(stored_files(&app), 1);
+
+        // Files of the directory that are not attachments are left as they are.
+        let unrelated = app.state::<AttachmentsDirectory>().0.join("notes.txt");
+
+        std::fs::write(&unrelated, b"notes").expect("failed to write file");
+
+        let attachments = get_attachments(app.state(), laptop.id.clone())
+            .await
+            .expect("failed to get attachments");
+
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].id, attachment.id);
+
+        remove_attachment(app.state(), app.state(), attachment.id)
+            .await
+            .expect("failed to remove attachment");
+
+        assert!(get_attachments(app.state(), laptop.id)
+            .await
+            .expect("failed to get attachments")
+            .is_empty());
+        assert_eq!(stored_files(&app), 2);
+
+        // Attachments and their files are removed with their transaction.
+        delete_transaction(app.state(), app.state(), refund.id.clone(), None)
+            .await
+            .expect("failed to delete transaction");
+
+        assert!(get_attachments(app.state(), refund.id)
+            .await
+            .expect("failed to get attachments")
+            .is_empty());
+        assert_eq!(stored_files(&app), 1);
+        assert!(unrelated.exists());
+    }
+
+    #[tokio::test]
+    pub async fn test_attachments_backup() {
+        let (app, account, files) = setup().await;
+        let backups = files.join("backups");
+        let invoice = files.join("invoice.png");
+
+        std::fs::write(&invoice, b"invoice").expect("failed to write file");
+
+        {
+            let database = app.state::<tokio::sync::Mutex<Surreal<Db>>>();
+            let database = database.lock().await;
+            let _: Option<Record> = database
+                .insert(("settings", "main"))
+                .content(Settings::new(backups.clone()))
+                .await
+                .expect("failed to create settings");
+        }
+
+        let laptop = transaction(&app, &account, "Laptop").await;
+
+        add_attachment(app.state(), app.state(), laptop.id.clone(), invoice)
+            .await
+            .expect("failed to add attachment");
+        export_backup(app.state(), app.state())
+            .await
+            .expect("failed to export backup");
+
+        // The attached files are saved next to the backup file.
+        let backup = std::fs::read_dir(&backups)
+            .expect("failed to read backups")
+            .map(|entry| entry.expect("failed to read backup").path())
+            .find(|path| path.is_file())
+            .expect("backup not found");
+
+        std::fs::remove_dir_all(&app.state::<AttachmentsDirectory>().0)
+            .expect("failed to remove attachments");
+
+        import_backup(app.state(), app.state(), backup.to_str().unwrap())
+            .await
+            .expect("failed to import backup");
+
+        let attachments = get_attachments(app.state(), laptop.id)
+            .await
+            .expect("failed to get attachments");
+
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].mime, "image/png");
+        assert_eq!(stored_files(&app), 1);
+    }
+}
@@ -12,11 +12,12 @@ mod tests {
     use surrealdb::RecordId;
     use tauri::Manager;
     use thunes_cli::{
-        account::{Account, AddAccountOptions},
+        account::{Account, AccountKind, AddAccountOptions},
         budget::{
             allocation::CreateAllocationOptions, expenses::ReadExpensesOptions,
             partition::CreatePartitionOptions, Budget, CreateSplitBudgetOptions, ExpensesPeriod,
         },
+        money::Money,
         transaction::AddTransactionOptions,
     };
     use thunes_lib::commands::{
@@ -34,6 +35,7 @@ mod tests {
             AddAccountOptions {
                 currency: "EUR".to_string(),
                 name: "My Account".to_string(),
+                kind: AccountKind::Checking,
             },
         )
         .await
@@ -43,7 +45,7 @@ mod tests {
             app.state(),
             CreateSplitBudgetOptions {
                 name: "My budget".to_string(),
-                income: 2000.0,
+                income: Money::from(2000),
                 currency: "EUR".to_string(),
                 accounts: vec![account.id.clone()],
             },
@@ -61,6 +63,7 @@ mod tests {
             AddAccountOptions {
                 currency: "EUR".to_string(),
                 name: "My Account".to_string(),
+                kind: AccountKind::Checking,
             },
         )
         .await
@@ -70,7 +73,7 @@ mod tests {
             app.state(),
             CreateSplitBudgetOptions {
                 name: "My budget".to_string(),
-                income: 2000.0,
+                income: Money::from(2000),
                 currency: "EUR".to_string(),
                 accounts: vec![account.id.clone()],
             },
@@ -96,7 +99,7 @@ mod tests {
             CreateAllocationOptions {
                 name: "Train".to_string(),
                 category: Some(category.clone()),
-                amount: 50.0,
+                amount: Money::from(50),
                 partition: partition.id.clone(),
             },
         )
@@ -107,7 +110,7 @@ mod tests {
             CreateAllocationOptions {
                 name: "Plane".to_string(),
                 category: Some(category.clone()),
-                amount: 300.0,
+                amount: Money::from(300),
                 partition: partition.id.clone(),
             },
         )
@@ -120,11 +123,14 @@ mod tests {
             app.state(),
             account.id.clone(),
             AddTransactionOptions {
-                amount: 25.0,
+                amount: Money::from(25),
                 category: Some(category.clone()),
                 description: "Going to work".to_string(),
                 tags: vec![],
+                splits: vec![],
                 date: Some(now.date_naive().and_hms_opt(1, 0, 0).unwrap().and_utc()),
+                external_id: None,
+                payee: None,
             },
         )
         .await
@@ -134,10 +140,11 @@ mod tests {
             app.state(),
             account.id.clone(),
             AddTransactionOptions {
-                amount: 200.0,
+                amount: Money::from(200),
                 category: Some(category.clone()),
                 description: "Going to summit".to_string(),
                 tags: vec![],
+                splits: vec![],
                 date: Some(
                     now.date_naive()
                         .with_day(15)
@@ -146,6 +153,8 @@ mod tests {
                         .unwrap()
                         .and_utc(),
                 ),
+                external_id: None,
+                payee: None,
             },
         )
         .await
@@ -154,10 +163,11 @@ mod tests {
             app.state(),
             account.id.clone(),
             AddTransactionOptions {
-                amount: 30.0,
+                amount: Money::from(30),
                 category: Some(category.clone()),
                 description: "Going to work".to_string(),
                 tags: vec![],
+                splits: vec![],
                 date: Some(
                     now.date_naive()
                         .with_day(25)
@@ -168,6 +178,8 @@ mod tests {
                         .unwrap()
                         .and_utc(),
                 ),
+                external_id: None,
+                payee: None,
             },
         )
         .await
@@ -182,7 +194,7 @@ mod tests {
 
         assert_eq!(budget.currency, "EUR");
         assert_eq!(budget.name, "My budget");
-        assert_eq!(budget.income, 2000.0);
+        assert_eq!(budget.income, Money::from(2000));
     }
 
     #[tokio::test]
@@ -205,7 +217,7 @@ mod tests {
             CreateAllocationOptions {
                 name: "Commute".to_string(),
                 category: None,
-                amount: 200.0,
+                amount: Money::from(200),
                 partition: partition.id.clone(),
             },
         )
@@ -217,7 +229,7 @@ mod tests {
 
         assert_eq!(allocation.partition, partition.id);
         assert_eq!(allocation.name, "Commute");
-        assert_eq!(allocation.amount, 200.0);
+        assert_eq!(allocation.amount, Money::from(200));
     }
 
     #[tokio::test]
@@ -236,8 +248,8 @@ mod tests {
         .await
         .unwrap();
 
-        assert_eq!(expenses.budget.transactions_total, 225.0);
-        assert_eq!(expenses.budget.allocations_total, 350.0);
+        assert_eq!(expenses.budget.transactions_total, Money::from(225));
+        assert_eq!(expenses.budget.allocations_total, Money::from(350));
     }
 
     #[tokio::test]
@@ -256,8 +268,8 @@ mod tests {
         .await
         .unwrap();
 
-        assert_eq!(expenses.budget.transactions_total, 225.0);
-        assert_eq!(expenses.budget.allocations_total, 350.0 * 3.0);
+        assert_eq!(expenses.budget.transactions_total, Money::from(225));
+        assert_eq!(expenses.budget.allocations_total, Money::from(350 * 3));
     }
 
     #[tokio::test]
@@ -276,7 +288,95 @@ mod tests {
         .await
         .unwrap();
 
-        assert_eq!(expenses.budget.transactions_total, 225.0);
-        assert_eq!(expenses.budget.allocations_total, 350.0 * 12.0);
+        assert_eq!(expenses.budget.transactions_total, Money::from(225));
+        assert_eq!(expenses.budget.allocations_total, Money::from(350 * 12));
+    }
+
+    #[tokio::test]
+    pub async fn test_get_expenses_child_categories() {
+        let (app, budget, account) = setup_expenses().await;
+
+        let now = chrono::Utc::now().with_day(1).unwrap();
+        let taxi: RecordId = ("category", "transport-taxi").into();
+        let read_expenses = || {
+            get_budget_expenses(
+                app.state(),
+                budget.id.clone(),
+                ReadExpensesOptions {
+                    period: ExpensesPeriod::Monthly,
+                    start_date: now,
+                },
+            )
+        };
+
+        add_transaction(
+            app.state(),
+            account.id.clone(),
+            AddTransactionOptions {
+                amount: Money::from(15),
+                category: Some(taxi.clone()),
+                description: "Taxi".to_string(),
+                tags: vec![],
+                splits: vec![],
+                date: Some(now.date_naive().and_hms_opt(1, 0, 0).unwrap().and_utc()),
+                external_id: None,
+                payee: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        // Spending in a child category goes to the allocation of its parent.
+        let expenses = read_expenses().await.unwrap();
+        let transport = &expenses.budget.partitions[0].allocations[0];
+
+        assert_eq!(expenses.budget.transactions_total, Money::from(240));
+        assert_eq!(transport.transactions_total, Money::from(240));
+        assert_eq!(transport.categories.len(), 2);
+        assert!(transport
+            .categories
+            .iter()
+            .any(|category| category.category.id == taxi
+                && category.transactions_total == Money::from(15)));
+
+        // Unless the child category has its own allocation.
+        let partition = create_budget_partition(
+            app.state(),
+            budget.id.clone(),
+            CreatePartitionOptions {
+                name: "Leisure".to_string(),
+                color: "red".to_string(),
+            },
+        )
+        .await
+        .expect("failed to create partition");
+
+        create_budget_allocation(
+            app.state(),
+            CreateAllocationOptions {
+                name: "Taxi".to_string(),
+                category: Some(taxi.clone()),
+                amount: Money::from(20),
+                partition: partition.id.clone(),
+            },
+        )
+        .await
+        .expect("failed to create allocations");
+
+        let expenses = read_expenses().await.unwrap();
+        let allocations = expenses
+            .budget
+            .partitions
+            .iter()
+            .flat_map(|partition| partition.allocations.iter());
+
+        assert_eq!(expenses.budget.transactions_total, Money::from(240));
+        for allocation in allocations {
+            if allocation.category.id == taxi {
+                assert_eq!(allocation.transactions_total, Money::from(15));
+            } else {
+                assert_eq!(allocation.transactions_total, Money::from(225));
+            }
+        }
     }
 }
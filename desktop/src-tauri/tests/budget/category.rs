@@ -5,7 +5,8 @@ mod tests {
     use tauri::Manager;
     use thunes_cli::{
         budget::{category::ReadCategoryOptions, ExpensesPeriod},
-        transaction::AddTransactionOptions,
+        money::Money,
+        transaction::{AddTransactionOptions, Split},
     };
     use thunes_lib::commands::transaction::{add_transaction, get_transactions_by_category};
 
@@ -23,11 +24,14 @@ mod tests {
             app.state(),
             account.id.clone(),
             AddTransactionOptions {
-                amount: 30.0,
+                amount: Money::from(30),
                 category: Some(category_food.clone()),
                 description: "Restaurant".to_string(),
                 tags: vec![],
+                splits: vec![],
                 date: Some(now.date_naive().and_hms_opt(1, 0, 0).unwrap().and_utc()),
+                external_id: None,
+                payee: None,
             },
         )
         .await
@@ -48,4 +52,87 @@ mod tests {
         assert_eq!(result.category, category_transport);
         assert_eq!(result.transactions.len(), 2);
     }
+
+    #[tokio::test]
+    pub async fn test_get_split_transactions_by_category() {
+        let (app, budget, account) = setup_expenses().await;
+
+        let now = chrono::Utc::now().with_day(1).unwrap();
+        let category_transport: RecordId = ("category", "transport").into();
+        let category_food: RecordId = ("category", "food").into();
+
+        add_transaction(
+            app.state(),
+            account.id.clone(),
+            AddTransactionOptions {
+                amount: Money::from(50),
+                category: None,
+                description: "Gas station".to_string(),
+                tags: vec![],
+                splits: vec![
+                    Split {
+                        category: category_transport.clone(),
+                        amount: Money::from(40),
+                        description: "Fuel".to_string(),
+                        tags: vec![],
+                    },
+                    Split {
+                        category: category_food.clone(),
+                        amount: Money::from(10),
+                        description: "Sandwich".to_string(),
+                        tags: vec![],
+                    },
+                ],
+                date: Some(now.date_naive().and_hms_opt(1, 0, 0).unwrap().and_utc()),
+                external_id: None,
+                payee: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let result = get_transactions_by_category(
+            app.state(),
+            budget.id,
+            ReadCategoryOptions {
+                period: ExpensesPeriod::Monthly,
+                category: category_food.clone(),
+                start_date: now,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.transactions.len(), 1);
+        assert_eq!(result.transactions[0].inner.amount, Money::from(10));
+        assert_eq!(result.transactions[0].inner.description, "Sandwich");
+    }
+
+    #[tokio::test]
+    pub async fn test_split_transaction_must_add_up() {
+        let (app, _, account) = setup_expenses().await;
+
+        let result = add_transaction(
+            app.state(),
+            account.id.clone(),
+            AddTransactionOptions {
+                amount: Money::from(50),
+                category: None,
+                description: "Gas station".to_string(),
+                tags: vec![],
+                splits: vec![Split {
+                    category: ("category", "transport").into(),
+                    amount: Money::from(40),
+                    description: "Fuel".to_string(),
+                    tags: vec![],
+                }],
+                date: None,
+                external_id: None,
+                payee: None,
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use surrealdb::{engine::local::Db, RecordId, Surreal};
+    use tauri::Manager;
+    use thunes_cli::{
+        account::{AccountKind, AddAccountOptions},
+        money::Money,
+        transaction::{
+            category::{CreateCategoryOptions, Icon},
+            AddTransactionOptions,
+        },
+    };
+    use thunes_lib::commands::{
+        account::add_account,
+        categories::{add_category, delete_category, get_categories, update_category},
+        transaction::{add_transaction, get_transactions},
+    };
+
+    fn options(id: &str, parent: Option<RecordId>) -> CreateCategoryOptions {
+        CreateCategoryOptions {
+            id: Some(id.to_string()),
+            name: "Pets".to_string(),
+            icon: Icon::EverydayLife,
+            color: "green".to_string(),
+            parent,
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_add_category() {
+        let app = crate::common::setup().await;
+
+        let category = add_category(
+            app.state(),
+            options("pets", Some(("category", "everyday-life").into())),
+        )
+        .await
+        .expect("failed to add category");
+
+        assert_eq!(category.id, ("category", "pets").into());
+        assert!(add_category(app.state(), options("pets", None))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    pub async fn test_update_category_cycle() {
+        let app = crate::common::setup().await;
+
+        let pets = add_category(app.state(), options("pets", None))
+            .await
+            .expect("failed to add category");
+        let mut food = add_category(app.state(), options("pets-food", Some(pets.id.clone())))
+            .await
+            .expect("failed to add category");
+
+        food.name = "Pet food".to_string();
+        update_category(app.state(), food.clone())
+            .await
+            .expect("failed to update category");
+
+        let mut pets = pets;
+        pets.parent = Some(food.id.clone());
+        assert!(update_category(app.state(), pets).await.is_err());
+
+        // A cycle already in the categories, like one written by hand, is rejected
+        // instead of being walked forever.
+        {
+            let database = app.state::<tokio::sync::Mutex<Surreal<Db>>>();
+            let database = database.lock().await;
+
+            database
+                .query("UPDATE category:pets SET parent = category:`pets-food`")
+                .await
+                .expect("failed to update category");
+        }
+
+        assert!(
+            add_category(app.state(), options("pets-toys", Some(food.id)))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_delete_category_reassigns_transactions() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "My Account".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let pets = add_category(app.state(), options("pets", None))
+            .await
+            .expect("failed to add category");
+
+        add_transaction(
+            app.state(),
+            account.id.clone(),
+            AddTransactionOptions {
+                amount: Money::from(-20),
+                category: Some(pets.id.clone()),
+                description: "Vet".to_string(),
+                tags: vec![],
+                splits: vec![],
+                date: None,
+                external_id: None,
+                payee: None,
+            },
+        )
+        .await
+        .expect("failed to add transaction");
+
+        let other: RecordId = ("category", "other").into();
+        assert!(
+            delete_category(app.state(), other.clone(), pets.id.clone(), None)
+                .await
+                .is_err()
+        );
+        // Unknown categories cannot be deleted.
+        assert!(delete_category(
+            app.state(),
+            ("category", "unknown").into(),
+            other.clone(),
+            None
+        )
+        .await
+        .is_err());
+
+        delete_category(app.state(), pets.id.clone(), other.clone(), None)
+            .await
+            .expect("failed to delete category");
+
+        let transactions = get_transactions(app.state(), account.id, None)
+            .await
+            .expect("failed to get transactions");
+
+        assert_eq!(transactions[0].category, other);
+        assert!(get_categories(app.state())
+            .await
+            .expect("failed to get categories")
+            .iter()
+            .all(|category| category.id != pets.id));
+    }
+}
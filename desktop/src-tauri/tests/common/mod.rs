@@ -30,6 +30,15 @@ pub async fn setup() -> tauri::App<tauri::test::MockRuntime> {
     result.expect("failed to create default categories");
 
     app.manage(tokio::sync::Mutex::new(db));
+    app.manage(thunes_lib::commands::attachments::AttachmentsDirectory(
+        std::env::temp_dir().join(format!(
+            "thunes-attachments-{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("failed to get time")
+                .as_nanos()
+        )),
+    ));
 
     app
 }
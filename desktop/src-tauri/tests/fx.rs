@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use tauri::Manager;
+    use thunes_cli::{
+        account::{AccountKind, AddAccountOptions},
+        fx::{ConversionOptions, CreateExchangeRateOptions},
+        money::Money,
+        transaction::AddTransactionOptions,
+    };
+    use thunes_lib::commands::{
+        account::add_account,
+        fx::{add_exchange_rate, get_net_worth, list_exchange_rates},
+        transaction::add_transaction,
+    };
+
+    fn date(year: i32, month: u32, day: u32) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc
+            .with_ymd_and_hms(year, month, day, 0, 0, 0)
+            .unwrap()
+    }
+
+    async fn setup() -> tauri::App<tauri::test::MockRuntime> {
+        let app = crate::common::setup().await;
+
+        for (currency, amount) in [("EUR", 100), ("USD", 50)] {
+            let account = add_account(
+                app.state(),
+                AddAccountOptions {
+                    currency: currency.to_string(),
+                    name: format!("My {currency} Account"),
+                    kind: AccountKind::Checking,
+                },
+            )
+            .await
+            .expect("failed to create account");
+
+            add_transaction(
+                app.state(),
+                account.id,
+                AddTransactionOptions {
+                    amount: Money::from(amount),
+                    category: None,
+                    description: "Salary".to_string(),
+                    tags: vec![],
+                    splits: vec![],
+                    date: Some(date(2026, 2, 1)),
+                    external_id: None,
+                    payee: None,
+                },
+            )
+            .await
+            .expect("failed to add transaction");
+        }
+
+        for (day, rate) in [(date(2026, 1, 1), "1.10"), (date(2026, 6, 1), "1.25")] {
+            add_exchange_rate(
+                app.state(),
+                CreateExchangeRateOptions {
+                    date: day,
+                    base: "EUR".to_string(),
+                    quote: "USD".to_string(),
+                    rate: rate.parse().unwrap(),
+                },
+            )
+            .await
+            .expect("failed to add exchange rate");
+        }
+
+        app
+    }
+
+    #[tokio::test]
+    pub async fn test_list_exchange_rates() {
+        let app = setup().await;
+
+        let rates = list_exchange_rates(app.state(), None)
+            .await
+            .expect("failed to list exchange rates");
+
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].date, date(2026, 6, 1));
+        assert_eq!(rates[0].rate, "1.25".parse().unwrap());
+    }
+
+    #[tokio::test]
+    pub async fn test_net_worth_uses_rate_of_the_date() {
+        let app = setup().await;
+
+        let net_worth = get_net_worth(
+            app.state(),
+            ConversionOptions {
+                currency: "USD".to_string(),
+                date: Some(date(2026, 3, 1)),
+            },
+        )
+        .await
+        .expect("failed to get net worth");
+
+        assert_eq!(net_worth.currencies.len(), 2);
+        assert_eq!(net_worth.total, Money::from(160));
+    }
+
+    #[tokio::test]
+    pub async fn test_net_worth_inverse_rate() {
+        let app = setup().await;
+
+        let net_worth = get_net_worth(
+            app.state(),
+            ConversionOptions {
+                currency: "EUR".to_string(),
+                date: Some(date(2026, 7, 1)),
+            },
+        )
+        .await
+        .expect("failed to get net worth");
+
+        assert_eq!(net_worth.total, Money::from(140));
+    }
+
+    #[tokio::test]
+    pub async fn test_net_worth_rounds_converted_balances() {
+        let app = setup().await;
+
+        // 50 USD at 1 / 1.10 is 45.4545... EUR, rounded to the cent.
+        let net_worth = get_net_worth(
+            app.state(),
+            ConversionOptions {
+                currency: "EUR".to_string(),
+                date: Some(date(2026, 3, 1)),
+            },
+        )
+        .await
+        .expect("failed to get net worth");
+        let usd = net_worth
+            .currencies
+            .iter()
+            .find(|currency| currency.currency == "USD")
+            .expect("missing USD balance");
+
+        assert_eq!(usd.accounts[0].converted_balance, "45.45".parse().unwrap());
+        assert_eq!(usd.converted_total_balance, "45.45".parse().unwrap());
+        assert_eq!(net_worth.total, "145.45".parse().unwrap());
+    }
+
+    #[tokio::test]
+    pub async fn test_net_worth_missing_rate() {
+        let app = setup().await;
+
+        assert!(get_net_worth(
+            app.state(),
+            ConversionOptions {
+                currency: "JPY".to_string(),
+                date: None,
+            },
+        )
+        .await
+        .is_err());
+    }
+}
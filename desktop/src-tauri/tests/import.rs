@@ -0,0 +1,986 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use surrealdb::{engine::local::Db, Surreal};
+    use tauri::Manager;
+    use thunes_cli::{
+        account::{AccountKind, AddAccountOptions},
+        import::{
+            csv::{AmountColumns, CreateCsvProfileOptions, CsvOptions},
+            qif::{QifCategoryMapping, QifOptions},
+            EntryDate,
+        },
+        money::Money,
+        transaction::{
+            transfer::ReadTransferOptions, AddTransactionOptions, AddTransactionTransferOptions,
+        },
+    };
+    use thunes_lib::commands::{
+        account::{add_account, update_account},
+        categories::delete_category,
+        import::{
+            add_csv_profile, delete_csv_profile, delete_qif_category_mapping, export_qif,
+            get_csv_profiles, get_qif_category_mappings, import_camt, import_mt940, import_ofx,
+            import_transactions, preview_csv, preview_qif, set_qif_category_mapping,
+            update_csv_profile,
+        },
+        transaction::{
+            add_transaction, add_transaction_transfer, get_duplicate_transactions,
+            get_transactions, get_transfers, merge_duplicate_transactions,
+        },
+    };
+
+    const STATEMENT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/samples/statement.csv");
+    const OFX_SGML: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/samples/statement.ofx");
+    const OFX_XML: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/samples/statement-v2.ofx"
+    );
+    const QIF: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/samples/statement.qif");
+    const CAMT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/samples/camt053.xml");
+    const MT940: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/samples/statement.sta");
+
+    fn options() -> CsvOptions {
+        CsvOptions {
+            delimiter: ";".to_string(),
+            encoding: "utf-8".to_string(),
+            date_format: "%d/%m/%Y".to_string(),
+            decimal_separator: ",".to_string(),
+            skip_rows: 1,
+            date: 0,
+            description: vec![1],
+            amount: AmountColumns::DebitCredit {
+                debit: 2,
+                credit: 3,
+            },
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_import_csv() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let rows = preview_csv(STATEMENT.to_string(), options())
+            .await
+            .expect("failed to preview csv");
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(
+            rows[0].transaction.as_ref().map(|t| t.description.as_str()),
+            Some("Cafe \"Le Zinc\"")
+        );
+        assert_eq!(
+            rows[1].transaction.as_ref().map(|t| t.amount),
+            Some("1234.56".parse::<Money>().unwrap())
+        );
+        assert_eq!(rows[2].line, 4);
+        assert!(rows[2].error.is_some());
+
+        // Only the rows without errors are imported.
+        let summary = import_transactions(
+            app.state(),
+            account.id.clone(),
+            rows.into_iter().filter_map(|row| row.transaction).collect(),
+        )
+        .await
+        .expect("failed to import transactions");
+
+        assert_eq!(summary.imported.len(), 3);
+
+        let mut amounts: Vec<Money> = get_transactions(app.state(), account.id, None)
+            .await
+            .expect("failed to get transactions")
+            .into_iter()
+            .map(|transaction| transaction.inner.amount)
+            .collect();
+        amounts.sort();
+
+        assert_eq!(
+            amounts,
+            vec![
+                Money::from(-800),
+                "-3.5".parse::<Money>().unwrap(),
+                "1234.56".parse::<Money>().unwrap()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_import_duplicates() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let rows = preview_csv(STATEMENT.to_string(), options())
+            .await
+            .expect("failed to preview csv");
+
+        import_transactions(
+            app.state(),
+            account.id.clone(),
+            rows.into_iter().filter_map(|row| row.transaction).collect(),
+        )
+        .await
+        .expect("failed to import transactions");
+
+        let transaction = |amount: &str, description: &str, day: u32| AddTransactionOptions {
+            amount: amount.parse().unwrap(),
+            description: description.to_string(),
+            date: chrono::NaiveDate::from_ymd_opt(2026, 2, day)
+                .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc()),
+            ..Default::default()
+        };
+
+        // An overlapping statement, written differently by the bank.
+        let summary = import_transactions(
+            app.state(),
+            account.id.clone(),
+            vec![
+                transaction("-800", "Loyer", 5),
+                transaction("1234.56", "SALAIRE FEVRIER", 3),
+                transaction("-3.5", "Cafe \"Le Zinc\"", 20),
+            ],
+        )
+        .await
+        .expect("failed to import transactions");
+
+        assert_eq!(summary.duplicates, 1);
+        assert_eq!(summary.imported.len(), 2);
+        assert_eq!(summary.possible_duplicates.len(), 1);
+        assert_eq!(
+            summary.possible_duplicates[0].existing.inner.description,
+            "Salaire"
+        );
+
+        let salary = summary.possible_duplicates[0].existing.id.clone();
+        let duplicate = summary.possible_duplicates[0].transaction.id.clone();
+        let groups = get_duplicate_transactions(app.state(), account.id.clone(), None)
+            .await
+            .expect("failed to get duplicate transactions");
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0]
+                .transactions
+                .iter()
+                .map(|transaction| transaction.id.clone())
+                .collect::<Vec<_>>(),
+            vec![salary.clone(), duplicate.clone()]
+        );
+
+        // The transaction to keep is reconciled, so it is only merged into when forced.
+        {
+            let database = app.state::<tokio::sync::Mutex<Surreal<Db>>>();
+            let database = database.lock().await;
+
+            database
+                .query("UPDATE $transaction SET status = 'Reconciled'")
+                .bind(("transaction", salary.clone()))
+                .await
+                .expect("failed to update transaction");
+        }
+
+        assert!(merge_duplicate_transactions(
+            app.state(),
+            salary.clone(),
+            vec![duplicate.clone()],
+            None
+        )
+        .await
+        .is_err());
+
+        let merged =
+            merge_duplicate_transactions(app.state(), salary.clone(), vec![duplicate], Some(true))
+                .await
+                .expect("failed to merge duplicate transactions");
+
+        assert_eq!(merged.id, salary);
+        assert_eq!(
+            get_transactions(app.state(), account.id.clone(), None)
+                .await
+                .expect("failed to get transactions")
+                .len(),
+            4
+        );
+        assert!(get_duplicate_transactions(app.state(), account.id, None)
+            .await
+            .expect("failed to get duplicate transactions")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn test_merge_duplicate_of_transfer() {
+        let app = crate::common::setup().await;
+        let mut accounts = vec![];
+
+        for name in ["Checking", "Savings"] {
+            accounts.push(
+                add_account(
+                    app.state(),
+                    AddAccountOptions {
+                        currency: "EUR".to_string(),
+                        name: name.to_string(),
+                        kind: AccountKind::Checking,
+                    },
+                )
+                .await
+                .expect("failed to create account"),
+            );
+        }
+
+        let transfer = add_transaction_transfer(
+            app.state(),
+            AddTransactionTransferOptions {
+                description: "Savings".to_string(),
+                amount: Money::from(100),
+                received: None,
+                fee: None,
+                date: None,
+                tags: vec![],
+                from: accounts[0].id.clone(),
+                to: accounts[1].id.clone(),
+            },
+        )
+        .await
+        .expect("failed to add transfer");
+        let imported = add_transaction(
+            app.state(),
+            accounts[0].id.clone(),
+            AddTransactionOptions {
+                amount: Money::from(-100),
+                description: "TRANSFER TO SAVINGS".to_string(),
+                date: Some(transfer.inner.date),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("failed to add transaction");
+
+        merge_duplicate_transactions(app.state(), imported.id, vec![transfer.id], None)
+            .await
+            .expect("failed to merge duplicate transactions");
+
+        // The other side of the transfer is kept as a regular transaction.
+        let savings = get_transactions(app.state(), accounts[1].id.clone(), None)
+            .await
+            .expect("failed to get transactions");
+
+        assert_eq!(savings.len(), 1);
+        assert_eq!(savings[0].transfer, None);
+        assert!(get_transfers(
+            app.state(),
+            ReadTransferOptions {
+                account: accounts[1].id.clone(),
+                other_account: None,
+            }
+        )
+        .await
+        .expect("failed to get transfers")
+        .is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn test_preview_csv_thousands_separators() {
+        let path = std::env::temp_dir()
+            .join(format!("thunes-{}.csv", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        std::fs::write(
+            &path,
+            "Date;Libelle;Debit;Credit\n01/02/2026;Grouped;1.234,50;\n02/02/2026;Wrong separator;12.50;\n03/02/2026;Misplaced group;1.23,00;\n",
+        )
+        .expect("failed to write file");
+
+        let rows = preview_csv(path.clone(), options())
+            .await
+            .expect("failed to preview csv");
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0].transaction.as_ref().map(|t| t.amount),
+            Some("-1234.5".parse::<Money>().unwrap())
+        );
+        assert!(rows[1].error.is_some());
+        assert!(rows[2].error.is_some());
+    }
+
+    #[tokio::test]
+    pub async fn test_csv_profiles() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let mut profile = add_csv_profile(
+            app.state(),
+            CreateCsvProfileOptions {
+                account: account.id.clone(),
+                name: "Bank".to_string(),
+                options: options(),
+            },
+        )
+        .await
+        .expect("failed to add csv profile");
+
+        assert_eq!(profile.options, options());
+
+        profile.options.skip_rows = 2;
+        update_csv_profile(app.state(), profile.clone())
+            .await
+            .expect("failed to update csv profile");
+
+        let profiles = get_csv_profiles(app.state(), account.id.clone())
+            .await
+            .expect("failed to get csv profiles");
+
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].options.skip_rows, 2);
+
+        delete_csv_profile(app.state(), profile.id)
+            .await
+            .expect("failed to delete csv profile");
+
+        assert!(get_csv_profiles(app.state(), account.id)
+            .await
+            .expect("failed to get csv profiles")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn test_import_ofx() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let summary = import_ofx(app.state(), OFX_SGML.to_string(), account.id.clone())
+            .await
+            .expect("failed to import ofx file");
+
+        assert_eq!(summary.imported.len(), 3);
+        assert_eq!(summary.imported[0].inner.description, "SALARY ACME & CO");
+        assert_eq!(summary.imported[1].inner.description, "Café de la Gare");
+        assert_eq!(
+            summary.imported[1].external_id.as_deref(),
+            Some("2026020501")
+        );
+        assert_eq!(summary.balances.len(), 1);
+        assert!(summary.balances[0].difference.is_zero());
+
+        // Importing the same file again does not create duplicates.
+        let summary = import_ofx(app.state(), OFX_SGML.to_string(), account.id.clone())
+            .await
+            .expect("failed to import ofx file");
+
+        assert!(summary.imported.is_empty());
+        assert_eq!(summary.duplicates, 3);
+        assert!(summary.balances[0].difference.is_zero());
+        assert_eq!(
+            get_transactions(app.state(), account.id, None)
+                .await
+                .expect("failed to get transactions")
+                .len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_import_ofx_xml() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Credit card".to_string(),
+                kind: AccountKind::CreditCard,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let summary = import_ofx(app.state(), OFX_XML.to_string(), account.id)
+            .await
+            .expect("failed to import ofx file");
+
+        assert_eq!(summary.imported.len(), 2);
+        assert_eq!(summary.errors.len(), 1);
+        assert_eq!(summary.balances[0].expected, Money::from(-100));
+        assert!(summary.balances[0].difference.is_zero());
+    }
+
+    #[tokio::test]
+    pub async fn test_import_ofx_separators_and_offsets() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let statement = |transactions: &str| {
+            format!("<OFX><BANKMSGSRSV1><STMTTRNRS><STMTRS><BANKTRANLIST>{transactions}</BANKTRANLIST></STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>")
+        };
+        let path = std::env::temp_dir()
+            .join(format!("thunes-{}.ofx", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+
+        std::fs::write(
+            &path,
+            statement(concat!(
+                "<STMTTRN><DTPOSTED>20260205230000.000[-5:EST]<TRNAMT>1.234,56<FITID>1<NAME>A</STMTTRN>",
+                "<STMTTRN><DTPOSTED>20260206013000[+2:EET]<TRNAMT>-1,234.56<FITID>2<NAME>B</STMTTRN>",
+            )),
+        )
+        .expect("failed to write file");
+
+        let summary = import_ofx(app.state(), path.clone(), account.id.clone())
+            .await
+            .expect("failed to import ofx file");
+
+        // Entries keep the day of the statement, whatever their offset to UTC, like a
+        // late evening entry of a negative offset.
+        assert_eq!(summary.imported.len(), 2);
+        assert_eq!(
+            summary.imported[0].inner.amount,
+            "1234.56".parse::<Money>().unwrap()
+        );
+        assert_eq!(
+            summary.imported[0].inner.date.date_naive(),
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 5).unwrap()
+        );
+        assert_eq!(
+            summary.imported[1].inner.amount,
+            "-1234.56".parse::<Money>().unwrap()
+        );
+        assert_eq!(
+            summary.imported[1].inner.date.date_naive(),
+            chrono::NaiveDate::from_ymd_opt(2026, 2, 6).unwrap()
+        );
+
+        // Files with several statements are rejected.
+        std::fs::write(&path, statement("").repeat(2)).expect("failed to write file");
+
+        assert!(import_ofx(app.state(), path.clone(), account.id)
+            .await
+            .is_err());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    pub async fn test_import_camt() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let summary = import_camt(app.state(), CAMT.to_string(), account.id.clone(), None)
+            .await
+            .expect("failed to import camt.053 file");
+
+        // Entries of both statements are imported, except the one without dates.
+        assert_eq!(summary.imported.len(), 4);
+        assert_eq!(summary.errors.len(), 1);
+        assert_eq!(
+            summary.imported[0].inner.description,
+            "ACME Corp Salary January"
+        );
+        assert_eq!(
+            summary.imported[0].inner.date.date_naive().to_string(),
+            "2026-01-02"
+        );
+        // Entry references are only unique with the date and amount of the entry, and
+        // the references of the account servicer are used as they are.
+        assert_eq!(
+            summary.imported[0].external_id.as_deref(),
+            Some("2026-01-02/2500.00/E-0001")
+        );
+        assert_eq!(
+            summary.imported[1].inner.description,
+            "Landlord Rent January Flat 3B"
+        );
+        assert_eq!(summary.imported[1].external_id.as_deref(), Some("E-0002"));
+        assert_eq!(
+            summary.imported[2].inner.description,
+            "Grocery & Co Card payment"
+        );
+        // The reversal of a debit is a credit.
+        assert_eq!(summary.imported[3].inner.amount, Money::from(50));
+
+        // Opening and closing balances of both statements, the last one missing the
+        // entry that could not be imported.
+        assert_eq!(summary.balances.len(), 4);
+        assert!(summary.balances[..3]
+            .iter()
+            .all(|balance| balance.difference.is_zero()));
+        assert_eq!(summary.balances[3].difference, Money::from(10));
+
+        let summary = import_camt(app.state(), CAMT.to_string(), account.id.clone(), None)
+            .await
+            .expect("failed to import camt.053 file");
+
+        assert!(summary.imported.is_empty());
+        assert_eq!(summary.duplicates, 4);
+    }
+
+    #[tokio::test]
+    pub async fn test_import_mt940() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let summary = import_mt940(
+            app.state(),
+            MT940.to_string(),
+            account.id.clone(),
+            Some(EntryDate::Value),
+        )
+        .await
+        .expect("failed to import mt940 file");
+
+        assert_eq!(summary.imported.len(), 4);
+        assert_eq!(summary.errors.len(), 1);
+        assert_eq!(
+            summary.imported[0].inner.description,
+            "ACME Corp Salary January"
+        );
+        assert_eq!(
+            summary.imported[0].inner.date.date_naive().to_string(),
+            "2026-01-01"
+        );
+        assert_eq!(summary.imported[0].external_id.as_deref(), Some("B0001"));
+        assert_eq!(
+            summary.imported[1].inner.description,
+            "Landlord Rent January Flat 3B"
+        );
+        assert_eq!(summary.imported[1].inner.amount, Money::from(-800));
+        assert_eq!(summary.imported[3].inner.amount, Money::from(50));
+
+        assert_eq!(summary.balances.len(), 4);
+        assert!(summary.balances[..3]
+            .iter()
+            .all(|balance| balance.difference.is_zero()));
+        assert_eq!(summary.balances[3].difference, Money::from(10));
+
+        let summary = import_mt940(app.state(), MT940.to_string(), account.id.clone(), None)
+            .await
+            .expect("failed to import mt940 file");
+
+        assert!(summary.imported.is_empty());
+        assert_eq!(summary.duplicates, 4);
+    }
+
+    #[tokio::test]
+    pub async fn test_import_camt_declared_encoding() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let path = std::env::temp_dir()
+            .join(format!("thunes-{}.xml", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let content = [
+            br#"<?xml version="1.0" encoding="ISO-8859-1"?><Document><BkToCstmrStmt><Stmt>"#
+                .as_slice(),
+            br#"<Ntry><NtryRef>E-1</NtryRef><Amt Ccy="EUR">4.20</Amt><CdtDbtInd>DBIT</CdtDbtInd>"#,
+            b"<BookgDt><Dt>2026-01-02</Dt></BookgDt><AddtlNtryInf>Caf\xe9 de la Gare",
+            b"</AddtlNtryInf></Ntry></Stmt></BkToCstmrStmt></Document>",
+        ]
+        .concat();
+        std::fs::write(&path, content).expect("failed to write file");
+
+        let summary = import_camt(app.state(), path.clone(), account.id, None)
+            .await
+            .expect("failed to import camt.053 file");
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(summary.imported.len(), 1);
+        assert_eq!(summary.imported[0].inner.description, "Café de la Gare");
+    }
+
+    #[tokio::test]
+    pub async fn test_import_mt940_customer_references() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let path = std::env::temp_dir()
+            .join(format!("thunes-{}.sta", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        std::fs::write(
+            &path,
+            concat!(
+                ":20:STMT0001\n:25:10020030/1234567\n:28C:1/1\n:60F:C260131EUR0,00\n",
+                ":61:2602020202CR100,00NTRFINV42\n:86:First instalment\n",
+                ":61:2602090209CR100,00NTRFINV42\n:86:Second instalment\n",
+                ":62F:C260228EUR200,00\n-\n",
+            ),
+        )
+        .expect("failed to write file");
+
+        let summary = import_mt940(app.state(), path.clone(), account.id, None)
+            .await
+            .expect("failed to import mt940 file");
+        let _ = std::fs::remove_file(path);
+
+        // A customer reference used twice does not make the second entry a duplicate.
+        assert_eq!(summary.imported.len(), 2);
+        assert_ne!(
+            summary.imported[0].external_id,
+            summary.imported[1].external_id
+        );
+        assert_ne!(summary.imported[0].external_id.as_deref(), Some("INV42"));
+    }
+
+    #[tokio::test]
+    pub async fn test_import_statements_of_different_accounts() {
+        let app = crate::common::setup().await;
+        let mut account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let camt = |iban: &str| {
+            format!(
+                concat!(
+                    "<Stmt><Acct><Id><IBAN>{}</IBAN></Id></Acct><Ntry>",
+                    "<AcctSvcrRef>S-0001</AcctSvcrRef><Amt Ccy=\"EUR\">4.20</Amt>",
+                    "<CdtDbtInd>DBIT</CdtDbtInd><BookgDt><Dt>2026-01-02</Dt></BookgDt>",
+                    "</Ntry></Stmt>",
+                ),
+                iban
+            )
+        };
+        let mt940 = |account: &str| {
+            format!(
+                concat!(
+                    ":20:STMT\n:25:{}\n:28C:1/1\n:60F:C260131EUR0,00\n",
+                    ":61:2602020202CR100,00NTRFNONREF//B1\n:86:Transfer\n",
+                    ":62F:C260228EUR100,00\n-\n",
+                ),
+                account
+            )
+        };
+        let path = std::env::temp_dir()
+            .join(format!("thunes-accounts-{}", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+
+        // Statements of two accounts in the same file are rejected.
+        std::fs::write(
+            &path,
+            format!(
+                "<Document><BkToCstmrStmt>{}{}</BkToCstmrStmt></Document>",
+                camt("FR7630004000031234567890143"),
+                camt("DE89370400440532013000")
+            ),
+        )
+        .expect("failed to write file");
+
+        assert!(
+            import_camt(app.state(), path.clone(), account.id.clone(), None)
+                .await
+                .is_err()
+        );
+
+        std::fs::write(
+            &path,
+            mt940("10020030/1234567") + &mt940("10020030/7654321"),
+        )
+        .expect("failed to write file");
+
+        assert!(
+            import_mt940(app.state(), path.clone(), account.id.clone(), None)
+                .await
+                .is_err()
+        );
+
+        // Statements of another account than the number of the account are rejected.
+        account.data.number = Some("FR76 3000 4000 0312 3456 7890 143".to_string());
+        update_account(app.state(), account.clone())
+            .await
+            .expect("failed to update account");
+
+        std::fs::write(
+            &path,
+            format!(
+                "<Document><BkToCstmrStmt>{}</BkToCstmrStmt></Document>",
+                camt("DE89370400440532013000")
+            ),
+        )
+        .expect("failed to write file");
+
+        assert!(
+            import_camt(app.state(), path.clone(), account.id.clone(), None)
+                .await
+                .is_err()
+        );
+
+        std::fs::write(
+            &path,
+            format!(
+                "<Document><BkToCstmrStmt>{}{}</BkToCstmrStmt></Document>",
+                camt("FR7630004000031234567890143"),
+                camt("FR76 3000 4000 0312 3456 7890 143")
+            ),
+        )
+        .expect("failed to write file");
+
+        let summary = import_camt(app.state(), path.clone(), account.id.clone(), None)
+            .await
+            .expect("failed to import camt.053 file");
+        let _ = std::fs::remove_file(path);
+
+        // Entries with the same servicer reference are duplicates.
+        assert_eq!(summary.imported.len(), 1);
+        assert_eq!(summary.duplicates, 1);
+    }
+
+    #[tokio::test]
+    pub async fn test_import_export_qif() {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let preview = preview_qif(app.state(), QIF.to_string(), QifOptions::default())
+            .await
+            .expect("failed to preview qif file");
+
+        assert_eq!(preview.unmapped, vec!["Rent", "Household", "Groceries"]);
+
+        for (name, category) in [
+            ("Rent", "accommodation-rent"),
+            ("groceries", "everyday-life-food"),
+        ] {
+            set_qif_category_mapping(
+                app.state(),
+                QifCategoryMapping {
+                    name: name.to_string(),
+                    category: ("category", category).into(),
+                },
+            )
+            .await
+            .expect("failed to set qif category mapping");
+        }
+
+        let preview = preview_qif(app.state(), QIF.to_string(), QifOptions::default())
+            .await
+            .expect("failed to preview qif file");
+
+        assert_eq!(preview.unmapped, vec!["Household"]);
+        assert_eq!(preview.rows.len(), 4);
+        assert!(preview.rows[3].error.is_some());
+
+        let rent = preview.rows[0].transaction.clone().unwrap();
+        let groceries = preview.rows[1].transaction.clone().unwrap();
+        let transfer = preview.rows[2].transaction.clone().unwrap();
+
+        assert_eq!(rent.description, "Landlord January rent");
+        assert_eq!(
+            rent.category,
+            Some(("category", "accommodation-rent").into())
+        );
+        assert_eq!(groceries.splits.len(), 2);
+        assert_eq!(
+            groceries.splits[0].category,
+            ("category", "everyday-life-food").into()
+        );
+        assert_eq!(groceries.splits[1].category, ("category", "other").into());
+        assert_eq!(
+            transfer.category,
+            Some(("category", "internal-movements").into())
+        );
+
+        import_transactions(
+            app.state(),
+            account.id.clone(),
+            preview
+                .rows
+                .into_iter()
+                .filter_map(|row| row.transaction)
+                .collect(),
+        )
+        .await
+        .expect("failed to import transactions");
+
+        let savings = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Savings".to_string(),
+                kind: AccountKind::Savings,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        add_transaction_transfer(
+            app.state(),
+            AddTransactionTransferOptions {
+                description: "Savings".to_string(),
+                amount: Money::from(100),
+                received: None,
+                fee: None,
+                date: None,
+                tags: vec![],
+                from: account.id.clone(),
+                to: savings.id,
+            },
+        )
+        .await
+        .expect("failed to add transfer");
+
+        // Exported files can be imported back.
+        let path = std::env::temp_dir()
+            .join(format!("thunes-{}.qif", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+
+        export_qif(app.state(), account.id, path.clone(), QifOptions::default())
+            .await
+            .expect("failed to export qif file");
+
+        let content = std::fs::read_to_string(&path).expect("failed to read file");
+        let exported = preview_qif(app.state(), path.clone(), QifOptions::default())
+            .await
+            .expect("failed to preview qif file");
+        let _ = std::fs::remove_file(path);
+
+        assert!(content.contains("MLandlord January rent\n"));
+        assert!(content.contains("L[Savings]\n"));
+        assert!(exported.unmapped.is_empty());
+        assert_eq!(exported.rows.len(), 4);
+        assert_eq!(
+            exported.rows[0].transaction.as_ref().unwrap().description,
+            "Landlord January rent"
+        );
+        assert_eq!(
+            exported.rows[0].transaction.as_ref().unwrap().category,
+            Some(("category", "accommodation-rent").into())
+        );
+        assert_eq!(
+            exported.rows[1].transaction.as_ref().unwrap().splits.len(),
+            2
+        );
+        assert_eq!(
+            exported.rows[3].transaction.as_ref().unwrap().category,
+            Some(("category", "internal-movements").into())
+        );
+
+        // Mappings of deleted categories move to the replacement.
+        delete_category(
+            app.state(),
+            ("category", "accommodation-rent").into(),
+            ("category", "accommodation").into(),
+            None,
+        )
+        .await
+        .expect("failed to delete category");
+
+        let mappings = get_qif_category_mappings(app.state())
+            .await
+            .expect("failed to get qif category mappings");
+
+        assert_eq!(mappings[0].name, "Rent");
+        assert_eq!(mappings[0].category, ("category", "accommodation").into());
+
+        delete_qif_category_mapping(app.state(), "Rent".to_string())
+            .await
+            .expect("failed to delete qif category mapping");
+
+        assert_eq!(
+            get_qif_category_mappings(app.state())
+                .await
+                .expect("failed to get qif category mappings")
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use surrealdb::{engine::local::Db, Surreal};
+    use thunes_cli::{
+        account::{AccountKind, BalanceOptions},
+        migrations::{
+            m1_account_filter::AccountFilterMigration, m2_decimal_amounts::DecimalAmountsMigration,
+            m3_account_metadata::AccountMetadataMigration,
+            m4_opening_balance::OpeningBalanceMigration,
+            m5_transaction_sort::TransactionSortMigration,
+            m6_transaction_filters::TransactionFiltersMigration, run_migrations, Migration,
+        },
+        money::Money,
+    };
+
+    fn migrations() -> Vec<Box<dyn Migration + Send>> {
+        vec![
+            Box::new(AccountFilterMigration {}),
+            Box::new(DecimalAmountsMigration {}),
+            Box::new(AccountMetadataMigration {}),
+            Box::new(OpeningBalanceMigration {}),
+            Box::new(TransactionSortMigration {}),
+            Box::new(TransactionFiltersMigration {}),
+        ]
+    }
+
+    async fn setup() -> Surreal<Db> {
+        let db: Surreal<Db> = Surreal::init();
+        db.connect::<surrealdb::engine::local::Mem>(())
+            .await
+            .expect("failed to initialize database");
+        db.use_ns("user")
+            .use_db("accounts")
+            .await
+            .expect("failed to switch namespace and database");
+
+        db
+    }
+
+    #[tokio::test]
+    pub async fn test_migrations() {
+        let db = setup().await;
+
+        // Records written by the first releases, with float amounts and without the
+        // fields added since.
+        db.query(
+            r#"
+            CREATE account:checking SET name = "Checking", currency = "EUR",
+                transaction_grid_sort_model = [];
+            CREATE account:savings SET name = "Savings", currency = "EUR";
+            CREATE transaction SET account = account:checking, amount = 0.1f,
+                description = "Coffee", date = d"2024-01-01T00:00:00Z",
+                category = category:food, tags = [];
+            CREATE transaction SET account = account:checking, amount = 0.2f,
+                description = "Tea", date = d"2024-01-02T00:00:00Z",
+                category = category:food, tags = [];
+            CREATE budget:home SET income = 1234.56f;
+            CREATE allocation:rent SET amount = 0.7f;
+            "#,
+        )
+        .await
+        .expect("failed to seed database")
+        .check()
+        .expect("failed to seed database");
+
+        // Account filters with a single category were saved after the first migration.
+        run_migrations(vec![Box::new(AccountFilterMigration {})], &db)
+            .await
+            .expect("failed to run migrations");
+        db.query("UPDATE account:checking SET filter.category = 'food'")
+            .await
+            .expect("failed to update account filter")
+            .check()
+            .expect("failed to update account filter");
+
+        run_migrations(migrations(), &db)
+            .await
+            .expect("failed to run migrations");
+        // Applied migrations are skipped.
+        run_migrations(migrations(), &db)
+            .await
+            .expect("failed to run migrations");
+
+        // Float amounts are converted to exact decimals.
+        let balance = thunes_cli::account::balance(
+            &db,
+            ("account", "checking").into(),
+            BalanceOptions::default(),
+        )
+        .await
+        .expect("failed to get balance");
+
+        assert_eq!(balance, "0.3".parse::<Money>().unwrap());
+
+        let converted: Vec<bool> = db
+            .query(
+                r#"RETURN [
+                    type::is::decimal(budget:home.income) AND budget:home.income = 1234.56dec,
+                    type::is::decimal(allocation:rent.amount) AND allocation:rent.amount = 0.7dec,
+                    math::sum((SELECT VALUE amount FROM transaction)) = 0.3dec,
+                ]"#,
+            )
+            .await
+            .expect("failed to check amounts")
+            .take(0)
+            .expect("failed to check amounts");
+
+        assert_eq!(converted, vec![true, true, true]);
+
+        // Accounts get their metadata, positioned by name, and the list of categories
+        // of their filter.
+        let checking = thunes_cli::account::read(&db, ("account", "checking").into())
+            .await
+            .expect("failed to read account");
+        let savings = thunes_cli::account::read(&db, ("account", "savings").into())
+            .await
+            .expect("failed to read account");
+
+        assert_eq!(checking.data.kind, AccountKind::Checking);
+        assert!(!checking.data.archived);
+        assert_eq!(checking.data.position, 0);
+        assert_eq!(savings.data.position, 1);
+        assert!(checking.data.opening_balance.is_zero());
+        assert_eq!(
+            checking.filter.categories,
+            vec![("category", "food").into()]
+        );
+        assert!(savings.filter.categories.is_empty());
+    }
+}
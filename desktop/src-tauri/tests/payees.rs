@@ -0,0 +1,325 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use tauri::Manager;
+    use thunes_cli::{
+        account::{Account, AccountKind, AddAccountOptions},
+        money::Money,
+        transaction::{
+            payee::{CreatePayeeOptions, PayeeSpendingOptions},
+            AddTransactionOptions,
+        },
+    };
+    use thunes_lib::commands::{
+        account::add_account,
+        categories::delete_category,
+        import::import_transactions,
+        payees::{
+            add_payee, assign_payees, delete_payee, get_payee_spending, get_payees, update_payee,
+        },
+        transaction::{add_transaction, get_transactions},
+    };
+
+    async fn setup() -> (tauri::App<tauri::test::MockRuntime>, Account) {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "My Account".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        (app, account)
+    }
+
+    fn date(day: u32) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc
+            .with_ymd_and_hms(2026, 3, day, 0, 0, 0)
+            .single()
+            .unwrap()
+    }
+
+    fn transaction(amount: i64, description: &str, day: u32) -> AddTransactionOptions {
+        AddTransactionOptions {
+            amount: Money::from(amount),
+            description: description.to_string(),
+            date: Some(date(day)),
+            ..Default::default()
+        }
+    }
+
+    fn payee(name: &str) -> CreatePayeeOptions {
+        CreatePayeeOptions {
+            name: name.to_string(),
+            aliases: vec![],
+            patterns: vec![],
+            category: None,
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_payees_crud() {
+        let (app, _) = setup().await;
+
+        let error = add_payee(
+            app.state(),
+            CreatePayeeOptions {
+                patterns: vec!["(".to_string()],
+                ..payee("Invalid")
+            },
+        )
+        .await;
+
+        assert!(error.is_err());
+
+        add_payee(app.state(), payee("Monoprix"))
+            .await
+            .expect("failed to add payee");
+        let mut carrefour = add_payee(app.state(), payee("Carrefour"))
+            .await
+            .expect("failed to add payee");
+
+        carrefour.aliases.push("carrefour market".to_string());
+
+        update_payee(app.state(), carrefour.clone())
+            .await
+            .expect("failed to update payee");
+
+        let payees = get_payees(app.state()).await.expect("failed to get payees");
+
+        // Payees are sorted by name.
+        assert_eq!(
+            payees
+                .iter()
+                .map(|payee| payee.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Carrefour", "Monoprix"]
+        );
+        assert_eq!(payees[0].aliases, vec!["carrefour market".to_string()]);
+
+        delete_payee(app.state(), carrefour.id, None)
+            .await
+            .expect("failed to delete payee");
+
+        assert_eq!(
+            get_payees(app.state())
+                .await
+                .expect("failed to get payees")
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_payee_spending_by_currency() {
+        let (app, account) = setup().await;
+        let yen = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "JPY".to_string(),
+                name: "Tokyo".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        add_payee(app.state(), payee("Uniqlo"))
+            .await
+            .expect("failed to add payee");
+
+        for (account, amount) in [(&account, -30), (&yen, -4990), (&yen, -1001)] {
+            add_transaction(
+                app.state(),
+                account.id.clone(),
+                transaction(amount, "UNIQLO", 1),
+            )
+            .await
+            .expect("failed to add transaction");
+        }
+
+        let spending = get_payee_spending(app.state(), PayeeSpendingOptions::default())
+            .await
+            .expect("failed to get payee spending");
+
+        assert_eq!(
+            spending
+                .iter()
+                .map(|payee| (payee.currency.as_str(), payee.total, payee.count))
+                .collect::<Vec<_>>(),
+            vec![("EUR", Money::from(-30), 1), ("JPY", Money::from(-5991), 2)]
+        );
+        // Averages are rounded to the minor unit of the currency.
+        assert_eq!(spending[1].average, Money::from(-2996));
+    }
+
+    #[tokio::test]
+    pub async fn test_payee_category_follows_deleted_category() {
+        let (app, _) = setup().await;
+
+        add_payee(
+            app.state(),
+            CreatePayeeOptions {
+                category: Some(("category", "everyday-life-food").into()),
+                ..payee("Carrefour")
+            },
+        )
+        .await
+        .expect("failed to add payee");
+
+        delete_category(
+            app.state(),
+            ("category", "everyday-life-food").into(),
+            ("category", "everyday-life").into(),
+            None,
+        )
+        .await
+        .expect("failed to delete category");
+
+        assert_eq!(
+            get_payees(app.state()).await.expect("failed to get payees")[0].category,
+            Some(("category", "everyday-life").into())
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_payees_matching_and_spending() {
+        let (app, account) = setup().await;
+
+        // Added before the payees, so it only gets one once they are assigned.
+        add_transaction(
+            app.state(),
+            account.id.clone(),
+            transaction(-5, "CB CARREFOUR CITY 0103", 1),
+        )
+        .await
+        .expect("failed to add transaction");
+
+        let carrefour = add_payee(
+            app.state(),
+            CreatePayeeOptions {
+                category: Some(("category", "everyday-life-food").into()),
+                ..payee("Carrefour")
+            },
+        )
+        .await
+        .expect("failed to add payee");
+        let market = add_payee(
+            app.state(),
+            CreatePayeeOptions {
+                aliases: vec!["carrefour mkt".to_string()],
+                ..payee("Carrefour Market")
+            },
+        )
+        .await
+        .expect("failed to add payee");
+        let fuel = add_payee(
+            app.state(),
+            CreatePayeeOptions {
+                patterns: vec![r"\bTOTAL\b".to_string()],
+                category: Some(("category", "car-fuel").into()),
+                ..payee("TotalEnergies")
+            },
+        )
+        .await
+        .expect("failed to add payee");
+
+        let groceries = add_transaction(
+            app.state(),
+            account.id.clone(),
+            transaction(-10, "CB CARREFOUR 1234 PARIS", 2),
+        )
+        .await
+        .expect("failed to add transaction");
+
+        assert_eq!(groceries.payee, Some(carrefour.id.clone()));
+        assert_eq!(
+            groceries.category,
+            ("category", "everyday-life-food").into()
+        );
+
+        let summary = import_transactions(
+            app.state(),
+            account.id.clone(),
+            vec![
+                transaction(-30, "CARREFOUR MARKET", 5),
+                transaction(-20, "Carrefour-Mkt 22", 9),
+                transaction(-50, "TOTAL STATION 12", 10),
+                transaction(-1, "Unknown", 10),
+            ],
+        )
+        .await
+        .expect("failed to import transactions");
+
+        // The longest alias wins over the name of another payee.
+        assert_eq!(summary.imported[0].payee, Some(market.id.clone()));
+        assert_eq!(summary.imported[1].payee, Some(market.id.clone()));
+        assert_eq!(summary.imported[2].payee, Some(fuel.id.clone()));
+        assert_eq!(
+            summary.imported[2].category,
+            ("category", "car-fuel").into()
+        );
+        assert_eq!(summary.imported[3].payee, None);
+
+        assert_eq!(
+            assign_payees(app.state())
+                .await
+                .expect("failed to assign payees"),
+            1
+        );
+
+        let spending = get_payee_spending(app.state(), PayeeSpendingOptions::default())
+            .await
+            .expect("failed to get payee spending");
+
+        assert_eq!(
+            spending
+                .iter()
+                .map(|payee| (payee.name.as_str(), payee.total, payee.count))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Carrefour", Money::from(-15), 2),
+                ("Carrefour Market", Money::from(-50), 2),
+                ("TotalEnergies", Money::from(-50), 1),
+            ]
+        );
+        assert_eq!(spending[0].average, "-7.5".parse().unwrap());
+        assert_eq!(spending[0].first, date(1));
+        assert_eq!(spending[0].last, date(2));
+
+        let spending = get_payee_spending(
+            app.state(),
+            PayeeSpendingOptions {
+                payee: Some(market.id.clone()),
+                start: Some(date(6)),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("failed to get payee spending");
+
+        assert_eq!(spending.len(), 1);
+        assert_eq!(spending[0].total, Money::from(-20));
+
+        // Transactions are kept without payee when their payee is deleted.
+        delete_payee(app.state(), carrefour.id.clone(), None)
+            .await
+            .expect("failed to delete payee");
+
+        let transactions = get_transactions(app.state(), account.id, None)
+            .await
+            .expect("failed to get transactions");
+
+        assert_eq!(transactions.len(), 6);
+        assert!(transactions
+            .iter()
+            .all(|transaction| transaction.payee.as_ref() != Some(&carrefour.id)));
+    }
+}
@@ -0,0 +1,297 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use tauri::Manager;
+    use thunes_cli::{
+        account::{
+            reconciliation::StartReconciliationOptions, Account, AccountKind, AddAccountOptions,
+        },
+        money::Money,
+        transaction::{
+            category::{CreateCategoryOptions, Icon},
+            payee::CreatePayeeOptions,
+            tag::Tag,
+            AddTransactionOptions, Status, TransactionWithId,
+        },
+    };
+    use thunes_lib::commands::{
+        account::add_account,
+        categories::{add_category, delete_category},
+        payees::{add_payee, assign_payees},
+        reconciliation::{
+            cancel_reconciliation, finish_reconciliation, start_reconciliation, toggle_cleared,
+        },
+        tags::{add_tags, delete_tag, recolor_tag, rename_tag},
+        transaction::{add_transaction, delete_transaction, get_transactions, update_transaction},
+    };
+
+    async fn setup() -> (
+        tauri::App<tauri::test::MockRuntime>,
+        Account,
+        Vec<TransactionWithId>,
+    ) {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "Checking".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+        let mut transactions = vec![];
+
+        for (amount, month, day) in [(100, 1, 1), (-30, 1, 15), (-5, 2, 2)] {
+            transactions.push(
+                add_transaction(
+                    app.state(),
+                    account.id.clone(),
+                    AddTransactionOptions {
+                        amount: Money::from(amount),
+                        category: None,
+                        description: String::new(),
+                        tags: vec![],
+                        date: Some(
+                            chrono::Utc
+                                .with_ymd_and_hms(2026, month, day, 12, 0, 0)
+                                .unwrap(),
+                        ),
+                        splits: vec![],
+                        external_id: None,
+                        payee: None,
+                    },
+                )
+                .await
+                .expect("failed to add transaction"),
+            );
+        }
+
+        (app, account, transactions)
+    }
+
+    fn january(account: &Account, balance: i64) -> StartReconciliationOptions {
+        StartReconciliationOptions {
+            account: account.id.clone(),
+            statement_date: chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap(),
+            statement_balance: Money::from(balance),
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_reconciliation() {
+        let (app, account, transactions) = setup().await;
+
+        let summary = start_reconciliation(app.state(), january(&account, 70))
+            .await
+            .expect("failed to start reconciliation");
+        let reconciliation = summary.reconciliation.id;
+
+        assert_eq!(summary.difference, Money::from(70));
+        assert!(start_reconciliation(app.state(), january(&account, 70))
+            .await
+            .is_err());
+
+        for transaction in &transactions {
+            toggle_cleared(app.state(), reconciliation.clone(), transaction.id.clone())
+                .await
+                .expect("failed to toggle transaction");
+        }
+
+        // The transaction after the statement date is not counted.
+        let summary = finish_reconciliation(app.state(), reconciliation)
+            .await
+            .expect("failed to finish reconciliation");
+
+        assert_eq!(summary.cleared_balance, Money::from(70));
+        assert!(summary.difference.is_zero());
+        assert!(summary.reconciliation.finished.is_some());
+
+        let statuses: Vec<(Money, Status)> = get_transactions(app.state(), account.id, None)
+            .await
+            .expect("failed to get transactions")
+            .into_iter()
+            .map(|transaction| (transaction.inner.amount, transaction.status))
+            .collect();
+
+        assert!(statuses.contains(&(Money::from(100), Status::Reconciled)));
+        assert!(statuses.contains(&(Money::from(-30), Status::Reconciled)));
+        assert!(statuses.contains(&(Money::from(-5), Status::Cleared)));
+    }
+
+    #[tokio::test]
+    pub async fn test_reconciliation_unbalanced() {
+        let (app, account, transactions) = setup().await;
+
+        let reconciliation = start_reconciliation(app.state(), january(&account, 70))
+            .await
+            .expect("failed to start reconciliation")
+            .reconciliation
+            .id;
+        let summary = toggle_cleared(
+            app.state(),
+            reconciliation.clone(),
+            transactions[0].id.clone(),
+        )
+        .await
+        .expect("failed to toggle transaction");
+
+        assert_eq!(summary.difference, Money::from(-30));
+        assert!(finish_reconciliation(app.state(), reconciliation.clone())
+            .await
+            .is_err());
+
+        cancel_reconciliation(app.state(), reconciliation)
+            .await
+            .expect("failed to cancel reconciliation");
+        start_reconciliation(app.state(), january(&account, 70))
+            .await
+            .expect("failed to start reconciliation");
+    }
+
+    #[tokio::test]
+    pub async fn test_reconciled_transactions_are_locked() {
+        let (app, account, transactions) = setup().await;
+
+        let reconciliation = start_reconciliation(app.state(), january(&account, 100))
+            .await
+            .expect("failed to start reconciliation")
+            .reconciliation
+            .id;
+        toggle_cleared(
+            app.state(),
+            reconciliation.clone(),
+            transactions[0].id.clone(),
+        )
+        .await
+        .expect("failed to toggle transaction");
+        finish_reconciliation(app.state(), reconciliation)
+            .await
+            .expect("failed to finish reconciliation");
+
+        let mut transaction = transactions[0].clone();
+        transaction.inner.description = "Salary".to_string();
+
+        assert!(update_transaction(app.state(), transaction.clone(), None)
+            .await
+            .is_err());
+        assert!(
+            delete_transaction(app.state(), app.state(), transaction.id.clone(), None)
+                .await
+                .is_err()
+        );
+
+        update_transaction(app.state(), transaction.clone(), Some(true))
+            .await
+            .expect("failed to update transaction");
+        delete_transaction(app.state(), app.state(), transaction.id, Some(true))
+            .await
+            .expect("failed to delete transaction");
+    }
+
+    #[tokio::test]
+    pub async fn test_reconciled_transactions_are_locked_from_bulk_changes() {
+        let (app, account, transactions) = setup().await;
+        let tag = Tag {
+            label: "work".to_string(),
+            color: None,
+        };
+        let category = add_category(
+            app.state(),
+            CreateCategoryOptions {
+                id: Some("salary".to_string()),
+                name: "Salary".to_string(),
+                icon: Icon::Savings,
+                color: "green".to_string(),
+                parent: None,
+            },
+        )
+        .await
+        .expect("failed to add category");
+
+        add_tags(app.state(), vec![tag.clone()])
+            .await
+            .expect("failed to add tags");
+
+        let mut transaction = transactions[0].clone();
+        transaction.inner.description = "Salary".to_string();
+        transaction.inner.tags = vec![tag];
+        transaction.category = category.id.clone();
+        update_transaction(app.state(), transaction.clone(), None)
+            .await
+            .expect("failed to update transaction");
+
+        let reconciliation = start_reconciliation(app.state(), january(&account, 100))
+            .await
+            .expect("failed to start reconciliation")
+            .reconciliation
+            .id;
+        toggle_cleared(app.state(), reconciliation.clone(), transaction.id.clone())
+            .await
+            .expect("failed to toggle transaction");
+        finish_reconciliation(app.state(), reconciliation)
+            .await
+            .expect("failed to finish reconciliation");
+
+        let other: surrealdb::RecordId = ("category", "other").into();
+
+        assert!(
+            rename_tag(app.state(), "work".to_string(), "job".to_string(), None)
+                .await
+                .is_err()
+        );
+        assert!(delete_tag(app.state(), "work".to_string(), None)
+            .await
+            .is_err());
+        // The color of a tag is only shown, so it is changed on reconciled
+        // transactions too.
+        recolor_tag(app.state(), "work".to_string(), Some("blue".to_string()))
+            .await
+            .expect("failed to recolor tag");
+        assert!(
+            delete_category(app.state(), category.id.clone(), other.clone(), None)
+                .await
+                .is_err()
+        );
+
+        // Reconciled transactions do not get a payee.
+        add_payee(
+            app.state(),
+            CreatePayeeOptions {
+                name: "Salary".to_string(),
+                aliases: vec![],
+                patterns: vec![],
+                category: None,
+            },
+        )
+        .await
+        .expect("failed to add payee");
+        assert_eq!(
+            assign_payees(app.state())
+                .await
+                .expect("failed to assign payees"),
+            0
+        );
+
+        delete_tag(app.state(), "work".to_string(), Some(true))
+            .await
+            .expect("failed to delete tag");
+        delete_category(app.state(), category.id, other.clone(), Some(true))
+            .await
+            .expect("failed to delete category");
+
+        let transaction = get_transactions(app.state(), account.id, None)
+            .await
+            .expect("failed to get transactions")
+            .into_iter()
+            .find(|found| found.id == transaction.id)
+            .unwrap();
+
+        assert!(transaction.inner.tags.is_empty());
+        assert_eq!(transaction.category, other);
+    }
+}
@@ -0,0 +1,280 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use tauri::Manager;
+    use thunes_cli::{
+        account::{Account, AccountKind, AddAccountOptions},
+        money::Money,
+        transaction::{
+            payee::CreatePayeeOptions,
+            recurring::{CreateRecurringOptions, PreviewRecurringOptions, Schedule},
+            rule::{CreateRuleOptions, DescriptionMatch},
+            tag::Tag,
+        },
+    };
+    use thunes_lib::commands::{
+        account::{add_account, get_balance},
+        payees::add_payee,
+        recurring::{
+            add_recurring_transaction, apply_recurring_transactions, list_recurring_transactions,
+            preview_recurring_transactions,
+        },
+        rules::add_rule,
+        tags::get_tags,
+        transaction::{delete_transaction, update_transaction},
+    };
+
+    async fn setup() -> (tauri::App<tauri::test::MockRuntime>, Account) {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "My Account".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        (app, account)
+    }
+
+    #[tokio::test]
+    pub async fn test_apply_recurring_transactions() {
+        let (app, account) = setup().await;
+
+        add_recurring_transaction(
+            app.state(),
+            CreateRecurringOptions {
+                account: account.id.clone(),
+                amount: Money::from(-800),
+                category: None,
+                description: "Rent".to_string(),
+                tags: vec![],
+                schedule: Schedule::Monthly { day: 1 },
+                start: chrono::Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+                end: Some(chrono::Utc.with_ymd_and_hms(2025, 6, 30, 0, 0, 0).unwrap()),
+            },
+        )
+        .await
+        .expect("failed to add recurring transaction");
+
+        let transactions = apply_recurring_transactions(app.state())
+            .await
+            .expect("failed to apply recurring transactions");
+
+        assert_eq!(transactions.len(), 6);
+
+        // Occurrences that already exist are skipped.
+        assert!(apply_recurring_transactions(app.state())
+            .await
+            .expect("failed to apply recurring transactions")
+            .is_empty());
+
+        assert_eq!(
+            get_balance(app.state(), account.id.clone(), None)
+                .await
+                .expect("failed to get balance"),
+            Money::from(-4800)
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_recurring_transactions_follow_rules_and_payees() {
+        let (app, account) = setup().await;
+
+        add_rule(
+            app.state(),
+            CreateRuleOptions {
+                name: "Subscriptions".to_string(),
+                priority: 0,
+                description: Some(DescriptionMatch::Contains("netflix".to_string())),
+                min_amount: None,
+                max_amount: None,
+                account: None,
+                days_of_month: vec![],
+                category: Some(("category", "subscription-other").into()),
+                tags: vec![Tag {
+                    label: "subscription".to_string(),
+                    color: None,
+                }],
+                rename: None,
+            },
+        )
+        .await
+        .expect("failed to add rule");
+        let payee = add_payee(
+            app.state(),
+            CreatePayeeOptions {
+                name: "Netflix".to_string(),
+                aliases: vec!["netflix".to_string()],
+                patterns: vec![],
+                category: None,
+            },
+        )
+        .await
+        .expect("failed to add payee");
+
+        add_recurring_transaction(
+            app.state(),
+            CreateRecurringOptions {
+                account: account.id.clone(),
+                amount: Money::from(-15),
+                category: None,
+                description: "Netflix".to_string(),
+                tags: vec![],
+                schedule: Schedule::Monthly { day: 5 },
+                start: chrono::Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap(),
+                end: Some(chrono::Utc.with_ymd_and_hms(2025, 2, 28, 0, 0, 0).unwrap()),
+            },
+        )
+        .await
+        .expect("failed to add recurring transaction");
+
+        let transactions = apply_recurring_transactions(app.state())
+            .await
+            .expect("failed to apply recurring transactions");
+
+        assert_eq!(transactions.len(), 2);
+        assert!(transactions.iter().all(|transaction| {
+            transaction.category == ("category", "subscription-other").into()
+                && transaction.payee.as_ref() == Some(&payee.id)
+                && transaction.inner.tags.len() == 1
+        }));
+        assert!(get_tags(app.state())
+            .await
+            .expect("failed to get tags")
+            .iter()
+            .any(|tag| tag.label == "subscription"));
+    }
+
+    #[tokio::test]
+    pub async fn test_removed_occurrences_are_not_recreated() {
+        let (app, account) = setup().await;
+
+        add_recurring_transaction(
+            app.state(),
+            CreateRecurringOptions {
+                account: account.id.clone(),
+                amount: Money::from(-800),
+                category: None,
+                description: "Rent".to_string(),
+                tags: vec![],
+                schedule: Schedule::Monthly { day: 1 },
+                start: chrono::Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+                end: Some(chrono::Utc.with_ymd_and_hms(2025, 6, 30, 0, 0, 0).unwrap()),
+            },
+        )
+        .await
+        .expect("failed to add recurring transaction");
+
+        let mut transactions = apply_recurring_transactions(app.state())
+            .await
+            .expect("failed to apply recurring transactions");
+
+        let deleted = transactions.pop().unwrap();
+        delete_transaction(app.state(), app.state(), deleted.id, None)
+            .await
+            .expect("failed to delete transaction");
+
+        let mut moved = transactions.pop().unwrap();
+        moved.inner.date += chrono::Duration::days(3);
+        update_transaction(app.state(), moved, None)
+            .await
+            .expect("failed to update transaction");
+
+        assert!(apply_recurring_transactions(app.state())
+            .await
+            .expect("failed to apply recurring transactions")
+            .is_empty());
+        assert_eq!(
+            get_balance(app.state(), account.id.clone(), None)
+                .await
+                .expect("failed to get balance"),
+            Money::from(-4000)
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_preview_includes_occurrences_of_today() {
+        let (app, account) = setup().await;
+        let today = chrono::Utc::now()
+            .date_naive()
+            .and_time(chrono::NaiveTime::MIN)
+            .and_utc();
+
+        add_recurring_transaction(
+            app.state(),
+            CreateRecurringOptions {
+                account: account.id.clone(),
+                amount: Money::from(-10),
+                category: None,
+                description: "Streaming".to_string(),
+                tags: vec![],
+                schedule: Schedule::Weekly,
+                start: today,
+                end: None,
+            },
+        )
+        .await
+        .expect("failed to add recurring transaction");
+
+        let occurrences = preview_recurring_transactions(
+            app.state(),
+            PreviewRecurringOptions {
+                account: None,
+                until: today + chrono::Duration::days(6),
+            },
+        )
+        .await
+        .expect("failed to preview recurring transactions");
+
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].date, today);
+    }
+
+    #[tokio::test]
+    pub async fn test_preview_recurring_transactions() {
+        let (app, account) = setup().await;
+        let now = chrono::Utc::now();
+
+        add_recurring_transaction(
+            app.state(),
+            CreateRecurringOptions {
+                account: account.id.clone(),
+                amount: Money::from(-10),
+                category: None,
+                description: "Streaming".to_string(),
+                tags: vec![],
+                schedule: Schedule::Weekly,
+                start: now + chrono::Duration::days(1),
+                end: None,
+            },
+        )
+        .await
+        .expect("failed to add recurring transaction");
+
+        let occurrences = preview_recurring_transactions(
+            app.state(),
+            PreviewRecurringOptions {
+                account: None,
+                until: now + chrono::Duration::days(28),
+            },
+        )
+        .await
+        .expect("failed to preview recurring transactions");
+
+        assert_eq!(occurrences.len(), 4);
+        assert_eq!(
+            list_recurring_transactions(app.state(), Some(account.id))
+                .await
+                .expect("failed to list recurring transactions")
+                .len(),
+            1
+        );
+    }
+}
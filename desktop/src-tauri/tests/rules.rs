@@ -0,0 +1,376 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use tauri::Manager;
+    use thunes_cli::{
+        account::{Account, AccountKind, AddAccountOptions},
+        money::Money,
+        transaction::{
+            rule::{ApplyRulesOptions, CreateRuleOptions, DescriptionMatch},
+            tag::Tag,
+            AddTransactionOptions, Split,
+        },
+    };
+    use thunes_lib::commands::{
+        account::add_account,
+        categories::delete_category,
+        import::import_transactions,
+        rules::{add_rule, apply_rules, delete_rule, get_rules, update_rule},
+        tags::{add_tags, delete_tag, get_tags, rename_tag},
+        transaction::{add_transaction, get_transactions},
+    };
+
+    async fn setup() -> (tauri::App<tauri::test::MockRuntime>, Account) {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "My Account".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        (app, account)
+    }
+
+    fn date(day: u32) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc
+            .with_ymd_and_hms(2026, 3, day, 0, 0, 0)
+            .single()
+            .unwrap()
+    }
+
+    fn transaction(amount: i64, description: &str, day: u32) -> AddTransactionOptions {
+        AddTransactionOptions {
+            amount: Money::from(amount),
+            description: description.to_string(),
+            date: Some(date(day)),
+            ..Default::default()
+        }
+    }
+
+    fn rule(name: &str, priority: i64) -> CreateRuleOptions {
+        CreateRuleOptions {
+            name: name.to_string(),
+            priority,
+            description: None,
+            min_amount: None,
+            max_amount: None,
+            account: None,
+            days_of_month: vec![],
+            category: None,
+            tags: vec![],
+            rename: None,
+        }
+    }
+
+    async fn add_rules(app: &tauri::App<tauri::test::MockRuntime>, account: &Account) {
+        add_rule(
+            app.state(),
+            CreateRuleOptions {
+                description: Some(DescriptionMatch::Regex(r"^CB (?<shop>\w+)".to_string())),
+                max_amount: Some(Money::from(0)),
+                category: Some(("category", "everyday-life-food").into()),
+                tags: vec![Tag {
+                    label: "card".to_string(),
+                    color: None,
+                }],
+                rename: Some("${shop}".to_string()),
+                ..rule("Groceries", 10)
+            },
+        )
+        .await
+        .expect("failed to add rule");
+        add_rule(
+            app.state(),
+            CreateRuleOptions {
+                description: Some(DescriptionMatch::Contains("loyer".to_string())),
+                min_amount: Some(Money::from(-1000)),
+                max_amount: Some(Money::from(-500)),
+                account: Some(account.id.clone()),
+                days_of_month: vec![1, 2, 3, 4, 5],
+                category: Some(("category", "accommodation-rent").into()),
+                ..rule("Rent", 5)
+            },
+        )
+        .await
+        .expect("failed to add rule");
+    }
+
+    #[tokio::test]
+    pub async fn test_rules_crud() {
+        let (app, account) = setup().await;
+
+        add_rules(&app, &account).await;
+
+        let error = add_rule(
+            app.state(),
+            CreateRuleOptions {
+                description: Some(DescriptionMatch::Regex("(".to_string())),
+                ..rule("Invalid", 0)
+            },
+        )
+        .await;
+
+        assert!(error.is_err());
+
+        let mut rules = get_rules(app.state()).await.expect("failed to get rules");
+
+        assert_eq!(
+            rules
+                .iter()
+                .map(|rule| rule.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Groceries", "Rent"]
+        );
+
+        // Rules are sorted by decreasing priority.
+        let mut groceries = rules.remove(0);
+        groceries.priority = 1;
+
+        update_rule(app.state(), groceries.clone())
+            .await
+            .expect("failed to update rule");
+
+        let rules = get_rules(app.state()).await.expect("failed to get rules");
+
+        assert_eq!(rules[1].name, "Groceries");
+
+        delete_rule(app.state(), groceries.id)
+            .await
+            .expect("failed to delete rule");
+
+        assert_eq!(
+            get_rules(app.state())
+                .await
+                .expect("failed to get rules")
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_rules_follow_category_and_tag_changes() {
+        let (app, account) = setup().await;
+
+        add_rules(&app, &account).await;
+        add_tags(
+            app.state(),
+            vec![Tag {
+                label: "card".to_string(),
+                color: None,
+            }],
+        )
+        .await
+        .expect("failed to add tags");
+
+        rename_tag(app.state(), "card".to_string(), "debit".to_string(), None)
+            .await
+            .expect("failed to rename tag");
+        delete_category(
+            app.state(),
+            ("category", "everyday-life-food").into(),
+            ("category", "everyday-life").into(),
+            None,
+        )
+        .await
+        .expect("failed to delete category");
+
+        let rules = get_rules(app.state()).await.expect("failed to get rules");
+
+        assert_eq!(rules[0].tags[0].label, "debit");
+        assert_eq!(
+            rules[0].category,
+            Some(("category", "everyday-life").into())
+        );
+
+        delete_tag(app.state(), "debit".to_string(), None)
+            .await
+            .expect("failed to delete tag");
+
+        let rules = get_rules(app.state()).await.expect("failed to get rules");
+
+        assert!(rules[0].tags.is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn test_rules_on_create_and_import() {
+        let (app, account) = setup().await;
+
+        add_rules(&app, &account).await;
+
+        let groceries = add_transaction(
+            app.state(),
+            account.id.clone(),
+            transaction(-10, "CB MONOP 0312", 12),
+        )
+        .await
+        .expect("failed to add transaction");
+
+        assert_eq!(
+            groceries.category,
+            ("category", "everyday-life-food").into()
+        );
+        assert_eq!(groceries.inner.description, "MONOP");
+        assert_eq!(groceries.inner.tags.len(), 1);
+
+        // A category chosen for the transaction is kept.
+        let chosen = add_transaction(
+            app.state(),
+            account.id.clone(),
+            AddTransactionOptions {
+                category: Some(("category", "other").into()),
+                ..transaction(-10, "CB MONOP 0313", 13)
+            },
+        )
+        .await
+        .expect("failed to add transaction");
+
+        assert_eq!(chosen.category, ("category", "other").into());
+        assert_eq!(chosen.inner.description, "MONOP");
+
+        let summary = import_transactions(
+            app.state(),
+            account.id.clone(),
+            vec![
+                transaction(-800, "PRLV LOYER MARS", 3),
+                transaction(-800, "PRLV LOYER AVRIL", 10),
+            ],
+        )
+        .await
+        .expect("failed to import transactions");
+
+        assert_eq!(
+            summary.imported[0].category,
+            ("category", "accommodation-rent").into()
+        );
+        // Outside of the days of the month of the rule.
+        assert_eq!(summary.imported[1].category, ("category", "other").into());
+    }
+
+    #[tokio::test]
+    pub async fn test_apply_rules() {
+        let (app, account) = setup().await;
+        let before = add_transaction(
+            app.state(),
+            account.id.clone(),
+            transaction(-42, "CB CARREFOUR 0312", 12),
+        )
+        .await
+        .expect("failed to add transaction");
+
+        add_rules(&app, &account).await;
+
+        let options = |dry_run| ApplyRulesOptions {
+            account: Some(account.id.clone()),
+            start: date(1),
+            end: date(31),
+            dry_run,
+        };
+        let changes = apply_rules(app.state(), options(true))
+            .await
+            .expect("failed to apply rules");
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].transaction.id, before.id);
+        assert_eq!(
+            changes[0].transaction.inner.description,
+            "CB CARREFOUR 0312"
+        );
+        assert_eq!(changes[0].description, "CARREFOUR");
+        assert_eq!(
+            changes[0].category,
+            ("category", "everyday-life-food").into()
+        );
+
+        // A dry run does not change the transactions.
+        assert_eq!(
+            apply_rules(app.state(), options(true))
+                .await
+                .expect("failed to apply rules")
+                .len(),
+            1
+        );
+
+        apply_rules(app.state(), options(false))
+            .await
+            .expect("failed to apply rules");
+
+        assert!(apply_rules(app.state(), options(true))
+            .await
+            .expect("failed to apply rules")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn test_rules_keep_split_categories_and_add_tags() {
+        let (app, account) = setup().await;
+        let split = |amount| Split {
+            category: ("category", "other").into(),
+            amount: Money::from(amount),
+            description: "Shopping".to_string(),
+            tags: vec![],
+        };
+        let before = add_transaction(
+            app.state(),
+            account.id.clone(),
+            AddTransactionOptions {
+                splits: vec![split(-30), split(-12)],
+                ..transaction(-42, "CB CARREFOUR 0312", 12)
+            },
+        )
+        .await
+        .expect("failed to add transaction");
+
+        add_rules(&app, &account).await;
+
+        // Transactions with splits keep the categories of their splits.
+        let created = add_transaction(
+            app.state(),
+            account.id.clone(),
+            AddTransactionOptions {
+                splits: vec![split(-5), split(-5)],
+                ..transaction(-10, "CB MONOP 0313", 13)
+            },
+        )
+        .await
+        .expect("failed to add transaction");
+
+        assert_eq!(created.category, ("category", "other").into());
+        assert_eq!(created.inner.description, "MONOP");
+
+        apply_rules(
+            app.state(),
+            ApplyRulesOptions {
+                account: Some(account.id.clone()),
+                start: date(1),
+                end: date(31),
+                dry_run: false,
+            },
+        )
+        .await
+        .expect("failed to apply rules");
+
+        let transactions = get_transactions(app.state(), account.id.clone(), None)
+            .await
+            .expect("failed to get transactions");
+        let after = transactions
+            .iter()
+            .find(|transaction| transaction.id == before.id)
+            .expect("missing transaction");
+
+        assert_eq!(after.category, ("category", "other").into());
+        assert_eq!(after.inner.description, "CARREFOUR");
+
+        // Tags added by the rules are in the tag list.
+        let tags = get_tags(app.state()).await.expect("failed to get tags");
+
+        assert!(tags.iter().any(|tag| tag.label == "card"));
+    }
+}
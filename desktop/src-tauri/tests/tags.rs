@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use tauri::Manager;
+    use thunes_cli::{
+        account::{Account, AccountKind, AddAccountOptions},
+        money::Money,
+        transaction::{tag::Tag, AddTransactionOptions, Split},
+    };
+    use thunes_lib::commands::{
+        account::add_account,
+        tags::{add_tags, delete_tag, get_tags, get_tags_usage, merge_tags, rename_tag},
+        transaction::{add_transaction, get_transactions},
+    };
+
+    fn tag(label: &str) -> Tag {
+        Tag {
+            label: label.to_string(),
+            color: Some("blue".to_string()),
+        }
+    }
+
+    async fn setup() -> (tauri::App<tauri::test::MockRuntime>, Account) {
+        let app = crate::common::setup().await;
+        let account = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "EUR".to_string(),
+                name: "My Account".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        add_tags(app.state(), vec![tag("holidays"), tag("vacation")])
+            .await
+            .expect("failed to add tags");
+
+        for (amount, label) in [(-50, "holidays"), (-20, "vacation")] {
+            add_transaction(
+                app.state(),
+                account.id.clone(),
+                AddTransactionOptions {
+                    amount: Money::from(amount),
+                    category: None,
+                    description: "Hotel".to_string(),
+                    tags: vec![tag(label)],
+                    splits: vec![],
+                    date: None,
+                    external_id: None,
+                    payee: None,
+                },
+            )
+            .await
+            .expect("failed to add transaction");
+        }
+
+        (app, account)
+    }
+
+    async fn labels(app: &tauri::App<tauri::test::MockRuntime>, account: &Account) -> Vec<String> {
+        get_transactions(app.state(), account.id.clone(), None)
+            .await
+            .expect("failed to get transactions")
+            .into_iter()
+            .flat_map(|transaction| transaction.inner.tags)
+            .map(|tag| tag.label)
+            .collect()
+    }
+
+    #[tokio::test]
+    pub async fn test_rename_tag() {
+        let (app, account) = setup().await;
+
+        rename_tag(
+            app.state(),
+            "holidays".to_string(),
+            "trip".to_string(),
+            None,
+        )
+        .await
+        .expect("failed to rename tag");
+
+        let labels = labels(&app, &account).await;
+        assert!(labels.contains(&"trip".to_string()));
+        assert!(!labels.contains(&"holidays".to_string()));
+    }
+
+    #[tokio::test]
+    pub async fn test_merge_tags() {
+        let (app, account) = setup().await;
+
+        merge_tags(
+            app.state(),
+            vec!["vacation".to_string()],
+            "holidays".to_string(),
+            None,
+        )
+        .await
+        .expect("failed to merge tags");
+
+        assert_eq!(labels(&app, &account).await, vec!["holidays", "holidays"]);
+        assert_eq!(get_tags(app.state()).await.unwrap().len(), 1);
+
+        let usage = get_tags_usage(app.state())
+            .await
+            .expect("failed to get tags usage");
+
+        assert_eq!(usage[0].count, 2);
+        assert_eq!(usage[0].totals[0].total, Money::from(-70));
+    }
+
+    #[tokio::test]
+    pub async fn test_delete_tag() {
+        let (app, account) = setup().await;
+
+        delete_tag(app.state(), "vacation".to_string(), None)
+            .await
+            .expect("failed to delete tag");
+
+        assert_eq!(labels(&app, &account).await, vec!["holidays"]);
+        assert_eq!(get_tags(app.state()).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    pub async fn test_tags_usage_of_splits() {
+        let (app, account) = setup().await;
+
+        // The tags of the splits replace the tags of the transaction.
+        add_transaction(
+            app.state(),
+            account.id.clone(),
+            AddTransactionOptions {
+                amount: Money::from(-100),
+                category: None,
+                description: "Travel agency".to_string(),
+                tags: vec![tag("vacation")],
+                splits: vec![
+                    Split {
+                        category: ("category", "other").into(),
+                        amount: Money::from(-70),
+                        description: "Flight".to_string(),
+                        tags: vec![tag("holidays")],
+                    },
+                    Split {
+                        category: ("category", "other").into(),
+                        amount: Money::from(-30),
+                        description: "Insurance".to_string(),
+                        tags: vec![],
+                    },
+                ],
+                date: None,
+                external_id: None,
+                payee: None,
+            },
+        )
+        .await
+        .expect("failed to add transaction");
+
+        let usage = get_tags_usage(app.state())
+            .await
+            .expect("failed to get tags usage");
+        let holidays = usage
+            .iter()
+            .find(|usage| usage.tag.label == "holidays")
+            .unwrap();
+        let vacation = usage
+            .iter()
+            .find(|usage| usage.tag.label == "vacation")
+            .unwrap();
+
+        assert_eq!(holidays.count, 2);
+        assert_eq!(holidays.totals[0].total, Money::from(-120));
+        assert_eq!(vacation.count, 1);
+        assert_eq!(vacation.totals[0].total, Money::from(-20));
+    }
+}
@@ -0,0 +1,710 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use surrealdb::{engine::local::Db, Surreal};
+    use tauri::Manager;
+    use thunes_cli::{
+        account::{Account, AccountKind, AddAccountOptions},
+        fx::CreateExchangeRateOptions,
+        money::Money,
+        transaction::{
+            tag::Tag, transfer::ReadTransferOptions, AddTransactionOptions,
+            AddTransactionTransferOptions, Flow, ReadTransactionOptions,
+            ReadTransactionPageOptions, SortDirection, SortKey, TagFilter, TransactionSort,
+        },
+    };
+    use thunes_lib::commands::{
+        account::{add_account, get_balance, update_account},
+        fx::add_exchange_rate,
+        transaction::{
+            add_transaction, add_transaction_transfer, delete_transaction, get_transaction_page,
+            get_transactions, get_transfers, search_transactions, suggest_categories,
+            update_transaction,
+        },
+    };
+
+    async fn setup() -> (tauri::App<tauri::test::MockRuntime>, Account, Account) {
+        let app = crate::common::setup().await;
+        let mut accounts = vec![];
+
+        for name in ["Checking", "Savings"] {
+            accounts.push(
+                add_account(
+                    app.state(),
+                    AddAccountOptions {
+                        currency: "EUR".to_string(),
+                        name: name.to_string(),
+                        kind: AccountKind::Checking,
+                    },
+                )
+                .await
+                .expect("failed to create account"),
+            );
+        }
+
+        let savings = accounts.pop().unwrap();
+        let checking = accounts.pop().unwrap();
+
+        (app, checking, savings)
+    }
+
+    async fn transfer(
+        app: &tauri::App<tauri::test::MockRuntime>,
+        from: &Account,
+        to: &Account,
+        amount: i64,
+    ) -> thunes_cli::transaction::TransactionWithId {
+        add_transaction_transfer(
+            app.state(),
+            AddTransactionTransferOptions {
+                description: "Savings".to_string(),
+                amount: Money::from(amount),
+                received: None,
+                fee: None,
+                date: None,
+                tags: vec![],
+                from: from.id.clone(),
+                to: to.id.clone(),
+            },
+        )
+        .await
+        .expect("failed to add transfer")
+    }
+
+    async fn balance(app: &tauri::App<tauri::test::MockRuntime>, account: &Account) -> Money {
+        get_balance(app.state(), account.id.clone(), None)
+            .await
+            .expect("failed to get balance")
+    }
+
+    #[tokio::test]
+    pub async fn test_transfer_creation() {
+        let (app, checking, savings) = setup().await;
+
+        let sent = transfer(&app, &checking, &savings, 100).await;
+
+        assert_eq!(sent.account, checking.id);
+        assert_eq!(sent.inner.amount, Money::from(-100));
+        assert!(sent.transfer.is_some());
+        assert_eq!(balance(&app, &checking).await, Money::from(-100));
+        assert_eq!(balance(&app, &savings).await, Money::from(100));
+    }
+
+    #[tokio::test]
+    pub async fn test_transfer_list() {
+        let (app, checking, savings) = setup().await;
+
+        transfer(&app, &checking, &savings, 100).await;
+        transfer(&app, &savings, &checking, 20).await;
+
+        let transfers = get_transfers(
+            app.state(),
+            ReadTransferOptions {
+                account: savings.id.clone(),
+                other_account: Some(checking.id.clone()),
+            },
+        )
+        .await
+        .expect("failed to get transfers");
+
+        assert_eq!(transfers.len(), 2);
+        assert!(
+            transfers
+                .iter()
+                .all(|transfer| transfer.from_transaction.transfer
+                    == transfer.to_transaction.transfer)
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_transfer_update_both_sides() {
+        let (app, checking, savings) = setup().await;
+
+        let mut sent = transfer(&app, &checking, &savings, 100).await;
+        sent.inner.amount = Money::from(-150);
+
+        update_transaction(app.state(), sent, None)
+            .await
+            .expect("failed to update transfer");
+
+        assert_eq!(balance(&app, &checking).await, Money::from(-150));
+        assert_eq!(balance(&app, &savings).await, Money::from(150));
+    }
+
+    #[tokio::test]
+    pub async fn test_transfer_delete_both_sides() {
+        let (app, checking, savings) = setup().await;
+
+        let sent = transfer(&app, &checking, &savings, 100).await;
+
+        delete_transaction(app.state(), app.state(), sent.id, None)
+            .await
+            .expect("failed to delete transfer");
+
+        assert_eq!(balance(&app, &checking).await, Money::from(0));
+        assert_eq!(balance(&app, &savings).await, Money::from(0));
+        assert!(get_transfers(
+            app.state(),
+            ReadTransferOptions {
+                account: checking.id.clone(),
+                other_account: None,
+            },
+        )
+        .await
+        .expect("failed to get transfers")
+        .is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn test_transfer_cross_currency() {
+        let (app, checking, _) = setup().await;
+        let dollars = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "USD".to_string(),
+                name: "Dollars".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        let sent = add_transaction_transfer(
+            app.state(),
+            AddTransactionTransferOptions {
+                description: "Exchange".to_string(),
+                amount: Money::from(100),
+                received: Some("110.50".parse().unwrap()),
+                fee: Some(Money::from(2)),
+                date: None,
+                tags: vec![],
+                from: checking.id.clone(),
+                to: dollars.id.clone(),
+            },
+        )
+        .await
+        .expect("failed to add transfer");
+
+        assert_eq!(sent.inner.amount, Money::from(-102));
+        assert_eq!(balance(&app, &dollars).await, "110.50".parse().unwrap());
+
+        let transfers = get_transfers(
+            app.state(),
+            ReadTransferOptions {
+                account: dollars.id.clone(),
+                other_account: None,
+            },
+        )
+        .await
+        .expect("failed to get transfers");
+
+        assert_eq!(transfers[0].sent, Money::from(100));
+        assert_eq!(transfers[0].fee, Money::from(2));
+        assert_eq!(transfers[0].rate, "1.105".parse().unwrap());
+    }
+
+    #[tokio::test]
+    pub async fn test_transfer_cross_currency_without_rate() {
+        let (app, checking, _) = setup().await;
+        let dollars = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "USD".to_string(),
+                name: "Dollars".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        assert!(add_transaction_transfer(
+            app.state(),
+            AddTransactionTransferOptions {
+                description: "Exchange".to_string(),
+                amount: Money::from(100),
+                received: None,
+                fee: None,
+                date: None,
+                tags: vec![],
+                from: checking.id.clone(),
+                to: dollars.id.clone(),
+            },
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    pub async fn test_transfer_cross_currency_rounds_to_minor_unit() {
+        let (app, checking, _) = setup().await;
+        let yens = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "JPY".to_string(),
+                name: "Yens".to_string(),
+                kind: AccountKind::Checking,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        add_exchange_rate(
+            app.state(),
+            CreateExchangeRateOptions {
+                date: chrono::Utc::now() - chrono::Duration::days(1),
+                base: "EUR".to_string(),
+                quote: "JPY".to_string(),
+                rate: "161.237".parse().unwrap(),
+            },
+        )
+        .await
+        .expect("failed to add exchange rate");
+
+        add_transaction_transfer(
+            app.state(),
+            AddTransactionTransferOptions {
+                description: "Exchange".to_string(),
+                amount: "10.55".parse().unwrap(),
+                received: None,
+                fee: None,
+                date: None,
+                tags: vec![],
+                from: checking.id.clone(),
+                to: yens.id.clone(),
+            },
+        )
+        .await
+        .expect("failed to add transfer");
+
+        // 10.55 EUR * 161.237 = 1701.05035 JPY, yens have no minor unit.
+        assert_eq!(balance(&app, &yens).await, Money::from(1701));
+    }
+
+    #[tokio::test]
+    pub async fn test_suggest_categories() {
+        let (app, checking, savings) = setup().await;
+        let card = Tag {
+            label: "card".to_string(),
+            color: None,
+        };
+
+        for (description, category, tags) in [
+            (
+                "CB CARREFOUR 0112",
+                "everyday-life-food",
+                vec![card.clone()],
+            ),
+            (
+                "CB CARREFOUR MARKET 0212",
+                "everyday-life-food",
+                vec![card.clone()],
+            ),
+            ("CB MONOP 0312", "everyday-life-food", vec![]),
+            ("CB TOTAL ENERGIES 0412", "car-fuel", vec![card.clone()]),
+            ("TOTAL STATION", "car-fuel", vec![]),
+        ] {
+            add_transaction(
+                app.state(),
+                checking.id.clone(),
+                AddTransactionOptions {
+                    amount: Money::from(-10),
+                    description: description.to_string(),
+                    category: Some(("category", category).into()),
+                    tags,
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("failed to add transaction");
+        }
+
+        // Transfers are not learned from.
+        transfer(&app, &checking, &savings, 100).await;
+
+        let suggestions = suggest_categories(
+            app.state(),
+            vec![
+                "CB CARREFOUR 1503".to_string(),
+                "Total".to_string(),
+                "Savings".to_string(),
+            ],
+            None,
+        )
+        .await
+        .expect("failed to suggest categories");
+
+        assert_eq!(suggestions.len(), 3);
+        assert_eq!(
+            suggestions[0][0].category,
+            ("category", "everyday-life-food").into()
+        );
+        assert!(suggestions[0][0].confidence > 0.5);
+        assert_eq!(suggestions[0][0].tags, vec![card]);
+        assert_eq!(suggestions[1][0].category, ("category", "car-fuel").into());
+        assert!(suggestions[2].is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn test_transaction_pages() {
+        let (app, mut checking, _) = setup().await;
+
+        for (day, amount, description, category) in [
+            (1, -10, "Fuel", "car-fuel"),
+            (2, 2500, "Salary", "other"),
+            (3, -30, "Groceries", "everyday-life-food"),
+            (4, -45, "Fuel", "car-fuel"),
+            (5, -5, "Bakery", "everyday-life-food"),
+        ] {
+            add_transaction(
+                app.state(),
+                checking.id.clone(),
+                AddTransactionOptions {
+                    amount: Money::from(amount),
+                    description: description.to_string(),
+                    category: Some(("category", category).into()),
+                    date: chrono::NaiveDate::from_ymd_opt(2026, 1, day)
+                        .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("failed to add transaction");
+        }
+
+        let descriptions = |page: &thunes_cli::transaction::TransactionPage| {
+            page.transactions
+                .iter()
+                .map(|transaction| transaction.inner.description.clone())
+                .collect::<Vec<_>>()
+        };
+
+        // Transactions are sorted by date, most recent first, by default.
+        let page = get_transaction_page(
+            app.state(),
+            checking.id.clone(),
+            ReadTransactionPageOptions {
+                offset: 1,
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("failed to get transactions");
+
+        assert_eq!(descriptions(&page), vec!["Fuel", "Groceries"]);
+        assert_eq!(page.total, 5);
+        assert_eq!(page.sum, Money::from(2410));
+
+        let page = get_transaction_page(
+            app.state(),
+            checking.id.clone(),
+            ReadTransactionPageOptions {
+                filter: Some(ReadTransactionOptions {
+                    categories: vec![("category", "car-fuel").into()],
+                    ..Default::default()
+                }),
+                sort: Some(TransactionSort {
+                    key: SortKey::Amount,
+                    direction: SortDirection::Ascending,
+                }),
+                limit: Some(1),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("failed to get transactions");
+
+        assert_eq!(page.transactions[0].inner.amount, Money::from(-45));
+        assert_eq!(page.total, 2);
+        assert_eq!(page.sum, Money::from(-55));
+
+        // The sort saved on the account is used when none is given.
+        checking.data.sort = TransactionSort {
+            key: SortKey::Description,
+            direction: SortDirection::Ascending,
+        };
+
+        update_account(app.state(), checking.clone())
+            .await
+            .expect("failed to update account");
+
+        let page = get_transaction_page(
+            app.state(),
+            checking.id.clone(),
+            ReadTransactionPageOptions::default(),
+        )
+        .await
+        .expect("failed to get transactions");
+
+        assert_eq!(
+            descriptions(&page),
+            vec!["Bakery", "Fuel", "Fuel", "Groceries", "Salary"]
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_transaction_filters() {
+        let (app, mut checking, _) = setup().await;
+        let tag = |label: &str| Tag {
+            label: label.to_string(),
+            color: None,
+        };
+
+        for (amount, description, category, tags) in [
+            (-60, "Fuel", "car-fuel", vec![tag("card")]),
+            (2500, "Salary", "other", vec![]),
+            (
+                -30,
+                "Groceries",
+                "everyday-life-food",
+                vec![tag("card"), tag("shared")],
+            ),
+            (-5, "Parking", "car", vec![tag("cash")]),
+            (-12, "Unknown", "other", vec![tag("card")]),
+        ] {
+            add_transaction(
+                app.state(),
+                checking.id.clone(),
+                AddTransactionOptions {
+                    amount: Money::from(amount),
+                    description: description.to_string(),
+                    category: Some(("category", category).into()),
+                    tags,
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("failed to add transaction");
+        }
+
+        let read = |filter: ReadTransactionOptions| {
+            let app = &app;
+            let account = checking.id.clone();
+
+            async move {
+                let mut descriptions = get_transactions(app.state(), account, Some(filter))
+                    .await
+                    .expect("failed to get transactions")
+                    .into_iter()
+                    .map(|transaction| transaction.inner.description)
+                    .collect::<Vec<_>>();
+
+                descriptions.sort();
+                descriptions
+            }
+        };
+
+        assert_eq!(
+            read(ReadTransactionOptions {
+                min_amount: Some(Money::from(-30)),
+                flow: Some(Flow::Expense),
+                ..Default::default()
+            })
+            .await,
+            vec!["Groceries", "Parking", "Unknown"]
+        );
+        assert_eq!(
+            read(ReadTransactionOptions {
+                flow: Some(Flow::Income),
+                ..Default::default()
+            })
+            .await,
+            vec!["Salary"]
+        );
+        assert_eq!(
+            read(ReadTransactionOptions {
+                tags: Some(TagFilter::Any(vec![
+                    "cash".to_string(),
+                    "shared".to_string()
+                ])),
+                ..Default::default()
+            })
+            .await,
+            vec!["Groceries", "Parking"]
+        );
+        assert_eq!(
+            read(ReadTransactionOptions {
+                tags: Some(TagFilter::All(vec![
+                    "card".to_string(),
+                    "shared".to_string()
+                ])),
+                ..Default::default()
+            })
+            .await,
+            vec!["Groceries"]
+        );
+        assert_eq!(
+            read(ReadTransactionOptions {
+                tags: Some(TagFilter::Untagged),
+                ..Default::default()
+            })
+            .await,
+            vec!["Salary"]
+        );
+        assert_eq!(
+            read(ReadTransactionOptions {
+                uncategorized: true,
+                max_amount: Some(Money::from(0)),
+                ..Default::default()
+            })
+            .await,
+            vec!["Unknown"]
+        );
+
+        // Fuel is a sub-category of car.
+        let categories = ReadTransactionOptions {
+            categories: vec![
+                ("category", "car").into(),
+                ("category", "everyday-life-food").into(),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(read(categories.clone()).await, vec!["Groceries", "Parking"]);
+        assert_eq!(
+            read(ReadTransactionOptions {
+                include_subcategories: true,
+                ..categories.clone()
+            })
+            .await,
+            vec!["Fuel", "Groceries", "Parking"]
+        );
+
+        // The filter saved on the account is used when none is given.
+        checking.filter = ReadTransactionOptions {
+            include_subcategories: true,
+            tags: Some(TagFilter::Any(vec!["card".to_string()])),
+            ..categories
+        };
+
+        update_account(app.state(), checking.clone())
+            .await
+            .expect("failed to update account");
+
+        let mut transactions = get_transactions(app.state(), checking.id.clone(), None)
+            .await
+            .expect("failed to get transactions")
+            .into_iter()
+            .map(|transaction| transaction.inner.description)
+            .collect::<Vec<_>>();
+
+        transactions.sort();
+
+        assert_eq!(transactions, vec!["Fuel", "Groceries"]);
+    }
+
+    #[tokio::test]
+    pub async fn test_search_transactions() {
+        let (app, checking, savings) = setup().await;
+        let card = add_account(
+            app.state(),
+            AddAccountOptions {
+                currency: "USD".to_string(),
+                name: "Card".to_string(),
+                kind: AccountKind::CreditCard,
+            },
+        )
+        .await
+        .expect("failed to create account");
+
+        for (account, amount, description) in [
+            (&checking, -40, "AMAZON MKTP"),
+            (&card, 40, "Amazon refund"),
+            (&savings, -3, "amazon prime"),
+            (&checking, -5, "Bakery"),
+        ] {
+            add_transaction(
+                app.state(),
+                account.id.clone(),
+                AddTransactionOptions {
+                    amount: Money::from(amount),
+                    description: description.to_string(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("failed to add transaction");
+        }
+
+        let amazon = ReadTransactionOptions {
+            search: Some("amazon".to_string()),
+            ..Default::default()
+        };
+
+        let result = search_transactions(
+            app.state(),
+            SearchTransactionOptions {
+                filter: amazon.clone(),
+                sort: TransactionSort {
+                    key: SortKey::Amount,
+                    direction: SortDirection::Descending,
+                },
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("failed to search transactions");
+
+        assert_eq!(result.total, 3);
+        assert_eq!(
+            result
+                .hits
+                .iter()
+                .map(|hit| (
+                    hit.account_name.as_str(),
+                    hit.currency.as_str(),
+                    hit.transaction.inner.amount
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Card", "USD", Money::from(40)),
+                ("Savings", "EUR", Money::from(-3)),
+            ]
+        );
+
+        // Only the chosen accounts are searched.
+        let result = search_transactions(
+            app.state(),
+            SearchTransactionOptions {
+                filter: amazon.clone(),
+                accounts: vec![checking.id.clone(), card.id.clone()],
+                offset: 1,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("failed to search transactions");
+
+        assert_eq!(result.total, 2);
+        assert_eq!(result.hits.len(), 1);
+
+        // Transactions whose account is missing are still found.
+        {
+            let database = app.state::<tokio::sync::Mutex<Surreal<Db>>>();
+            let database = database.lock().await;
+
+            database
+                .query("DELETE $account")
+                .bind(("account", card.id))
+                .await
+                .expect("failed to delete account");
+        }
+
+        let result = search_transactions(
+            app.state(),
+            SearchTransactionOptions {
+                filter: amazon,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("failed to search transactions");
+
+        assert_eq!(result.total, 3);
+        assert_eq!(result.hits.len(), 3);
+        assert!(result.hits.iter().any(|hit| hit.account_name.is_empty()));
+    }
+}
@@ -2,16 +2,37 @@ import { invoke } from "@tauri-apps/api/core";
 import { AddTransactionOptions } from "../../../cli/bindings/AddTransactionOptions";
 import { AddTransactionTransferOptions } from "../../../cli/bindings/AddTransactionTransferOptions";
 import { Tag } from "../../../cli/bindings/Tag";
+import { TagUsage } from "../../../cli/bindings/TagUsage";
 import { TransactionWithId } from "../../../cli/bindings/TransactionWithId";
+import { DuplicateGroup } from "../../../cli/bindings/DuplicateGroup";
+import { DuplicateOptions } from "../../../cli/bindings/DuplicateOptions";
+import { CategorySuggestion } from "../../../cli/bindings/CategorySuggestion";
 import { Account } from "../../../cli/bindings/Account";
 import { BalanceOptions } from "../../../cli/bindings/BalanceOptions";
 import { Settings } from "../../../cli/bindings/Settings";
 import { CurrencyBalance } from "../../../cli/bindings/CurrencyBalance";
 import { AddAccountOptions } from "../../../cli/bindings/AddAccountOptions";
+import { ReadAccountsOptions } from "../../../cli/bindings/ReadAccountsOptions";
+import { BalanceAssertion } from "../../../cli/bindings/BalanceAssertion";
+import { CreateBalanceAssertionOptions } from "../../../cli/bindings/CreateBalanceAssertionOptions";
+import { FailedBalanceAssertion } from "../../../cli/bindings/FailedBalanceAssertion";
+import { Reconciliation } from "../../../cli/bindings/Reconciliation";
+import { ReconciliationSummary } from "../../../cli/bindings/ReconciliationSummary";
+import { StartReconciliationOptions } from "../../../cli/bindings/StartReconciliationOptions";
+import { CsvOptions } from "../../../cli/bindings/CsvOptions";
+import { CsvProfile } from "../../../cli/bindings/CsvProfile";
+import { CreateCsvProfileOptions } from "../../../cli/bindings/CreateCsvProfileOptions";
+import { PreviewRow } from "../../../cli/bindings/PreviewRow";
+import { ImportSummary } from "../../../cli/bindings/ImportSummary";
+import { EntryDate } from "../../../cli/bindings/EntryDate";
+import { QifOptions } from "../../../cli/bindings/QifOptions";
+import { QifPreview } from "../../../cli/bindings/QifPreview";
+import { QifCategoryMapping } from "../../../cli/bindings/QifCategoryMapping";
 import { AccountIdentifiers } from "../../../cli/bindings/AccountIdentifiers";
 import { CreateSplitBudgetOptions } from "../../../cli/bindings/CreateSplitBudgetOptions";
 import { Budget } from "../../../cli/bindings/Budget";
 import { CategoryWithId } from "../../../cli/bindings/CategoryWithId";
+import { CreateCategoryOptions } from "../../../cli/bindings/CreateCategoryOptions";
 import { CreateAllocationOptions } from "../../../cli/bindings/CreateAllocationOptions";
 import { UpdateAllocationOptions } from "../../../cli/bindings/UpdateAllocationOptions";
 import { CreatePartitionOptions } from "../../../cli/bindings/CreatePartitionOptions";
@@ -24,8 +45,32 @@ import { Currency } from "../../../cli/bindings/Currency";
 import { WriteTileOptions } from "../../../cli/bindings/WriteTileOptions";
 import { PortfolioTile } from "../../../cli/bindings/PortfolioTile";
 import { ReadTransactionOptions } from "../../../cli/bindings/ReadTransactionOptions";
+import { ReadTransactionPageOptions } from "../../../cli/bindings/ReadTransactionPageOptions";
+import { TransactionPage } from "../../../cli/bindings/TransactionPage";
+import { SearchTransactionOptions } from "../../../cli/bindings/SearchTransactionOptions";
+import { SearchResult } from "../../../cli/bindings/SearchResult";
 import { ReadCategoryOptions } from "../../../cli/bindings/ReadCategoryOptions";
 import { ReadCategoryResult } from "../../../cli/bindings/ReadCategoryResult";
+import { CreateExchangeRateOptions } from "../../../cli/bindings/CreateExchangeRateOptions";
+import { ExchangeRate } from "../../../cli/bindings/ExchangeRate";
+import { ReadExchangeRateOptions } from "../../../cli/bindings/ReadExchangeRateOptions";
+import { ConversionOptions } from "../../../cli/bindings/ConversionOptions";
+import { NetWorth } from "../../../cli/bindings/NetWorth";
+import { ReadTransferOptions } from "../../../cli/bindings/ReadTransferOptions";
+import { Transfer } from "../../../cli/bindings/Transfer";
+import { CreateRecurringOptions } from "../../../cli/bindings/CreateRecurringOptions";
+import { Recurring } from "../../../cli/bindings/Recurring";
+import { PreviewRecurringOptions } from "../../../cli/bindings/PreviewRecurringOptions";
+import { Occurrence } from "../../../cli/bindings/Occurrence";
+import { Rule } from "../../../cli/bindings/Rule";
+import { CreateRuleOptions } from "../../../cli/bindings/CreateRuleOptions";
+import { ApplyRulesOptions } from "../../../cli/bindings/ApplyRulesOptions";
+import { RuleChange } from "../../../cli/bindings/RuleChange";
+import { Payee } from "../../../cli/bindings/Payee";
+import { CreatePayeeOptions } from "../../../cli/bindings/CreatePayeeOptions";
+import { PayeeSpendingOptions } from "../../../cli/bindings/PayeeSpendingOptions";
+import { PayeeSpending } from "../../../cli/bindings/PayeeSpending";
+import { Attachment } from "../../../cli/bindings/Attachment";
 
 // TODO: could this be automated ?
 
@@ -40,13 +85,22 @@ export const getBalance = (
   accountId: RecordId,
   options?: BalanceOptions,
 ): Promise<number> => invoke("get_balance", { accountId, options });
-export const getAllBalance = (): Promise<CurrencyBalance[]> =>
-  invoke("get_all_balance");
+export const getAllBalance = (
+  options?: ReadAccountsOptions,
+): Promise<CurrencyBalance[]> => invoke("get_all_balance", { options });
 export const getTransactions = (
   accountId: RecordId,
   filter?: ReadTransactionOptions,
 ): Promise<TransactionWithId[]> =>
   invoke("get_transactions", { accountId, filter });
+export const getTransactionPage = (
+  accountId: RecordId,
+  options: ReadTransactionPageOptions,
+): Promise<TransactionPage> =>
+  invoke("get_transaction_page", { accountId, options });
+export const searchTransactions = (
+  options: SearchTransactionOptions,
+): Promise<SearchResult> => invoke("search_transactions", { options });
 export const addTransaction = (
   accountId: RecordId,
   options: AddTransactionOptions,
@@ -56,25 +110,188 @@ export const addTransactionTransfer = (
   options: AddTransactionTransferOptions,
 ): Promise<TransactionWithId> =>
   invoke("add_transaction_transfer", { options });
+export const getTransfers = (
+  options: ReadTransferOptions,
+): Promise<Transfer[]> => invoke("get_transfers", { options });
 export const updateTransaction = (
   transaction: TransactionWithId,
-): Promise<void> => invoke("update_transaction", { transaction });
-export const deleteTransaction = (transaction: RecordId): Promise<void> =>
-  invoke("delete_transaction", { transaction });
+  force?: boolean,
+): Promise<void> => invoke("update_transaction", { transaction, force });
+export const deleteTransaction = (
+  transaction: RecordId,
+  force?: boolean,
+): Promise<void> => invoke("delete_transaction", { transaction, force });
+export const getDuplicateTransactions = (
+  accountId: RecordId,
+  options?: DuplicateOptions,
+): Promise<DuplicateGroup[]> =>
+  invoke("get_duplicate_transactions", { accountId, options });
+export const mergeDuplicateTransactions = (
+  keep: RecordId,
+  duplicates: RecordId[],
+  force?: boolean,
+): Promise<TransactionWithId> =>
+  invoke("merge_duplicate_transactions", { keep, duplicates, force });
+export const suggestCategories = (
+  descriptions: string[],
+  limit?: number,
+): Promise<CategorySuggestion[][]> =>
+  invoke("suggest_categories", { descriptions, limit });
+
+// Imports.
+export const previewCsv = (
+  path: string,
+  options: CsvOptions,
+): Promise<PreviewRow[]> => invoke("preview_csv", { path, options });
+export const importTransactions = (
+  accountId: RecordId,
+  transactions: AddTransactionOptions[],
+): Promise<ImportSummary> =>
+  invoke("import_transactions", { accountId, transactions });
+export const importOfx = (
+  path: string,
+  accountId: RecordId,
+): Promise<ImportSummary> => invoke("import_ofx", { path, accountId });
+export const importCamt = (
+  path: string,
+  accountId: RecordId,
+  date?: EntryDate,
+): Promise<ImportSummary> => invoke("import_camt", { path, accountId, date });
+export const importMt940 = (
+  path: string,
+  accountId: RecordId,
+  date?: EntryDate,
+): Promise<ImportSummary> => invoke("import_mt940", { path, accountId, date });
+export const previewQif = (
+  path: string,
+  options: QifOptions,
+): Promise<QifPreview> => invoke("preview_qif", { path, options });
+export const exportQif = (
+  accountId: RecordId,
+  path: string,
+  options: QifOptions,
+): Promise<void> => invoke("export_qif", { accountId, path, options });
+export const getQifCategoryMappings = (): Promise<QifCategoryMapping[]> =>
+  invoke("get_qif_category_mappings");
+export const setQifCategoryMapping = (
+  mapping: QifCategoryMapping,
+): Promise<void> => invoke("set_qif_category_mapping", { mapping });
+export const deleteQifCategoryMapping = (name: string): Promise<void> =>
+  invoke("delete_qif_category_mapping", { name });
+export const addCsvProfile = (
+  options: CreateCsvProfileOptions,
+): Promise<CsvProfile> => invoke("add_csv_profile", { options });
+export const getCsvProfiles = (accountId: RecordId): Promise<CsvProfile[]> =>
+  invoke("get_csv_profiles", { accountId });
+export const updateCsvProfile = (profile: CsvProfile): Promise<void> =>
+  invoke("update_csv_profile", { profile });
+export const deleteCsvProfile = (profile: RecordId): Promise<void> =>
+  invoke("delete_csv_profile", { profile });
+
+// Reconciliations.
+export const startReconciliation = (
+  options: StartReconciliationOptions,
+): Promise<ReconciliationSummary> =>
+  invoke("start_reconciliation", { options });
+export const getReconciliations = (
+  accountId: RecordId,
+): Promise<Reconciliation[]> => invoke("get_reconciliations", { accountId });
+export const getReconciliationSummary = (
+  reconciliation: RecordId,
+): Promise<ReconciliationSummary> =>
+  invoke("get_reconciliation_summary", { reconciliation });
+export const toggleCleared = (
+  reconciliation: RecordId,
+  transaction: RecordId,
+): Promise<ReconciliationSummary> =>
+  invoke("toggle_cleared", { reconciliation, transaction });
+export const finishReconciliation = (
+  reconciliation: RecordId,
+): Promise<ReconciliationSummary> =>
+  invoke("finish_reconciliation", { reconciliation });
+export const cancelReconciliation = (reconciliation: RecordId): Promise<void> =>
+  invoke("cancel_reconciliation", { reconciliation });
+
+// Recurring transactions.
+export const addRecurringTransaction = (
+  options: CreateRecurringOptions,
+): Promise<Recurring> => invoke("add_recurring_transaction", { options });
+export const listRecurringTransactions = (
+  account?: RecordId,
+): Promise<Recurring[]> => invoke("list_recurring_transactions", { account });
+export const updateRecurringTransaction = (
+  recurring: Recurring,
+): Promise<void> => invoke("update_recurring_transaction", { recurring });
+export const deleteRecurringTransaction = (
+  recurring: RecordId,
+): Promise<void> => invoke("delete_recurring_transaction", { recurring });
+export const previewRecurringTransactions = (
+  options: PreviewRecurringOptions,
+): Promise<Occurrence[]> =>
+  invoke("preview_recurring_transactions", { options });
+export const applyRecurringTransactions = (): Promise<TransactionWithId[]> =>
+  invoke("apply_recurring_transactions");
+
+// Rules.
+export const addRule = (options: CreateRuleOptions): Promise<Rule> =>
+  invoke("add_rule", { options });
+export const getRules = (): Promise<Rule[]> => invoke("get_rules");
+export const updateRule = (rule: Rule): Promise<void> =>
+  invoke("update_rule", { rule });
+export const deleteRule = (rule: RecordId): Promise<void> =>
+  invoke("delete_rule", { rule });
+export const applyRules = (options: ApplyRulesOptions): Promise<RuleChange[]> =>
+  invoke("apply_rules", { options });
+
+// Payees.
+export const addPayee = (options: CreatePayeeOptions): Promise<Payee> =>
+  invoke("add_payee", { options });
+export const getPayees = (): Promise<Payee[]> => invoke("get_payees");
+export const updatePayee = (payee: Payee): Promise<void> =>
+  invoke("update_payee", { payee });
+export const deletePayee = (
+  payee: RecordId,
+  force?: boolean,
+): Promise<void> => invoke("delete_payee", { payee, force });
+export const assignPayees = (): Promise<number> => invoke("assign_payees");
+export const getPayeeSpending = (
+  options: PayeeSpendingOptions,
+): Promise<PayeeSpending[]> => invoke("get_payee_spending", { options });
+
+// Attachments.
+export const addAttachment = (
+  transaction: RecordId,
+  path: string,
+): Promise<Attachment> => invoke("add_attachment", { transaction, path });
+export const getAttachments = (transaction: RecordId): Promise<Attachment[]> =>
+  invoke("get_attachments", { transaction });
+export const openAttachment = (attachment: RecordId): Promise<void> =>
+  invoke("open_attachment", { attachment });
+export const removeAttachment = (attachment: RecordId): Promise<void> =>
+  invoke("remove_attachment", { attachment });
 
 // Categories.
-// TODO:
 export const getCategories = (): Promise<CategoryWithId[]> =>
   invoke("get_categories");
-// export const addCategory = (categoryId: RecordId, options: AddCategoryOptions): Promise<void> => invoke("add_category", { categoryId, options });
-// export const updateCategory = (transaction: TransactionWithId): Promise<void> => invoke("update_category", { transaction });
-// export const deleteCategory = (categoryId: RecordId): Promise<void> => invoke("delete_category", { categoryId });
+export const addCategory = (
+  options: CreateCategoryOptions,
+): Promise<CategoryWithId> => invoke("add_category", { options });
+export const updateCategory = (category: CategoryWithId): Promise<void> =>
+  invoke("update_category", { category });
+export const deleteCategory = (
+  category: RecordId,
+  replacement: RecordId,
+  force?: boolean,
+): Promise<void> =>
+  invoke("delete_category", { category, replacement, force });
 
 // Accounts.
-export const listAccounts = (): Promise<AccountIdentifiers[]> =>
-  invoke("list_accounts");
-export const listAccountsWithDetails = (): Promise<Account[]> =>
-  invoke("list_accounts_with_details");
+export const listAccounts = (
+  options?: ReadAccountsOptions,
+): Promise<AccountIdentifiers[]> => invoke("list_accounts", { options });
+export const listAccountsWithDetails = (
+  options?: ReadAccountsOptions,
+): Promise<Account[]> => invoke("list_accounts_with_details", { options });
 export const getAccount = (accountId: RecordId): Promise<Account> =>
   invoke("get_account", { accountId });
 export const addAccount = (options: AddAccountOptions): Promise<Account> =>
@@ -84,6 +301,19 @@ export const deleteAccount = (accountId: RecordId): Promise<void> =>
 // TODO: Make this Partial<Account>
 export const updateAccount = (account: Account): Promise<void> =>
   invoke("update_account", { account });
+export const addBalanceAssertion = (
+  options: CreateBalanceAssertionOptions,
+): Promise<BalanceAssertion> => invoke("add_balance_assertion", { options });
+export const getBalanceAssertions = (
+  accountId: RecordId,
+): Promise<BalanceAssertion[]> =>
+  invoke("get_balance_assertions", { accountId });
+export const deleteBalanceAssertion = (assertion: RecordId): Promise<void> =>
+  invoke("delete_balance_assertion", { assertion });
+export const checkBalanceAssertions = (
+  accountId: RecordId,
+): Promise<FailedBalanceAssertion[]> =>
+  invoke("check_balance_assertions", { accountId });
 
 // Budgets
 export const listBudgets = (): Promise<Budget[]> => invoke("list_budgets");
@@ -135,6 +365,22 @@ export const saveSettings = (settings: Settings): Promise<void> =>
 export const getTags = (): Promise<Tag[]> => invoke("get_tags");
 export const addTags = (tags: Tag[]): Promise<void> =>
   invoke("add_tags", { tags });
+export const renameTag = (
+  label: string,
+  newLabel: string,
+  force?: boolean,
+): Promise<void> => invoke("rename_tag", { label, newLabel, force });
+export const recolorTag = (label: string, color?: string): Promise<void> =>
+  invoke("recolor_tag", { label, color });
+export const mergeTags = (
+  labels: string[],
+  into: string,
+  force?: boolean,
+): Promise<void> => invoke("merge_tags", { labels, into, force });
+export const deleteTag = (label: string, force?: boolean): Promise<void> =>
+  invoke("delete_tag", { label, force });
+export const getTagsUsage = (): Promise<TagUsage[]> =>
+  invoke("get_tags_usage");
 
 // Backups.
 export const exportBackup = (): Promise<void> => invoke("export_backup");
@@ -157,3 +403,17 @@ export const updateTile = (tile: PortfolioTile): Promise<void> =>
   invoke("update_tile", { tile });
 export const removeTile = (id: RecordId): Promise<void> =>
   invoke("remove_tile", { id });
+
+// Exchange rates.
+export const addExchangeRate = (
+  options: CreateExchangeRateOptions,
+): Promise<ExchangeRate> => invoke("add_exchange_rate", { options });
+export const listExchangeRates = (
+  options?: ReadExchangeRateOptions,
+): Promise<ExchangeRate[]> => invoke("list_exchange_rates", { options });
+export const updateExchangeRate = (rate: ExchangeRate): Promise<void> =>
+  invoke("update_exchange_rate", { rate });
+export const deleteExchangeRate = (rate: RecordId): Promise<void> =>
+  invoke("delete_exchange_rate", { rate });
+export const getNetWorth = (options: ConversionOptions): Promise<NetWorth> =>
+  invoke("get_net_worth", { options });
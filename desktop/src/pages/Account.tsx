@@ -6,6 +6,7 @@ import {
   DialogContent,
   DialogTitle,
   Grid,
+  MenuItem,
   Snackbar,
   SnackbarCloseReason,
   TextField,
@@ -15,7 +16,8 @@ import Transactions from "./account/Transactions";
 import Summary from "./account/Summary";
 import { SyntheticEvent } from "react";
 import SettingsDrawer from "./account/Settings";
-import { Account } from "../../../cli/bindings/Account";
+import { AccountKind } from "../../../cli/bindings/AccountKind";
+import { AddAccountOptions } from "../../../cli/bindings/AddAccountOptions";
 import { AccountIdentifiers } from "../../../cli/bindings/AccountIdentifiers";
 import { useDispatchSnackbar } from "../contexts/Snackbar";
 import { useParams } from "react-router-dom";
@@ -25,6 +27,7 @@ import { Item } from "../components/form/CustomSelector";
 import CustomSelector from "../components/form/CustomSelector";
 import { useAccountStore } from "../stores/account";
 import { useSettingStore } from "../stores/setting";
+import { accountKinds } from "../utils";
 
 function AddAccountDialog({
   open,
@@ -36,10 +39,10 @@ function AddAccountDialog({
   const accountStore = useAccountStore();
   const navigate = useAccountNavigate();
   const dispatchSnackbar = useDispatchSnackbar()!;
-  const [form, setForm] = useState<Omit<Account, "id">>({
+  const [form, setForm] = useState<AddAccountOptions>({
     name: "",
     currency: "",
-    filter: {},
+    kind: "Checking",
   });
 
   const handleCloseForm = () => {
@@ -95,6 +98,24 @@ function AddAccountDialog({
               }
             />
           </Grid>
+          <Grid size={5}>
+            <TextField
+              select
+              id="account-kind"
+              label="Kind"
+              name="kind"
+              value={form.kind}
+              onChange={(kind) =>
+                setForm({ ...form, kind: kind.target.value as AccountKind })
+              }
+            >
+              {accountKinds.map(([kind, label]) => (
+                <MenuItem key={kind} value={kind}>
+                  {label}
+                </MenuItem>
+              ))}
+            </TextField>
+          </Grid>
         </Grid>
       </DialogContent>
       <DialogActions>
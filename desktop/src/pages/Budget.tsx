@@ -40,11 +40,12 @@ import { BudgetIdentifiers } from "../../../cli/bindings/BudgetIdentifiers";
 import { useBudgetNavigate } from "../hooks/budget";
 import { useDispatchSnackbar } from "../contexts/Snackbar";
 import { CreateSplitBudgetOptions } from "../../../cli/bindings/CreateSplitBudgetOptions";
-import { filterFloat } from "../utils";
+import { accountKinds, filterFloat } from "../utils";
 import { AccountIdentifiers } from "../../../cli/bindings/AccountIdentifiers";
 import { useParams } from "react-router-dom";
 import { Budget } from "../../../cli/bindings/Budget";
 import { Account } from "../../../cli/bindings/Account";
+import { AccountKind } from "../../../cli/bindings/AccountKind";
 import { categoryIconToMuiIcon } from "../utils/icons";
 import { AddAllocationDrawer, EditAllocationDrawer } from "./budget/Allocation";
 import PieChartIcon from "@mui/icons-material/PieChart";
@@ -86,6 +87,7 @@ function AddBudgetDialog({
     currency: "",
     accounts: [],
   });
+  const [kinds, setKinds] = useState<AccountKind[]>([]);
 
   // FIXME: filter using the backend.
   const filterAccountByCurrency = (
@@ -93,7 +95,9 @@ function AddBudgetDialog({
     currency: string,
   ) =>
     Array.from(accounts.values()).filter(
-      (account) => account.currency === currency,
+      (account) =>
+        account.currency === currency &&
+        (kinds.length === 0 || kinds.includes(account.kind)),
     );
 
   const handleCloseForm = () => {
@@ -185,6 +189,24 @@ function AddBudgetDialog({
               </Select>
             </Grid>
 
+            <Grid size={5}>
+              <InputLabel>Account kinds</InputLabel>
+              <Select
+                multiple
+                value={kinds}
+                label="Account kinds"
+                onChange={(event) =>
+                  setKinds(event.target.value as AccountKind[])
+                }
+              >
+                {accountKinds.map(([kind, label]) => (
+                  <MenuItem key={kind} value={kind}>
+                    {label}
+                  </MenuItem>
+                ))}
+              </Select>
+            </Grid>
+
             <Grid size={5}>
               {form.currency !== "" && (
                 <Autocomplete
@@ -96,6 +96,19 @@ export default function ({
               })
             }
           />
+          <TextField
+            id="opening-balance"
+            label="Opening balance"
+            variant="outlined"
+            type="number"
+            value={form.opening_balance}
+            onChange={(balance) =>
+              setForm({
+                ...form,
+                opening_balance: Number(balance.target.value),
+              })
+            }
+          />
         </Stack>
       </DialogContent>
       <DialogActions>
@@ -201,6 +201,7 @@ function AddTransactionDrawer({
         ...form,
         category,
         amount,
+        splits: [],
         date: form.date.toISOString(),
       })
       .catch((error) =>
@@ -298,6 +299,8 @@ function AddTransferDrawer({
   const transactionStore = useTransactionStore();
   const [form, setForm] = useState<{
     amount: string;
+    received: string;
+    fee: string;
     description: string;
     date: Dayjs;
     tags: Tag[];
@@ -305,6 +308,8 @@ function AddTransferDrawer({
     to: AccountIdentifiers | null;
   }>({
     amount: "0",
+    received: "",
+    fee: "",
     description: "",
     tags: [],
     // FIXME: should be re-run every time the UI is opened because it will
@@ -323,10 +328,20 @@ function AddTransferDrawer({
     if (isNaN(number)) return true;
     return number < 0;
   };
+  // Received amount and fee are optional.
+  const handleValidOptionalAmount = (amount: string) => {
+    if (amount === "") return false;
+    const number = filterFloat(amount);
+    if (isNaN(number)) return true;
+    return number < 0;
+  };
   const handleValidAccounts = () => !form.from || !form.to;
 
   const handleTransactionSubmission = async () => {
     const amount = filterFloat(form.amount);
+    const received =
+      form.received === "" ? undefined : filterFloat(form.received);
+    const fee = form.fee === "" ? undefined : filterFloat(form.fee);
     const from = form.from!.id;
     const to = form.to!.id;
 
@@ -336,6 +351,8 @@ function AddTransferDrawer({
         from,
         to,
         amount,
+        received,
+        fee,
         date: form.date.toISOString(),
       })
       .then(() => {
@@ -376,6 +393,42 @@ function AddTransferDrawer({
             error={handleValidAmount()}
             helperText={handleValidAmount() && "Not a valid amount"}
           />
+          <TextField
+            id="transaction-received"
+            label="Received amount"
+            name="received"
+            slotProps={{
+              inputLabel: {
+                shrink: true,
+              },
+            }}
+            value={form.received}
+            onChange={(received) =>
+              setForm({ ...form, received: received.target.value })
+            }
+            error={handleValidOptionalAmount(form.received)}
+            helperText={
+              handleValidOptionalAmount(form.received)
+                ? "Not a valid amount"
+                : "In the currency of the receiving account, converted with the exchange rate of the date if empty"
+            }
+          />
+          <TextField
+            id="transaction-fee"
+            label="Fee"
+            name="fee"
+            slotProps={{
+              inputLabel: {
+                shrink: true,
+              },
+            }}
+            value={form.fee}
+            onChange={(fee) => setForm({ ...form, fee: fee.target.value })}
+            error={handleValidOptionalAmount(form.fee)}
+            helperText={
+              handleValidOptionalAmount(form.fee) && "Not a valid amount"
+            }
+          />
           <DatePicker
             value={form.date}
             onChange={(date) => date && setForm({ ...form, date })}
@@ -401,15 +454,10 @@ function AddTransferDrawer({
             account={form.to}
             onChange={(to) => setForm({ ...form, to })}
             filter={(accounts) => {
-              const from = accounts.find(
-                (account) => account.id.id.String === form.from?.id.id.String,
-              );
-
-              return from
+              return form.from
                 ? accounts.filter(
                     (account) =>
-                      account.currency === from.currency &&
-                      account.id.id.String !== from.id.id.String,
+                      account.id.id.String !== form.from?.id.id.String,
                   )
                 : [];
             }}
@@ -422,7 +470,12 @@ function AddTransferDrawer({
           Cancel
         </Button>
         <Button
-          disabled={handleValidAmount() || handleValidAccounts()}
+          disabled={
+            handleValidAmount() ||
+            handleValidOptionalAmount(form.received) ||
+            handleValidOptionalAmount(form.fee) ||
+            handleValidAccounts()
+          }
           onClick={handleTransactionSubmission}
         >
           Add
@@ -578,13 +631,13 @@ export default function Transactions({ account }: { account: Account }) {
           />
 
           <ChipCategoryPicker
-            category={storeAccount.filter.category}
+            category={storeAccount.filter.categories[0]}
             onChange={async (category) => {
               const copy = {
                 ...storeAccount,
                 filter: {
                   ...storeAccount.filter,
-                  category: category?.id.id.String,
+                  categories: category ? [category.id] : [],
                 },
               };
               await commitAccount(copy);
@@ -1,5 +1,16 @@
+import { AccountKind } from "../../cli/bindings/AccountKind";
+
 export const filterFloat = (value: string) =>
     /^(-|\+)?([0-9]+(\.[0-9]+)?)$/.test(value.replace(",", "."))
       ? Number(value.replace(",", "."))
       : NaN;
-  
\ No newline at end of file
+  
+export const accountKinds: [AccountKind, string][] = [
+  ["Checking", "Checking"],
+  ["Savings", "Savings"],
+  ["CreditCard", "Credit card"],
+  ["Cash", "Cash"],
+  ["Loan", "Loan"],
+  ["Investment", "Investment"],
+  ["Asset", "Asset"],
+];